@@ -314,6 +314,28 @@ pub enum Cmd {
         /// Set how to compact the bottommost level
         bottommost: String,
     },
+    /// Force a column family's memtable to flush to disk
+    Flush {
+        #[structopt(
+            short = "d",
+            default_value = "kv",
+            possible_values = &["kv", "raft"],
+        )]
+        /// Which db to flush
+        db: String,
+
+        #[structopt(
+            short = "c",
+            default_value = CF_DEFAULT,
+            possible_values = &["default", "lock", "write"],
+        )]
+        /// The column family name.
+        cf: String,
+
+        #[structopt(long)]
+        /// Wait for the flush to finish before returning
+        wait: bool,
+    },
     /// Set some regions on the node to tombstone by manual
     Tombstone {
         #[structopt(
@@ -493,6 +515,57 @@ pub enum Cmd {
         /// The target region id
         region: u64,
     },
+    /// Propose split keys for a region based on a target piece size
+    SplitKeys {
+        #[structopt(short = "r")]
+        /// The target region id
+        region: u64,
+
+        #[structopt(long)]
+        /// Target size in bytes of each resulting piece
+        target_size: u64,
+    },
+    /// Compute a crc32 checksum over a region's data, for comparing replicas
+    Checksum {
+        #[structopt(short = "r")]
+        /// The target region id
+        region: u64,
+    },
+    /// Report how many write-cf MVCC versions in a region would be
+    /// reclaimable by GC at a given safe point
+    GcReclaimableMvcc {
+        #[structopt(short = "r")]
+        /// The target region id
+        region: u64,
+
+        #[structopt(long)]
+        /// The safe point to evaluate reclaimable versions against
+        safe_point: u64,
+    },
+    /// Dump a region's cf contents to a local SST file
+    DumpRegionSst {
+        #[structopt(short = "r")]
+        /// The target region id
+        region: u64,
+        #[structopt(short = "c", default_value = CF_DEFAULT, possible_values = &["default", "lock", "write"])]
+        /// The column family name
+        cf: String,
+        #[structopt(long)]
+        /// Path of the SST file to write
+        path: String,
+    },
+    /// Load a previously dumped region SST file directly into a cf
+    LoadRegionSst {
+        #[structopt(short = "r")]
+        /// The target region id
+        region: u64,
+        #[structopt(short = "c", default_value = CF_DEFAULT, possible_values = &["default", "lock", "write"])]
+        /// The column family name
+        cf: String,
+        #[structopt(long)]
+        /// Path of the SST file to load
+        path: String,
+    },
     /// Show range properties
     RangeProperties {
         #[structopt(long, default_value = "")]
@@ -645,6 +718,56 @@ pub enum Cmd {
         /// can be recorded in TiKV logs.
         min_start_ts: Option<u64>,
     },
+    /// Inspect the in-memory (range cache) engine. Only `offline` works; the
+    /// live-node subcommands print that they are not supported and exit
+    RangeCache {
+        #[structopt(subcommand)]
+        cmd: RangeCacheCmd,
+    },
+}
+
+#[derive(StructOpt)]
+pub enum RangeCacheCmd {
+    /// List the regions currently cached by the in-memory engine (not
+    /// supported: prints so and exits)
+    List {},
+    /// Evict a region from the in-memory engine (not supported: prints so
+    /// and exits)
+    Evict {
+        #[structopt(short = "r")]
+        /// The target region id
+        region: u64,
+    },
+    /// Load a region into the in-memory engine (not supported: prints so and
+    /// exits)
+    Load {
+        #[structopt(short = "r")]
+        /// The target region id
+        region: u64,
+    },
+    /// Trigger a gc run on the in-memory engine (not supported: prints so
+    /// and exits)
+    Gc {
+        #[structopt(long)]
+        /// The safe point to gc up to
+        safe_point: u64,
+    },
+    /// Dump the top-K most frequently read keys for a cached region (not
+    /// supported: prints so and exits; the node's
+    /// `/debug/range_cache/region/<id>/hot_keys` endpoint serves this when
+    /// `range-cache-engine.hot-key-collection-enabled` is on)
+    HotKeys {
+        #[structopt(short = "r", long)]
+        /// The target region id
+        region: u64,
+
+        #[structopt(long, default_value = "10")]
+        /// Number of hottest keys to print
+        top: usize,
+    },
+    /// Inspect which regions would be re-admitted to the in-memory engine
+    /// after a restart, using `--data-dir` instead of `--host`
+    Offline {},
 }
 
 #[derive(StructOpt)]
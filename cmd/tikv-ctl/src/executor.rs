@@ -572,13 +572,23 @@ pub trait DebugExecutor {
         db: DbType,
         cf: &str,
         region_id: u64,
+        from: Option<Vec<u8>>,
+        to: Option<Vec<u8>>,
         threads: u32,
         bottommost: BottommostLevelCompaction,
     ) {
         let region_local = self.get_region_info(region_id).region_local_state.unwrap();
         let r = region_local.get_region();
-        let from = keys::data_key(r.get_start_key());
-        let to = keys::data_end_key(r.get_end_key());
+        let region_start = keys::data_key(r.get_start_key());
+        let region_end = keys::data_end_key(r.get_end_key());
+        // Clamp the requested sub-range to the region's own range so a typo'd
+        // `from`/`to` can't make us compact data outside this region.
+        let from = from
+            .map(|k| std::cmp::max(keys::data_key(&k), region_start.clone()))
+            .unwrap_or(region_start);
+        let to = to
+            .map(|k| std::cmp::min(keys::data_end_key(&k), region_end.clone()))
+            .unwrap_or(region_end);
         self.do_compaction(db, cf, &from, &to, threads, bottommost);
         println!(
             "store:{:?} compact_region db:{:?} cf:{} range:[{:?}, {:?}) success!",
@@ -694,6 +704,18 @@ pub trait DebugExecutor {
         bottommost: BottommostLevelCompaction,
     );
 
+    fn flush(&self, address: Option<&str>, db: DbType, cf: &str, wait: bool);
+
+    fn get_region_approximate_split_keys(&self, region: u64, target_size: u64) -> Vec<Vec<u8>>;
+
+    fn get_region_checksum(&self, region: u64) -> u32;
+
+    fn get_region_mvcc_reclaimable_versions(&self, region: u64, safe_point: u64) -> (u64, u64);
+
+    fn dump_region_sst(&self, region: u64, cf: &str, path: &str);
+
+    fn load_region_sst(&self, region: u64, cf: &str, path: &str);
+
     fn set_region_tombstone(&self, regions: Vec<Region>);
 
     fn set_region_tombstone_by_id(&self, regions: Vec<u64>);
@@ -726,6 +748,84 @@ pub trait DebugExecutor {
     ) -> Result<(), (KeyRange, grpcio::Error)>;
 
     fn get_region_read_progress(&self, region_id: u64, log: bool, min_start_ts: u64);
+
+    // The range cache (in-memory) engine has no corresponding RPCs in
+    // `kvproto`'s debug service yet, so these can't be implemented against a
+    // live node today; the default impls just explain why and exit, the same
+    // way an unimplemented command would, rather than pretending to work.
+    fn range_cache_list(&self) {
+        range_cache_not_supported();
+    }
+
+    fn range_cache_evict(&self, _region: u64) {
+        range_cache_not_supported();
+    }
+
+    fn range_cache_load(&self, _region: u64) {
+        range_cache_not_supported();
+    }
+
+    fn range_cache_gc(&self, _safe_point: u64) {
+        range_cache_not_supported();
+    }
+
+    fn range_cache_hot_keys(&self, _region: u64, _top: usize) {
+        range_cache_not_supported();
+    }
+
+    // The in-memory engine keeps its admission state (which regions are
+    // cached, load/eviction history) purely in memory; the only part of it
+    // ever written to disk is the pinned/active range list, and only when
+    // `RangeCacheEngineConfig::persist_cached_region_list` is on (see
+    // `range_cache_memory_engine::persist`). So this can only read back that
+    // range list, not reconstruct the engine's full admission state.
+    fn range_cache_offline(&self, cfg: &TikvConfig) {
+        let range_cache_cfg = &cfg.range_cache_engine;
+        if !range_cache_cfg.persist_cached_region_list
+            || range_cache_cfg.cached_region_list_path.is_empty()
+        {
+            println!(
+                "range-cache offline inspection found nothing to read: \
+                 persist-cached-region-list is off (or cached-region-list-path is unset), so \
+                 the in-memory engine isn't persisting its cached range list to disk."
+            );
+            tikv_util::logger::exit_process_gracefully(-1);
+        }
+        match range_cache_memory_engine::persist::load_persisted_ranges(
+            &range_cache_cfg.cached_region_list_path,
+        ) {
+            Ok(ranges) if ranges.is_empty() => {
+                println!(
+                    "no cached ranges persisted at {}",
+                    range_cache_cfg.cached_region_list_path
+                );
+            }
+            Ok(ranges) => {
+                println!(
+                    "cached ranges persisted at {}:",
+                    range_cache_cfg.cached_region_list_path
+                );
+                for range in ranges {
+                    println!("{} -> {}", escape(&range.start), escape(&range.end));
+                }
+            }
+            Err(e) => {
+                println!(
+                    "failed to read persisted cached region list from {}: {:?}",
+                    range_cache_cfg.cached_region_list_path, e
+                );
+                tikv_util::logger::exit_process_gracefully(-1);
+            }
+        }
+    }
+}
+
+fn range_cache_not_supported() {
+    println!(
+        "range-cache subcommands are not supported yet: kvproto's debug service has no \
+         range cache RPCs. Use the node's /debug/range_cache HTTP endpoint in the meantime."
+    );
+    tikv_util::logger::exit_process_gracefully(-1);
 }
 
 impl DebugExecutor for DebugClient {
@@ -829,6 +929,32 @@ impl DebugExecutor for DebugClient {
             .unwrap_or_else(|e| perror_and_exit("DebugClient::compact", e));
     }
 
+    fn flush(&self, _address: Option<&str>, _db: DbType, _cf: &str, _wait: bool) {
+        unimplemented!("flush is only supported against a local data directory")
+    }
+
+    fn get_region_approximate_split_keys(&self, _region: u64, _target_size: u64) -> Vec<Vec<u8>> {
+        unimplemented!("get_region_approximate_split_keys is only supported against a local data directory")
+    }
+
+    fn get_region_checksum(&self, _region: u64) -> u32 {
+        unimplemented!("get_region_checksum is only supported against a local data directory")
+    }
+
+    fn get_region_mvcc_reclaimable_versions(&self, _region: u64, _safe_point: u64) -> (u64, u64) {
+        unimplemented!(
+            "get_region_mvcc_reclaimable_versions is only supported against a local data directory"
+        )
+    }
+
+    fn dump_region_sst(&self, _region: u64, _cf: &str, _path: &str) {
+        unimplemented!("dump_region_sst is only supported against a local data directory")
+    }
+
+    fn load_region_sst(&self, _region: u64, _cf: &str, _path: &str) {
+        unimplemented!("load_region_sst is only supported against a local data directory")
+    }
+
     fn dump_metrics(&self, tags: Vec<&str>) {
         let mut req = GetMetricsRequest::default();
         req.set_all(true);
@@ -1124,6 +1250,43 @@ where
             .unwrap_or_else(|e| perror_and_exit("Debugger::compact", e));
     }
 
+    fn flush(&self, address: Option<&str>, db: DbType, cf: &str, wait: bool) {
+        Debugger::flush(self, db, cf, wait)
+            .unwrap_or_else(|e| perror_and_exit("Debugger::flush", e));
+        println!(
+            "store:{:?} flush db:{:?} cf:{} success!",
+            address.unwrap_or("local"),
+            db,
+            cf,
+        );
+    }
+
+    fn get_region_approximate_split_keys(&self, region: u64, target_size: u64) -> Vec<Vec<u8>> {
+        Debugger::get_region_approximate_split_keys(self, region, target_size)
+            .unwrap_or_else(|e| perror_and_exit("Debugger::get_region_approximate_split_keys", e))
+    }
+
+    fn get_region_checksum(&self, region: u64) -> u32 {
+        Debugger::region_checksum(self, region)
+            .unwrap_or_else(|e| perror_and_exit("Debugger::region_checksum", e))
+    }
+
+    fn get_region_mvcc_reclaimable_versions(&self, region: u64, safe_point: u64) -> (u64, u64) {
+        Debugger::get_region_mvcc_reclaimable_versions(self, region, safe_point).unwrap_or_else(
+            |e| perror_and_exit("Debugger::get_region_mvcc_reclaimable_versions", e),
+        )
+    }
+
+    fn dump_region_sst(&self, region: u64, cf: &str, path: &str) {
+        Debugger::dump_region_sst(self, region, cf, path)
+            .unwrap_or_else(|e| perror_and_exit("Debugger::dump_region_sst", e))
+    }
+
+    fn load_region_sst(&self, region: u64, cf: &str, path: &str) {
+        Debugger::load_region_sst(self, region, cf, path)
+            .unwrap_or_else(|e| perror_and_exit("Debugger::load_region_sst", e))
+    }
+
     fn set_region_tombstone(&self, regions: Vec<Region>) {
         let ret = self
             .set_region_tombstone(regions)
@@ -1387,6 +1550,43 @@ impl<ER: RaftEngine> DebugExecutor for DebuggerImplV2<ER> {
             .unwrap_or_else(|e| perror_and_exit("Debugger::compact", e));
     }
 
+    fn flush(&self, address: Option<&str>, db: DbType, cf: &str, wait: bool) {
+        Debugger::flush(self, db, cf, wait)
+            .unwrap_or_else(|e| perror_and_exit("Debugger::flush", e));
+        println!(
+            "store:{:?} flush db:{:?} cf:{} success!",
+            address.unwrap_or("local"),
+            db,
+            cf,
+        );
+    }
+
+    fn get_region_approximate_split_keys(&self, region: u64, target_size: u64) -> Vec<Vec<u8>> {
+        Debugger::get_region_approximate_split_keys(self, region, target_size)
+            .unwrap_or_else(|e| perror_and_exit("Debugger::get_region_approximate_split_keys", e))
+    }
+
+    fn get_region_checksum(&self, region: u64) -> u32 {
+        Debugger::region_checksum(self, region)
+            .unwrap_or_else(|e| perror_and_exit("Debugger::region_checksum", e))
+    }
+
+    fn get_region_mvcc_reclaimable_versions(&self, region: u64, safe_point: u64) -> (u64, u64) {
+        Debugger::get_region_mvcc_reclaimable_versions(self, region, safe_point).unwrap_or_else(
+            |e| perror_and_exit("Debugger::get_region_mvcc_reclaimable_versions", e),
+        )
+    }
+
+    fn dump_region_sst(&self, region: u64, cf: &str, path: &str) {
+        Debugger::dump_region_sst(self, region, cf, path)
+            .unwrap_or_else(|e| perror_and_exit("Debugger::dump_region_sst", e))
+    }
+
+    fn load_region_sst(&self, region: u64, cf: &str, path: &str) {
+        Debugger::load_region_sst(self, region, cf, path)
+            .unwrap_or_else(|e| perror_and_exit("Debugger::load_region_sst", e))
+    }
+
     fn set_region_tombstone(&self, regions: Vec<Region>) {
         let ret = self
             .set_region_tombstone(regions)
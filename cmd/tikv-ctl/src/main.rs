@@ -517,13 +517,18 @@ fn main() {
                     let to_key = to.map(|k| unescape(&k));
                     let bottommost = BottommostLevelCompaction::from(Some(bottommost.as_ref()));
                     if let Some(region) = region {
-                        debug_executor
-                            .compact_region(host, db_type, &cf, region, threads, bottommost);
+                        debug_executor.compact_region(
+                            host, db_type, &cf, region, from_key, to_key, threads, bottommost,
+                        );
                     } else {
                         debug_executor
                             .compact(host, db_type, &cf, from_key, to_key, threads, bottommost);
                     }
                 }
+                Cmd::Flush { db, cf, wait } => {
+                    let db_type = if db == "kv" { DbType::Kv } else { DbType::Raft };
+                    debug_executor.flush(host, db_type, &cf, wait);
+                }
                 Cmd::Tombstone { regions, pd, force } => {
                     if let Some(pd_urls) = pd {
                         let cfg = PdConfig {
@@ -609,6 +614,31 @@ fn main() {
                     debug_executor.dump_metrics(tags)
                 }
                 Cmd::RegionProperties { region } => debug_executor.dump_region_properties(region),
+                Cmd::SplitKeys {
+                    region,
+                    target_size,
+                } => {
+                    let keys = debug_executor.get_region_approximate_split_keys(region, target_size);
+                    for key in keys {
+                        println!("{}", escape(&key));
+                    }
+                }
+                Cmd::Checksum { region } => {
+                    let checksum = debug_executor.get_region_checksum(region);
+                    println!("{:08x}", checksum);
+                }
+                Cmd::GcReclaimableMvcc { region, safe_point } => {
+                    let (total, reclaimable) =
+                        debug_executor.get_region_mvcc_reclaimable_versions(region, safe_point);
+                    println!("total versions: {}", total);
+                    println!("reclaimable versions: {}", reclaimable);
+                }
+                Cmd::DumpRegionSst { region, cf, path } => {
+                    debug_executor.dump_region_sst(region, &cf, &path);
+                }
+                Cmd::LoadRegionSst { region, cf, path } => {
+                    debug_executor.load_region_sst(region, &cf, &path);
+                }
                 Cmd::RangeProperties { start, end } => {
                     let start_key = from_hex(&start).unwrap();
                     let end_key = from_hex(&end).unwrap();
@@ -681,6 +711,16 @@ fn main() {
                         min_start_ts.unwrap_or_default(),
                     );
                 }
+                Cmd::RangeCache { cmd: subcmd } => match subcmd {
+                    RangeCacheCmd::List {} => debug_executor.range_cache_list(),
+                    RangeCacheCmd::Evict { region } => debug_executor.range_cache_evict(region),
+                    RangeCacheCmd::Load { region } => debug_executor.range_cache_load(region),
+                    RangeCacheCmd::Gc { safe_point } => debug_executor.range_cache_gc(safe_point),
+                    RangeCacheCmd::HotKeys { region, top } => {
+                        debug_executor.range_cache_hot_keys(region, top)
+                    }
+                    RangeCacheCmd::Offline {} => debug_executor.range_cache_offline(&cfg),
+                },
                 _ => {
                     unreachable!()
                 }
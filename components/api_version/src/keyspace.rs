@@ -41,6 +41,12 @@ impl From<u32> for KeyspaceId {
     }
 }
 
+impl KeyspaceId {
+    pub fn into_inner(self) -> u32 {
+        self.0
+    }
+}
+
 impl Keyspace for ApiV1 {
     fn make_kv_pair(p: (Vec<u8>, Vec<u8>)) -> Result<Self::KvPair> {
         Ok(p)
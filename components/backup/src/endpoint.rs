@@ -318,6 +318,7 @@ impl BackupRange {
         saver: async_channel::Sender<InMemBackupFiles<E::Local>>,
         storage_name: &str,
         resource_limiter: Option<Arc<ResourceLimiter>>,
+        enable_range_cache_engine: bool,
     ) -> Result<Statistics> {
         assert!(!self.codec.is_raw_kv);
 
@@ -362,6 +363,17 @@ impl BackupRange {
                 )
                 .map_err(MvccError::from)
                 .map_err(TxnError::from)?;
+            // `async_snapshot` only asks the range cache engine for a snapshot when
+            // `start_ts` is set (that's how it knows which ts the cache needs to cover),
+            // so without it a leader-served backup always falls back to the disk
+            // engine even when the region is fully cached. Set it here too, gated on
+            // the config, now that the lock check above has already guaranteed there's
+            // no in-memory lock blocking a read at `backup_ts`; the range cache engine
+            // still falls back to disk on its own if the region isn't Active for this
+            // ts or cached at all.
+            if enable_range_cache_engine {
+                snap_ctx.start_ts = Some(backup_ts);
+            }
         }
 
         let start_snapshot = Instant::now();
@@ -935,6 +947,7 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
         let concurrency_manager = self.concurrency_manager.clone();
         let batch_size = self.config_manager.0.read().unwrap().batch_size;
         let sst_max_size = self.config_manager.0.read().unwrap().sst_max_size.0;
+        let enable_range_cache_engine = self.config_manager.0.read().unwrap().enable_range_cache_engine;
         let limit = self.softlimit.limit();
         let resource_limiter = self.resource_ctl.as_ref().and_then(|r| {
             r.get_background_resource_limiter(&request.resource_group_name, &request.source_tag)
@@ -1032,6 +1045,7 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
                                 saver_tx.clone(),
                                 _backend.name(),
                                 resource_limiter.clone(),
+                                enable_range_cache_engine,
                             ), resource_limiter.clone())
                             .await
                     };
@@ -3,7 +3,7 @@
 use std::sync::{Arc, RwLock};
 
 use collections::HashMap;
-use engine_traits::KvEngine;
+use engine_traits::{CacheRange, KvEngine, RangeCacheEngineExt, SnapshotContext};
 use fail::fail_point;
 use kvproto::metapb::{Peer, Region};
 use raft::StateRole;
@@ -123,10 +123,44 @@ impl<E: KvEngine> CmdObserver<E> for CdcObserver {
         // whether to get it.
         let snapshot =
             RegionSnapshot::from_snapshot(Arc::new(engine.snapshot(None)), Arc::new(region));
-        let get_old_value = move |key,
+        // The cmd batches flushed together may span more than one region, so there
+        // is no single range we can use to take one range-cache-backed snapshot
+        // upfront. Instead, resolve the cached region (if any) per key below and
+        // fall back to the disk-only snapshot above otherwise.
+        let range_cache_engine_enabled = engine.range_cache_engine_enabled();
+        let engine = engine.clone();
+        let get_old_value = move |key: txn_types::Key,
                                   query_ts,
                                   old_value_cache: &mut OldValueCache,
                                   statistics: &mut Statistics| {
+            if range_cache_engine_enabled {
+                let data_key = keys::data_key(key.as_encoded());
+                if let Some(cached_region) = engine.get_region_for_key(&data_key) {
+                    let range = CacheRange::from_region(&cached_region);
+                    if range.contains_key(&data_key) {
+                        let snap_ctx = SnapshotContext {
+                            region_id: cached_region.get_id(),
+                            epoch_version: cached_region.get_region_epoch().get_version(),
+                            range: Some(range),
+                            read_ts: query_ts.into_inner(),
+                            force_disk_read: false,
+                        };
+                        let mut cached_region_with_peer = cached_region;
+                        cached_region_with_peer.mut_peers().push(Peer::default());
+                        let cache_snapshot = RegionSnapshot::from_snapshot(
+                            Arc::new(engine.snapshot(Some(snap_ctx))),
+                            Arc::new(cached_region_with_peer),
+                        );
+                        return old_value::get_old_value(
+                            &cache_snapshot,
+                            key,
+                            query_ts,
+                            old_value_cache,
+                            statistics,
+                        );
+                    }
+                }
+            }
             old_value::get_old_value(&snapshot, key, query_ts, old_value_cache, statistics)
         };
         if let Err(e) = self.sched.schedule(Task::MultiBatch {
@@ -567,6 +567,8 @@ pub fn get_range_stats(
         num_entries,
         num_versions: props.num_versions,
         num_rows: props.num_rows,
+        num_deletes: props.num_deletes,
+        max_row_versions: props.max_row_versions,
     })
 }
 
@@ -91,6 +91,11 @@ pub struct SnapshotContext {
     pub epoch_version: u64,
 
     pub read_ts: u64,
+    // When set, the range cache engine is not consulted even if it is enabled and
+    // otherwise able to serve this read; the snapshot is taken from the disk engine
+    // only. Used by callers such as GC that must see data the cache does not retain
+    // (e.g. versions below the safe point) and gain nothing from the cache.
+    pub force_disk_read: bool,
 }
 
 impl SnapshotContext {
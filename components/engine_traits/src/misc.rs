@@ -63,6 +63,10 @@ pub struct RangeStats {
     pub num_versions: u64,
     // The number of rows.
     pub num_rows: u64,
+    // The number of MVCC deletes of all rows.
+    pub num_deletes: u64,
+    // The maximal number of MVCC versions of a single row.
+    pub max_row_versions: u64,
 }
 
 pub trait MiscExt: CfNamesExt + FlowControlFactorsExt + WriteBatchExt {
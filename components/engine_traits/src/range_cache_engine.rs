@@ -5,6 +5,7 @@ use std::{
     result,
 };
 
+use collections::HashMap;
 use keys::{enc_end_key, enc_start_key};
 use kvproto::metapb::Region;
 
@@ -20,6 +21,11 @@ pub enum FailedReason {
     // epoch after ApplyRes is returned, so it's possible that IME's region epoch is
     // newer than raftstore's, so we still need to check epoch again in IME snapshot.
     EpochNotMatch,
+    // Only possible on a store admitted via `cache_on_learner`: a stale read on a
+    // learner peer asked for a `read_ts` beyond the region's resolved ts, so the
+    // cache can't guarantee the read wouldn't observe a write the stale read isn't
+    // supposed to see yet.
+    TooNewRead,
 }
 
 #[derive(Debug, PartialEq)]
@@ -50,6 +56,58 @@ pub enum EvictReason {
     DeleteRange,
     Merge,
     Disabled,
+    // The region (or the peer serving it on this store) was destroyed, e.g. by
+    // a `ConfChange` removing the peer or by applying a tombstone.
+    Destroyed,
+    // The peer entered hibernation, so the region is idle and its cached data
+    // is no longer worth the memory budget.
+    Hibernated,
+    // A flashback is about to rewrite the region's data wholesale, so any
+    // cached content would become stale.
+    Flashback,
+    // The local peer became a witness or a learner that never serves reads,
+    // e.g. via a conf change or a switch-witness admin command, so it no
+    // longer makes sense to keep the region cached on this store.
+    IneligiblePeer,
+    // Unsafe recovery is forcing a peer into leadership or executing a
+    // recovery plan step that can roll back applied state, so any cached
+    // data for the region may no longer match what's on disk.
+    UnsafeRecovery,
+    // An operator asked for the region to be evicted via a debug endpoint,
+    // rather than the engine deciding to evict it on its own.
+    Manual,
+    // A write touched a range configured for the write-around policy (see
+    // `RangeHintService`'s `write-policy` label), so the region is evicted
+    // instead of letting the write go through to the cache.
+    WriteAround,
+    // The consistency checker, checksum verification, or shadow reads found
+    // cached data diverging from the disk engine beyond the configured
+    // threshold, so every region is evicted and admission is paused as a
+    // precaution (see `RangeCacheMemoryEngine::fence_for_corruption`).
+    CorruptionDetected,
+}
+
+/// A snapshot of a `RangeCacheEngine`'s own state, read under a single
+/// critical section so the two fields can't be torn by a concurrent
+/// eviction, split, or GC in between. See `RangeCacheEngine::consistency_snapshot`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheConsistencySnapshot {
+    // The minimum per-region safe point across every cached region, i.e. the
+    // oldest point below which the cache guarantees no data has been
+    // filtered away by GC. `u64::MAX` means nothing is cached.
+    pub safe_point: u64,
+    // Region epoch (the `version` component) of every cached region, keyed
+    // by region id, as of the moment this snapshot was taken.
+    pub region_epochs: HashMap<u64, u64>,
+}
+
+impl Default for CacheConsistencySnapshot {
+    fn default() -> Self {
+        Self {
+            safe_point: u64::MAX,
+            region_epochs: HashMap::default(),
+        }
+    }
 }
 
 /// RangeCacheEngine works as a range cache caching some ranges (in Memory or
@@ -85,12 +143,87 @@ pub trait RangeCacheEngine:
         false
     }
 
+    // Whether a region's cached data should be evicted once its peer enters
+    // hibernation. Disabled by default so that loading a hibernating region
+    // back into the cache on wake-up isn't forced onto every deployment.
+    fn should_evict_on_hibernate(&self) -> bool {
+        false
+    }
+
     fn on_region_event(&self, event: RegionEvent);
+
+    // Returns the approximate size and key count of `range`, if it is exactly
+    // covered by a single `Active` cached region. Split check uses this to skip
+    // a disk property scan when the region's true size is already known
+    // precisely by the memory engine. `None` means "ask the disk engine
+    // instead", either because the range isn't (fully) cached or because this
+    // implementation doesn't track per-region stats.
+    fn region_cached_size_and_keys(&self, _range: &CacheRange) -> Option<(u64, u64)> {
+        None
+    }
+
+    // Record that a snapshot request for `region_id` was served out of the
+    // cache, for diagnostics (see `RangeCacheMemoryEngine::region_cache_stats`).
+    // Default no-op so implementations that don't track this don't need to
+    // care.
+    fn record_region_cache_hit(&self, _region_id: u64) {}
+
+    // Record that a snapshot request for `region_id` fell back to the disk
+    // engine rather than being served out of the cache. See
+    // `record_region_cache_hit`.
+    fn record_region_cache_miss(&self, _region_id: u64) {}
+
+    // Suspend admission of new regions into the cache. Used while the store
+    // is in import mode for a bulk load, since data an import is about to
+    // rewrite isn't worth admitting. Regions already cached keep serving
+    // reads; only new admission (stats-driven load and PD range hints) is
+    // affected.
+    fn pause_admission(&self) {}
+
+    // Undo `pause_admission`.
+    fn resume_admission(&self) {}
+
+    // Takes a `CacheConsistencySnapshot` of this engine's own state: its
+    // overall safe point and every cached region's epoch, read together so
+    // they can't be torn by a concurrent eviction, split, or GC. The caller
+    // pairs it with a disk-engine sequence number read right afterwards (see
+    // `HybridEngine::consistency_barrier`); nothing holds off writes between
+    // the two reads.
+    fn consistency_snapshot(&self) -> CacheConsistencySnapshot {
+        CacheConsistencySnapshot::default()
+    }
+
+    // Fences the engine off after the caller (the consistency checker, checksum
+    // verification, or shadow reads) detected cached data diverging from the
+    // disk engine beyond its configured threshold. See
+    // `RangeCacheMemoryEngine::fence_for_corruption` for what this does.
+    fn fence_for_corruption(&self, _reason: &str) {}
 }
 
 pub trait RangeCacheEngineExt {
     fn range_cache_engine_enabled(&self) -> bool;
 
+    fn should_evict_on_hibernate(&self) -> bool {
+        false
+    }
+
+    // See `RangeCacheEngine::pause_admission`.
+    fn pause_range_cache_admission(&self) {}
+
+    // See `RangeCacheEngine::resume_admission`.
+    fn resume_range_cache_admission(&self) {}
+
+    // See `RangeCacheEngine::fence_for_corruption`.
+    fn fence_range_cache_for_corruption(&self, _reason: &str) {}
+
+    // Returns the region whose cached range currently covers the data key `key`
+    // (i.e. a key already encoded with `keys::data_key`), if the range cache
+    // engine has one loaded. Lets callers that only hold a raw key decide
+    // whether a cache-backed read of it is worth attempting.
+    fn get_region_for_key(&self, _key: &[u8]) -> Option<Region> {
+        None
+    }
+
     // TODO(SpadeA): try to find a better way to reduce coupling degree of range
     // cache engine and kv engine
     fn on_region_event(&self, event: RegionEvent);
@@ -19,4 +19,16 @@ where
     fn range_cache_engine_hit(&self) -> bool {
         false
     }
+
+    /// The range cache engine's load generation for the region this snapshot
+    /// was taken against, if it was served (even partially) from the range
+    /// cache engine. This changes across an evict-then-reload of the region
+    /// even when nothing else about the snapshot's data version would, so
+    /// callers that cache a response keyed on data version (e.g. the
+    /// coprocessor cache) should fold it in to avoid serving a stale response
+    /// across such a transition. `None` if the range cache engine wasn't
+    /// consulted for this snapshot.
+    fn range_cache_load_generation(&self) -> Option<u64> {
+        None
+    }
 }
@@ -140,6 +140,24 @@ impl std::ops::AddAssign for IoBytes {
     }
 }
 
+thread_local! {
+    static CACHE_READ_BYTES: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// Records bytes read from an in-memory cache (e.g. the range cache engine)
+/// that bypassed disk I/O entirely, so callers that attribute resource usage
+/// from IO byte counts, like the resource control layer's IO-based limiter,
+/// don't see such reads as free.
+pub fn record_cache_read_bytes(bytes: u64) {
+    CACHE_READ_BYTES.with(|c| c.set(c.get() + bytes));
+}
+
+/// Returns and resets the calling thread's accumulated
+/// `record_cache_read_bytes` total.
+pub fn take_cache_read_bytes() -> u64 {
+    CACHE_READ_BYTES.with(|c| c.replace(0))
+}
+
 #[repr(u32)]
 #[derive(Debug, Clone, PartialEq, Copy, EnumCount)]
 pub enum IoPriority {
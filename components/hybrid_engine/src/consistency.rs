@@ -0,0 +1,42 @@
+// Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
+
+use collections::HashMap;
+use engine_traits::{CacheConsistencySnapshot, KvEngine, MiscExt, RangeCacheEngine};
+
+use crate::engine::HybridEngine;
+
+/// An observation point across both engines: the RocksDB sequence number and
+/// the range cache engine's own state (its overall safe point and every
+/// cached region's epoch), read back to back. See
+/// `HybridEngine::consistency_barrier` for how closely the two are matched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsistencyBarrier {
+    pub rocksdb_seqno: u64,
+    pub cache_safe_point: u64,
+    pub region_epochs: HashMap<u64, u64>,
+}
+
+impl<EK, EC> HybridEngine<EK, EC>
+where
+    EK: KvEngine,
+    EC: RangeCacheEngine,
+{
+    /// Takes a `ConsistencyBarrier`. The range cache engine's own fields are
+    /// read together under one critical section (see
+    /// `RangeCacheEngine::consistency_snapshot`), so they can't have been torn
+    /// by a concurrent eviction, split, or GC. The RocksDB seqno is read right
+    /// after that critical section ends; writes are not paused in between, so
+    /// a write landing between the two reads leaves `rocksdb_seqno` newer than
+    /// the cache state it is paired with.
+    pub fn consistency_barrier(&self) -> ConsistencyBarrier {
+        let CacheConsistencySnapshot {
+            safe_point,
+            region_epochs,
+        } = self.range_cache_engine().consistency_snapshot();
+        ConsistencyBarrier {
+            rocksdb_seqno: self.disk_engine().get_latest_sequence_number(),
+            cache_safe_point: safe_point,
+            region_epochs,
+        }
+    }
+}
@@ -93,9 +93,26 @@ where
     type Snapshot = HybridEngineSnapshot<EK, EC>;
 
     fn snapshot(&self, ctx: Option<SnapshotContext>) -> Self::Snapshot {
+        // Even a lease read that ends up served entirely out of the range
+        // cache still needs a real disk snapshot here: its sequence number is
+        // the consistency anchor passed into the range cache engine's own
+        // snapshot below, and `disk_snap` doubles as the fallback reader for
+        // any CF the cache doesn't serve (see `HybridEngineSnapshot`) or for
+        // a read that turns out not to be cacheable. `get_latest_sequence_number`
+        // would avoid pinning a snapshot, but the number it returns keeps
+        // advancing after it's read, so it can't substitute for a pinned,
+        // stable view the way `disk_snap.sequence_number()` does here.
+        // Acquiring a RocksDB snapshot itself is a cheap, in-memory operation
+        // (just an atomic read of the current sequence number), so there's no
+        // meaningful per-read cost left to cut by skipping it.
         let disk_snap = self.disk_engine.snapshot(ctx.clone());
         let range_cache_snap = if !self.range_cache_engine.enabled() {
             None
+        } else if ctx.as_ref().is_some_and(|ctx| ctx.force_disk_read) {
+            RANGE_CACHEN_SNAPSHOT_ACQUIRE_FAILED_REASON_COUNT_STAIC
+                .force_disk_read
+                .inc();
+            None
         } else if let Some(ctx) = ctx {
             match self.range_cache_engine.snapshot(
                 ctx.region_id,
@@ -106,24 +123,40 @@ where
             ) {
                 Ok(snap) => {
                     SNAPSHOT_TYPE_COUNT_STATIC.range_cache_engine.inc();
+                    self.range_cache_engine
+                        .record_region_cache_hit(ctx.region_id);
                     Some(snap)
                 }
                 Err(FailedReason::TooOldRead) => {
                     RANGE_CACHEN_SNAPSHOT_ACQUIRE_FAILED_REASON_COUNT_STAIC
                         .too_old_read
                         .inc();
+                    self.range_cache_engine
+                        .record_region_cache_miss(ctx.region_id);
                     None
                 }
                 Err(FailedReason::NotCached) => {
                     RANGE_CACHEN_SNAPSHOT_ACQUIRE_FAILED_REASON_COUNT_STAIC
                         .not_cached
                         .inc();
+                    self.range_cache_engine
+                        .record_region_cache_miss(ctx.region_id);
+                    None
+                }
+                Err(FailedReason::TooNewRead) => {
+                    RANGE_CACHEN_SNAPSHOT_ACQUIRE_FAILED_REASON_COUNT_STAIC
+                        .too_new_read
+                        .inc();
+                    self.range_cache_engine
+                        .record_region_cache_miss(ctx.region_id);
                     None
                 }
                 Err(FailedReason::EpochNotMatch) => {
                     RANGE_CACHEN_SNAPSHOT_ACQUIRE_FAILED_REASON_COUNT_STAIC
                         .epoch_not_match
                         .inc();
+                    self.range_cache_engine
+                        .record_region_cache_miss(ctx.region_id);
                     None
                 }
             }
@@ -253,6 +286,7 @@ mod tests {
             epoch_version: 0,
             read_ts: 15,
             range: Some(range.clone()),
+            force_disk_read: false,
         };
         let s = hybrid_engine.snapshot(Some(snap_ctx.clone()));
         assert!(s.range_cache_snapshot_available());
@@ -261,6 +295,12 @@ mod tests {
         let s = hybrid_engine.snapshot(Some(snap_ctx.clone()));
         assert!(!s.range_cache_snapshot_available());
 
+        snap_ctx.read_ts = 15;
+        snap_ctx.force_disk_read = true;
+        let s = hybrid_engine.snapshot(Some(snap_ctx.clone()));
+        assert!(!s.range_cache_snapshot_available());
+        snap_ctx.force_disk_read = false;
+
         let mut config_manager = RangeCacheConfigManager(config.clone());
         let mut config_change = ConfigChange::new();
         config_change.insert(String::from("enabled"), ConfigValue::Bool(false));
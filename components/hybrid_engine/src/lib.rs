@@ -7,6 +7,7 @@ mod cf_names;
 mod cf_options;
 mod checkpoint;
 mod compact;
+mod consistency;
 mod db_options;
 mod db_vector;
 mod engine;
@@ -29,5 +30,6 @@ mod ttl_properties;
 pub mod util;
 mod write_batch;
 
+pub use consistency::ConsistencyBarrier;
 pub use engine::HybridEngine;
 pub use snapshot::HybridEngineSnapshot;
@@ -18,7 +18,9 @@ make_auto_flush_static_metric! {
         no_read_ts,
         not_cached,
         too_old_read,
+        too_new_read,
         epoch_not_match,
+        force_disk_read,
     }
 
     pub struct FailedReasonCountVec: LocalIntCounter {
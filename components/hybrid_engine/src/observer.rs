@@ -3,12 +3,17 @@
 use std::sync::{Arc, Mutex};
 
 use engine_traits::{CacheRange, EvictReason, KvEngine, RangeCacheEngineExt, RegionEvent};
-use kvproto::{metapb::Region, raft_cmdpb::AdminCmdType, raft_serverpb::RaftApplyState};
+use kvproto::{
+    metapb::{PeerRole, Region},
+    raft_cmdpb::AdminCmdType,
+    raft_serverpb::RaftApplyState,
+};
 use raft::StateRole;
 use raftstore::coprocessor::{
     AdminObserver, ApplyCtxInfo, BoxAdminObserver, BoxCmdObserver, BoxQueryObserver,
-    BoxRoleObserver, Cmd, CmdBatch, CmdObserver, Coprocessor, CoprocessorHost, ObserveLevel,
-    ObserverContext, QueryObserver, RegionState, RoleObserver,
+    BoxRegionChangeObserver, BoxRoleObserver, Cmd, CmdBatch, CmdObserver, Coprocessor,
+    CoprocessorHost, ObserveLevel, ObserverContext, QueryObserver, RegionChangeEvent,
+    RegionChangeObserver, RegionChangeReason, RegionState, RoleObserver,
 };
 
 #[derive(Clone)]
@@ -19,13 +24,17 @@ pub struct Observer {
     // TODO: change Observer's interface to `&mut self`.
     pending_events: Arc<Mutex<Vec<RegionEvent>>>,
     cache_engine: Arc<dyn RangeCacheEngineExt + Send + Sync>,
+    // The local store ID, used to find this store's own peer in a region's peer
+    // list. 0 means unknown and disables the role-change eviction check below.
+    store_id: u64,
 }
 
 impl Observer {
-    pub fn new(cache_engine: Arc<dyn RangeCacheEngineExt + Send + Sync>) -> Self {
+    pub fn new(cache_engine: Arc<dyn RangeCacheEngineExt + Send + Sync>, store_id: u64) -> Self {
         Observer {
             pending_events: Arc::default(),
             cache_engine,
+            store_id,
         }
     }
 
@@ -47,10 +56,26 @@ impl Observer {
         coprocessor_host
             .registry
             .register_role_observer(priority, BoxRoleObserver::new(self.clone()));
+        // Evict cache when a peer hibernates, if configured to do so.
+        coprocessor_host
+            .registry
+            .register_region_change_observer(priority, BoxRegionChangeObserver::new(self.clone()));
 
         // NB: We do not evict the cache when applying a snapshot because
         // the peer must be in the follower role during this process.
         // The cache is already evicted when the leader steps down.
+
+        // NB: `sst_importer` ingests SST files straight into the disk engine
+        // with `ingest_external_file_cf`, bypassing the normal per-key write
+        // path the cache otherwise observes. This looks like a gap, but it
+        // isn't one in practice: the query observer above buffers an eviction
+        // for the ingested range in `post_exec_cmd` while the command is
+        // still being executed, and `ApplyContext::write_to_db` flushes that
+        // buffered eviction (`on_flush_applied_cmd_batch`) before invoking
+        // the command's callback, even though the actual disk-level ingest
+        // happens earlier in the same `write_to_db` call. So the eviction is
+        // always visible to the cache before any client can observe a read
+        // that reflects the ingested data.
     }
 
     fn post_exec_cmd(
@@ -64,26 +89,40 @@ impl Observer {
             return;
         }
         // Evict caches for successfully executed ingest commands and admin
-        // commands that change region range.
+        // commands that change region range or invalidate its cached data.
         //
         // NB: We do not evict the cache for region splits, as the split ranges
         // still contain the latest data and hot regions are often split.
         // Evicting the cache for region splits is not worthwhile and may cause
         // performance regression due to frequent loading and evicting of
         // hot regions.
-        if apply.pending_handle_ssts.is_some()
-            || (state.modified_region.is_some()
-                && matches!(
-                    cmd.request.get_admin_request().get_cmd_type(),
-                    AdminCmdType::PrepareMerge | AdminCmdType::CommitMerge
-                ))
+        let admin_cmd_type = cmd.request.get_admin_request().get_cmd_type();
+        // A flashback is about to rewrite the region's data wholesale, so evict
+        // before it proceeds; `PrepareFlashback` runs before any of the rewrite
+        // commands are applied, and `FinishFlashback` just flips the region back
+        // out of the flashback state, so there's no need to evict again there.
+        let evict_reason = if apply.pending_handle_ssts.is_some() {
+            Some(EvictReason::Merge)
+        } else if state.modified_region.is_some()
+            && matches!(
+                admin_cmd_type,
+                AdminCmdType::PrepareMerge | AdminCmdType::CommitMerge
+            )
+        {
+            Some(EvictReason::Merge)
+        } else if state.modified_region.is_some() && admin_cmd_type == AdminCmdType::PrepareFlashback
         {
-            let range = CacheRange::from_region(ctx.region());
+            Some(EvictReason::Flashback)
+        } else {
+            None
+        };
+        if let Some(reason) = evict_reason {
             tikv_util::info!(
                 "evict range due to apply commands";
                 "region_id" => ctx.region().get_id(),
                 "is_ingest_sst" => apply.pending_handle_ssts.is_some(),
-                "admin_command" => ?cmd.request.get_admin_request().get_cmd_type(),
+                "admin_command" => ?admin_cmd_type,
+                "reason" => ?reason,
                 "start_key" => ?log_wrappers::Value(&ctx.region().start_key),
                 "end_key" => ?log_wrappers::Value(&ctx.region().end_key),
             );
@@ -92,7 +131,7 @@ impl Observer {
                 .unwrap()
                 .push(RegionEvent::Eviction {
                     region: ctx.region().clone(),
-                    reason: EvictReason::Merge,
+                    reason,
                 });
         }
         // there are new_regions, this must be a split event.
@@ -128,6 +167,30 @@ impl Observer {
         }
     }
 
+    fn evict_range_on_hibernate(&self, region: &Region) {
+        if !self.cache_engine.range_cache_engine_enabled()
+            || !self.cache_engine.should_evict_on_hibernate()
+        {
+            return;
+        }
+
+        tikv_util::info!(
+           "evict region because the peer hibernated";
+           "region_id" => region.get_id(),
+           "epoch" => ?region.get_region_epoch(),
+           "start_key" => ?log_wrappers::Value(&region.start_key),
+           "end_key" => ?log_wrappers::Value(&region.end_key),
+        );
+        // Unlike cache invalidation driven by applying a raft command, hibernation
+        // is not tied to the cmd-batch flush cycle, so there is no guarantee
+        // `on_flush_cmd` will run again soon. Apply the eviction directly instead
+        // of going through `pending_events`.
+        self.cache_engine.on_region_event(RegionEvent::Eviction {
+            region: region.clone(),
+            reason: EvictReason::Hibernated,
+        });
+    }
+
     fn evict_range_on_leader_steps_down(&self, region: &Region) {
         if !self.cache_engine.range_cache_engine_enabled() {
             return;
@@ -149,6 +212,66 @@ impl Observer {
                 reason: EvictReason::BecomeFollower,
             });
     }
+
+    // Witness peers hold no data and some learners never serve reads, so a
+    // region is no longer worth caching once the local peer becomes one of
+    // those, e.g. via a conf change or a switch-witness admin command.
+    fn local_peer_is_ineligible(&self, region: &Region) -> bool {
+        if self.store_id == 0 {
+            return false;
+        }
+        region
+            .get_peers()
+            .iter()
+            .find(|p| p.get_store_id() == self.store_id)
+            .is_some_and(|p| p.get_is_witness() || p.get_role() == PeerRole::Learner)
+    }
+
+    fn evict_range_on_unsafe_recovery(&self, region: &Region) {
+        if !self.cache_engine.range_cache_engine_enabled() {
+            return;
+        }
+
+        tikv_util::info!(
+           "evict region for unsafe recovery";
+           "region_id" => region.get_id(),
+           "epoch" => ?region.get_region_epoch(),
+           "start_key" => ?log_wrappers::Value(&region.start_key),
+           "end_key" => ?log_wrappers::Value(&region.end_key),
+        );
+        // Unsafe recovery doesn't go through the normal apply-cmd-batch flush
+        // cycle (force leader mutates raft state directly, and a recovery plan
+        // step may run before any command is ever applied), so there is no
+        // guarantee `on_flush_cmd` will run again soon. Apply the eviction
+        // directly, same as `evict_range_on_hibernate`.
+        self.cache_engine.on_region_event(RegionEvent::Eviction {
+            region: region.clone(),
+            reason: EvictReason::UnsafeRecovery,
+        });
+    }
+
+    fn evict_range_on_ineligible_role(&self, region: &Region) {
+        if !self.cache_engine.range_cache_engine_enabled()
+            || !self.local_peer_is_ineligible(region)
+        {
+            return;
+        }
+
+        tikv_util::info!(
+           "evict region because the local peer is no longer eligible for caching";
+           "region_id" => region.get_id(),
+           "epoch" => ?region.get_region_epoch(),
+           "start_key" => ?log_wrappers::Value(&region.start_key),
+           "end_key" => ?log_wrappers::Value(&region.end_key),
+        );
+        self.pending_events
+            .lock()
+            .unwrap()
+            .push(RegionEvent::Eviction {
+                region: region.clone(),
+                reason: EvictReason::IneligiblePeer,
+            });
+    }
 }
 
 impl Coprocessor for Observer {}
@@ -199,6 +322,26 @@ impl RoleObserver for Observer {
     }
 }
 
+impl RegionChangeObserver for Observer {
+    fn on_region_changed(
+        &self,
+        ctx: &mut ObserverContext<'_>,
+        event: RegionChangeEvent,
+        _: StateRole,
+    ) {
+        match event {
+            RegionChangeEvent::Hibernate => self.evict_range_on_hibernate(ctx.region()),
+            RegionChangeEvent::Update(
+                RegionChangeReason::ChangePeer | RegionChangeReason::SwitchWitness,
+            ) => self.evict_range_on_ineligible_role(ctx.region()),
+            RegionChangeEvent::Update(RegionChangeReason::UnsafeRecovery) => {
+                self.evict_range_on_unsafe_recovery(ctx.region())
+            }
+            _ => {}
+        }
+    }
+}
+
 impl<E> CmdObserver<E> for Observer {
     fn on_flush_applied_cmd_batch(
         &self,
@@ -227,12 +370,16 @@ mod tests {
     #[derive(Default)]
     struct MockRangeCacheEngine {
         enabled: AtomicBool,
+        evict_on_hibernate: AtomicBool,
         region_events: Arc<Mutex<Vec<RegionEvent>>>,
     }
     impl RangeCacheEngineExt for MockRangeCacheEngine {
         fn range_cache_engine_enabled(&self) -> bool {
             self.enabled.load(Ordering::Relaxed)
         }
+        fn should_evict_on_hibernate(&self) -> bool {
+            self.evict_on_hibernate.load(Ordering::Relaxed)
+        }
         fn on_region_event(&self, event: RegionEvent) {
             self.region_events.lock().unwrap().push(event);
         }
@@ -246,10 +393,18 @@ mod tests {
         request
     }
 
+    fn new_admin_request_prepare_flashback() -> RaftCmdRequest {
+        let mut request = RaftCmdRequest::default();
+        request
+            .mut_admin_request()
+            .set_cmd_type(AdminCmdType::PrepareFlashback);
+        request
+    }
+
     #[test]
     fn test_do_not_evict_range_region_split() {
         let cache_engine = Arc::new(MockRangeCacheEngine::default());
-        let observer = Observer::new(cache_engine.clone());
+        let observer = Observer::new(cache_engine.clone(), 0);
 
         let mut region = Region::default();
         region.set_id(1);
@@ -282,7 +437,7 @@ mod tests {
     #[test]
     fn test_evict_range_ingest_sst() {
         let cache_engine = Arc::new(MockRangeCacheEngine::default());
-        let observer = Observer::new(cache_engine.clone());
+        let observer = Observer::new(cache_engine.clone(), 0);
 
         let mut region = Region::default();
         region.set_id(1);
@@ -325,4 +480,138 @@ mod tests {
         };
         assert_eq!(&cache_engine.region_events.lock().unwrap()[0], &expected);
     }
+
+    #[test]
+    fn test_evict_range_prepare_flashback() {
+        let cache_engine = Arc::new(MockRangeCacheEngine::default());
+        let observer = Observer::new(cache_engine.clone(), 0);
+
+        let mut region = Region::default();
+        region.set_id(1);
+        region.mut_peers().push(Peer::default());
+        let mut ctx = ObserverContext::new(&region);
+
+        let mut pending_handle_ssts = None;
+        let mut delete_ssts = Vec::new();
+        let mut pending_delete_ssts = Vec::new();
+        let mut apply = ApplyCtxInfo {
+            pending_handle_ssts: &mut pending_handle_ssts,
+            delete_ssts: &mut delete_ssts,
+            pending_delete_ssts: &mut pending_delete_ssts,
+        };
+        let request = new_admin_request_prepare_flashback();
+        let response = RaftCmdResponse::default();
+        let cmd = Cmd::new(0, 0, request, response);
+        let state = RegionState {
+            peer_id: 0,
+            pending_remove: false,
+            modified_region: Some(region.clone()),
+            new_regions: vec![],
+        };
+
+        cache_engine.enabled.store(true, Ordering::Relaxed);
+        observer.post_exec_cmd(&mut ctx, &cmd, &state, &mut apply);
+        observer.on_flush_cmd();
+        let expected = RegionEvent::Eviction {
+            region,
+            reason: EvictReason::Flashback,
+        };
+        assert_eq!(&cache_engine.region_events.lock().unwrap()[0], &expected);
+    }
+
+    #[test]
+    fn test_evict_range_on_hibernate() {
+        let cache_engine = Arc::new(MockRangeCacheEngine::default());
+        let observer = Observer::new(cache_engine.clone(), 0);
+
+        let mut region = Region::default();
+        region.set_id(1);
+        region.mut_peers().push(Peer::default());
+        let mut ctx = ObserverContext::new(&region);
+
+        cache_engine.enabled.store(true, Ordering::Relaxed);
+
+        // Must not evict when `evict_on_hibernate` is disabled.
+        observer.on_region_changed(&mut ctx, RegionChangeEvent::Hibernate, StateRole::Leader);
+        assert!(cache_engine.region_events.lock().unwrap().is_empty());
+
+        cache_engine.evict_on_hibernate.store(true, Ordering::Relaxed);
+        observer.on_region_changed(&mut ctx, RegionChangeEvent::Hibernate, StateRole::Leader);
+        let expected = RegionEvent::Eviction {
+            region,
+            reason: EvictReason::Hibernated,
+        };
+        assert_eq!(&cache_engine.region_events.lock().unwrap()[0], &expected);
+    }
+
+    #[test]
+    fn test_evict_range_on_ineligible_role() {
+        let cache_engine = Arc::new(MockRangeCacheEngine::default());
+        let observer = Observer::new(cache_engine.clone(), 1);
+        cache_engine.enabled.store(true, Ordering::Relaxed);
+
+        let mut region = Region::default();
+        region.set_id(1);
+        let mut local_peer = Peer::default();
+        local_peer.set_store_id(1);
+        region.mut_peers().push(local_peer);
+        let mut ctx = ObserverContext::new(&region);
+
+        // Must not evict while the local peer is a normal voter.
+        observer.on_region_changed(
+            &mut ctx,
+            RegionChangeEvent::Update(RegionChangeReason::ChangePeer),
+            StateRole::Leader,
+        );
+        observer.on_flush_cmd();
+        assert!(cache_engine.region_events.lock().unwrap().is_empty());
+
+        // The local peer became a learner via a conf change.
+        let mut learner_region = region.clone();
+        learner_region.mut_peers()[0].set_role(PeerRole::Learner);
+        let mut ctx = ObserverContext::new(&learner_region);
+        observer.on_region_changed(
+            &mut ctx,
+            RegionChangeEvent::Update(RegionChangeReason::ChangePeer),
+            StateRole::Leader,
+        );
+        observer.on_flush_cmd();
+        let expected = RegionEvent::Eviction {
+            region: learner_region,
+            reason: EvictReason::IneligiblePeer,
+        };
+        assert_eq!(&cache_engine.region_events.lock().unwrap()[0], &expected);
+    }
+
+    #[test]
+    fn test_evict_range_on_unsafe_recovery() {
+        let cache_engine = Arc::new(MockRangeCacheEngine::default());
+        let observer = Observer::new(cache_engine.clone(), 0);
+
+        let mut region = Region::default();
+        region.set_id(1);
+        region.mut_peers().push(Peer::default());
+        let mut ctx = ObserverContext::new(&region);
+
+        // Must not evict when range cache engine is disabled.
+        observer.on_region_changed(
+            &mut ctx,
+            RegionChangeEvent::Update(RegionChangeReason::UnsafeRecovery),
+            StateRole::Leader,
+        );
+        assert!(cache_engine.region_events.lock().unwrap().is_empty());
+
+        // Applied directly, without waiting for `on_flush_cmd`.
+        cache_engine.enabled.store(true, Ordering::Relaxed);
+        observer.on_region_changed(
+            &mut ctx,
+            RegionChangeEvent::Update(RegionChangeReason::UnsafeRecovery),
+            StateRole::Leader,
+        );
+        let expected = RegionEvent::Eviction {
+            region,
+            reason: EvictReason::UnsafeRecovery,
+        };
+        assert_eq!(&cache_engine.region_events.lock().unwrap()[0], &expected);
+    }
 }
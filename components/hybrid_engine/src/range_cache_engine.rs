@@ -1,6 +1,7 @@
 // Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
 
 use engine_traits::{KvEngine, RangeCacheEngine, RangeCacheEngineExt, RegionEvent};
+use kvproto::metapb::Region;
 
 use crate::HybridEngine;
 
@@ -13,8 +14,33 @@ where
         true
     }
 
+    #[inline]
+    fn should_evict_on_hibernate(&self) -> bool {
+        self.range_cache_engine().should_evict_on_hibernate()
+    }
+
+    #[inline]
+    fn get_region_for_key(&self, key: &[u8]) -> Option<Region> {
+        self.range_cache_engine().get_region_for_key(key)
+    }
+
     #[inline]
     fn on_region_event(&self, event: RegionEvent) {
         self.range_cache_engine().on_region_event(event);
     }
+
+    #[inline]
+    fn pause_range_cache_admission(&self) {
+        self.range_cache_engine().pause_admission();
+    }
+
+    #[inline]
+    fn resume_range_cache_admission(&self) {
+        self.range_cache_engine().resume_admission();
+    }
+
+    #[inline]
+    fn fence_range_cache_for_corruption(&self, reason: &str) {
+        self.range_cache_engine().fence_for_corruption(reason);
+    }
 }
@@ -1,6 +1,6 @@
 // Copyright 2023 TiKV Project Authors. Licensed under Apache-2.0.
 
-use engine_traits::{KvEngine, Range, RangeCacheEngine, RangePropertiesExt, Result};
+use engine_traits::{CacheRange, KvEngine, Range, RangeCacheEngine, RangePropertiesExt, Result};
 
 use crate::engine::HybridEngine;
 
@@ -10,6 +10,13 @@ where
     EC: RangeCacheEngine,
 {
     fn get_range_approximate_keys(&self, range: Range<'_>, large_threshold: u64) -> Result<u64> {
+        let cache_range = CacheRange::new(range.start_key.to_vec(), range.end_key.to_vec());
+        if let Some((_, keys)) = self
+            .range_cache_engine()
+            .region_cached_size_and_keys(&cache_range)
+        {
+            return Ok(keys);
+        }
         self.disk_engine()
             .get_range_approximate_keys(range, large_threshold)
     }
@@ -25,6 +32,13 @@ where
     }
 
     fn get_range_approximate_size(&self, range: Range<'_>, large_threshold: u64) -> Result<u64> {
+        let cache_range = CacheRange::new(range.start_key.to_vec(), range.end_key.to_vec());
+        if let Some((size, _)) = self
+            .range_cache_engine()
+            .region_cached_size_and_keys(&cache_range)
+        {
+            return Ok(size);
+        }
         self.disk_engine()
             .get_range_approximate_size(range, large_threshold)
     }
@@ -51,6 +51,12 @@ where
     fn range_cache_engine_hit(&self) -> bool {
         self.range_cache_snap.is_some()
     }
+
+    fn range_cache_load_generation(&self) -> Option<u64> {
+        self.range_cache_snap
+            .as_ref()
+            .and_then(|s| s.range_cache_load_generation())
+    }
 }
 
 impl<EK, EC> Debug for HybridEngineSnapshot<EK, EC>
@@ -180,6 +186,7 @@ mod tests {
             epoch_version: 0,
             range: Some(CacheRange::from_region(&region)),
             read_ts: 10,
+            force_disk_read: false,
         };
         let snap = hybrid_engine.snapshot(Some(ctx));
         {
@@ -48,6 +48,12 @@ impl<EK: KvEngine> WriteBatch for HybridEngineWriteBatch<EK> {
             .disk_write_batch
             .write_callback_opt(opts, |s| {
                 if !called.fetch_or(true, Ordering::SeqCst) {
+                    // `s` is the RocksDB sequence number assigned to the disk write we just
+                    // made -- the very same write that advances this region's raft applied
+                    // index. Reusing it here, instead of deriving a separate mapping from
+                    // applied index to sequence number, is what keeps the cache's view
+                    // consistent with disk: both land with the same sequence number in the
+                    // same underlying RocksDB instance.
                     self.cache_write_batch.set_sequence_number(s).unwrap();
                     self.cache_write_batch.write_opt(opts).unwrap();
                 }
@@ -195,6 +201,7 @@ mod tests {
             epoch_version: 0,
             range: Some(CacheRange::from_region(&region)),
             read_ts: 10,
+            force_disk_read: false,
         };
         let snap = hybrid_engine.snapshot(Some(ctx));
         let actual: &[u8] = &snap.get_value(b"zhello").unwrap().unwrap();
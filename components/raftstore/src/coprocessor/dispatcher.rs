@@ -785,12 +785,18 @@ impl<E: KvEngine> CoprocessorHost<E> {
             role
         );
     }
-    pub fn on_region_heartbeat(&self, region: &Region, region_stat: &RegionStat) {
+    pub fn on_region_heartbeat(
+        &self,
+        region: &Region,
+        region_stat: &RegionStat,
+        bucket_stat: Option<&BucketStat>,
+    ) {
         loop_ob!(
             region,
             &self.registry.region_heartbeat_observers,
             on_region_heartbeat,
-            region_stat
+            region_stat,
+            bucket_stat
         );
     }
 
@@ -19,7 +19,7 @@ use kvproto::{
     },
     raft_serverpb::RaftApplyState,
 };
-use pd_client::RegionStat;
+use pd_client::{BucketStat, RegionStat};
 use raft::{eraftpb, StateRole};
 
 pub mod config;
@@ -43,13 +43,13 @@ pub use self::{
     },
     error::{Error, Result},
     region_info_accessor::{
-        Callback as RegionInfoCallback, RangeKey, RegionCollector, RegionInfo, RegionInfoAccessor,
-        RegionInfoProvider, SeekRegionCallback,
+        Callback as RegionInfoCallback, RangeKey, RegionActivity, RegionCollector, RegionInfo,
+        RegionInfoAccessor, RegionInfoProvider, SeekRegionCallback,
     },
     split_check::{
-        get_region_approximate_keys, get_region_approximate_middle, get_region_approximate_size,
-        HalfCheckObserver, Host as SplitCheckerHost, KeysCheckObserver, SizeCheckObserver,
-        TableCheckObserver,
+        get_approximate_split_keys, get_region_approximate_keys, get_region_approximate_middle,
+        get_region_approximate_size, HalfCheckObserver, Host as SplitCheckerHost,
+        KeysCheckObserver, SizeCheckObserver, TableCheckObserver,
     },
 };
 pub use crate::store::{Bucket, KeyEntry};
@@ -321,6 +321,10 @@ pub enum RegionChangeReason {
     RollbackMerge,
     SwitchWitness,
     Flashback,
+    // Unsafe recovery is about to force a peer into leadership or execute a
+    // recovery plan step (demote/destroy) that can roll back applied state,
+    // e.g. by truncating raft log entries the region cache already reflects.
+    UnsafeRecovery,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -329,6 +333,9 @@ pub enum RegionChangeEvent {
     Update(RegionChangeReason),
     Destroy,
     UpdateBuckets(usize),
+    // The peer has stopped ticking and entered hibernation because the region
+    // has been idle. Unlike `Update`, this does not change the region itself.
+    Hibernate,
 }
 
 pub trait RegionChangeObserver: Coprocessor {
@@ -354,7 +361,16 @@ pub trait RegionChangeObserver: Coprocessor {
     }
 }
 pub trait RegionHeartbeatObserver: Coprocessor {
-    fn on_region_heartbeat(&self, _: &mut ObserverContext<'_>, _: &RegionStat) {}
+    /// `bucket_stat` is the region's latest reported bucket stats, if any,
+    /// letting observers see intra-region read/write skew in addition to the
+    /// whole-region totals in `region_stat`.
+    fn on_region_heartbeat(
+        &self,
+        _: &mut ObserverContext<'_>,
+        _: &RegionStat,
+        _bucket_stat: Option<&BucketStat>,
+    ) {
+    }
 }
 
 pub trait MessageObserver: Coprocessor {
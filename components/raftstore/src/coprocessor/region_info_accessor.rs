@@ -16,7 +16,7 @@ use collections::{HashMap, HashSet};
 use engine_traits::KvEngine;
 use itertools::Itertools;
 use kvproto::metapb::Region;
-use pd_client::RegionStat;
+use pd_client::{BucketStat, RegionStat};
 use raft::StateRole;
 use tikv_util::{
     box_err, debug, info, warn,
@@ -110,6 +110,11 @@ impl RegionInfo {
 #[derive(Clone, Debug)]
 pub struct RegionActivity {
     pub region_stat: RegionStat,
+    // The region's latest reported bucket stats, if any. `None` until the leader has reported
+    // buckets at least once (e.g. a freshly split or very small region may never have them).
+    // Lets consumers see intra-region read/write skew that `region_stat` averages away, e.g. to
+    // find the hot sub-range of a region before bucket-level caching exists to act on it.
+    pub bucket_stat: Option<BucketStat>,
     // TODO: add region's MVCC version/tombstone count to measure effectiveness of the in-memory
     // cache for that region's data. This information could be collected from rocksdb, see:
     // collection_regions_to_compact.
@@ -167,6 +172,10 @@ pub enum RegionInfoQuery {
         count: usize,
         callback: Callback<TopRegions>,
     },
+    GetRegionActivity {
+        region_id: u64,
+        callback: Callback<Option<RegionActivity>>,
+    },
     /// Gets all contents from the collection. Only used for testing.
     DebugDump(mpsc::Sender<(RegionsMap, RegionRangesMap)>),
 }
@@ -192,6 +201,9 @@ impl Display for RegionInfoQuery {
             RegionInfoQuery::GetTopRegions { count, .. } => {
                 write!(f, "GetTopRegions(count: {})", count)
             }
+            RegionInfoQuery::GetRegionActivity { region_id, .. } => {
+                write!(f, "GetRegionActivity(region_id: {})", region_id)
+            }
             RegionInfoQuery::DebugDump(_) => write!(f, "DebugDump"),
         }
     }
@@ -245,16 +257,25 @@ impl RoleObserver for RegionEventListener {
 }
 
 impl RegionHeartbeatObserver for RegionEventListener {
-    fn on_region_heartbeat(&self, context: &mut ObserverContext<'_>, region_stat: &RegionStat) {
+    fn on_region_heartbeat(
+        &self,
+        context: &mut ObserverContext<'_>,
+        region_stat: &RegionStat,
+        bucket_stat: Option<&BucketStat>,
+    ) {
         if !(self.region_stats_manager_enabled_cb)() {
             // Region stats manager is disabled, return early.
             return;
         }
         let region = context.region().clone();
         let region_stat = region_stat.clone();
+        let bucket_stat = bucket_stat.cloned();
         let event = RaftStoreEvent::UpdateRegionActivity {
             region,
-            activity: RegionActivity { region_stat },
+            activity: RegionActivity {
+                region_stat,
+                bucket_stat,
+            },
         };
 
         self.scheduler
@@ -542,6 +563,14 @@ impl RegionCollector {
         callback(self.regions.get(&region_id).cloned());
     }
 
+    pub fn handle_get_region_activity(
+        &self,
+        region_id: u64,
+        callback: Callback<Option<RegionActivity>>,
+    ) {
+        callback(self.region_activity.get(&region_id).cloned());
+    }
+
     // It returns the regions covered by [start_key, end_key]
     pub fn handle_get_regions_in_range(
         &self,
@@ -703,6 +732,12 @@ impl Runnable for RegionCollector {
             RegionInfoQuery::GetTopRegions { count, callback } => {
                 self.handle_get_top_regions(count, callback);
             }
+            RegionInfoQuery::GetRegionActivity {
+                region_id,
+                callback,
+            } => {
+                self.handle_get_region_activity(region_id, callback);
+            }
             RegionInfoQuery::DebugDump(tx) => {
                 tx.send((self.regions.clone(), self.region_ranges.clone()))
                     .unwrap();
@@ -833,6 +868,13 @@ pub trait RegionInfoProvider: Send + Sync {
     fn get_top_regions(&self, _count: Option<NonZeroUsize>) -> Result<TopRegions> {
         unimplemented!()
     }
+
+    /// Gets the most recently reported activity for `region_id`, including
+    /// per-bucket stats if the region has reported buckets, or `None` if no
+    /// heartbeat has been recorded for it yet.
+    fn region_activity(&self, _region_id: u64) -> Result<Option<RegionActivity>> {
+        Ok(None)
+    }
 }
 
 impl RegionInfoProvider for RegionInfoAccessor {
@@ -927,6 +969,28 @@ impl RegionInfoProvider for RegionInfoAccessor {
                 })
             })
     }
+    fn region_activity(&self, region_id: u64) -> Result<Option<RegionActivity>> {
+        let (tx, rx) = mpsc::channel();
+        let msg = RegionInfoQuery::GetRegionActivity {
+            region_id,
+            callback: Box::new(move |activity| {
+                if let Err(e) = tx.send(activity) {
+                    warn!("failed to send region_activity result: {:?}", e);
+                }
+            }),
+        };
+        self.scheduler
+            .schedule(msg)
+            .map_err(|e| box_err!("failed to send request to region collector: {:?}", e))
+            .and_then(|_| {
+                rx.recv().map_err(|e| {
+                    box_err!(
+                        "failed to receive region_activity result from region collector: {:?}",
+                        e
+                    )
+                })
+            })
+    }
 }
 
 // Use in tests only.
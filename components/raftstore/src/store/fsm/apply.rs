@@ -29,9 +29,10 @@ use batch_system::{
 use collections::{HashMap, HashMapEntry, HashSet};
 use crossbeam::channel::{TryRecvError, TrySendError};
 use engine_traits::{
-    util::SequenceNumber, DeleteStrategy, KvEngine, Mutable, PerfContext, PerfContextKind,
-    RaftEngine, RaftEngineReadOnly, Range as EngineRange, Snapshot, SstMetaInfo, WriteBatch,
-    WriteOptions, ALL_CFS, CF_DEFAULT, CF_LOCK, CF_RAFT, CF_WRITE,
+    util::SequenceNumber, CacheRange, DeleteStrategy, KvEngine, Mutable, PerfContext,
+    PerfContextKind, RaftEngine, RaftEngineReadOnly, Range as EngineRange, Snapshot,
+    SnapshotContext, SstMetaInfo, WriteBatch, WriteOptions, ALL_CFS, CF_DEFAULT, CF_LOCK, CF_RAFT,
+    CF_WRITE,
 };
 use fail::fail_point;
 use health_controller::types::LatencyInspector;
@@ -71,6 +72,7 @@ use tikv_util::{
 };
 use time::Timespec;
 use tracker::{TrackerToken, TrackerTokenArray, GLOBAL_TRACKERS};
+use txn_types::TimeStamp;
 use uuid::Builder as UuidBuilder;
 
 use self::memtrace::*;
@@ -1974,6 +1976,10 @@ where
         let start_key = keys::data_key(s_key);
         // Use delete_files_in_range to drop as many sst files as possible, this
         // is a way to reclaim disk space quickly after drop a table/index.
+        //
+        // This is also the apply path for `Storage::delete_range` and raw `delete_range`,
+        // so on `EK = HybridEngine` each `delete_ranges_cf` call below already evicts the
+        // overlapping cached regions ahead of the disk-side delete.
         if !notify_only {
             let range = vec![EngineRange::new(&start_key, &end_key)];
             let fail_f = |e: engine_traits::Error, strategy: DeleteStrategy| {
@@ -3459,6 +3465,13 @@ pub fn check_sst_for_ingestion(sst: &SstMeta, region: &Region) -> Result<()> {
     }
 
     let range = sst.get_range();
+    if !range.get_end().is_empty() && range.get_start() >= range.get_end() {
+        return Err(box_err!(
+            "invalid sst range, start {:?} >= end {:?}",
+            range.get_start(),
+            range.get_end()
+        ));
+    }
     util::check_key_in_region(range.get_start(), region)?;
     util::check_key_in_region(range.get_end(), region)?;
 
@@ -4258,8 +4271,19 @@ where
             self.delegate.last_flush_applied_index = applied_index;
         }
 
+        // If the whole region is cached, read it out of the range cache engine
+        // at the latest visible version instead of scanning the disk engine,
+        // the same way a lease read would, so sending a snapshot for a hot
+        // region doesn't have to pay for a RocksDB scan.
+        let snap_ctx = SnapshotContext {
+            range: Some(CacheRange::from_region(&self.delegate.region)),
+            region_id: self.delegate.region_id(),
+            epoch_version: self.delegate.region.get_region_epoch().get_version(),
+            read_ts: TimeStamp::max().into_inner(),
+            force_disk_read: false,
+        };
         if let Err(e) = snap_task.generate_and_schedule_snapshot::<EK>(
-            apply_ctx.engine.snapshot(None),
+            apply_ctx.engine.snapshot(Some(snap_ctx)),
             self.delegate.applied_term,
             self.delegate.apply_state.clone(),
             &apply_ctx.region_scheduler,
@@ -4328,10 +4352,25 @@ where
                 if apply_ctx.kv_wb().count() > 0 {
                     apply_ctx.commit(&mut self.delegate);
                 }
+                // Log backup's initial scan (`ObserverType::Pitr`) re-reads the
+                // write and default CFs for every region it starts observing,
+                // which can be most of the regions in the cluster. Let it use
+                // the range cache engine the same way `handle_snapshot` does,
+                // with the usual automatic fallback to disk for anything the
+                // cache doesn't cover. CDC and RTS capture the same data
+                // incrementally afterwards rather than re-scanning it, so
+                // there's no analogous hot-path benefit for them here.
+                let snap_ctx = matches!(ty, ObserverType::Pitr(_)).then(|| SnapshotContext {
+                    range: Some(CacheRange::from_region(&self.delegate.region)),
+                    region_id: self.delegate.region_id(),
+                    epoch_version: self.delegate.region.get_region_epoch().get_version(),
+                    read_ts: TimeStamp::max().into_inner(),
+                    force_disk_read: false,
+                });
                 ReadResponse {
                     response: Default::default(),
                     snapshot: Some(RegionSnapshot::from_snapshot(
-                        Arc::new(apply_ctx.engine.snapshot(None)),
+                        Arc::new(apply_ctx.engine.snapshot(snap_ctx)),
                         Arc::new(self.delegate.region.clone()),
                     )),
                     txn_extra_op: TxnExtraOp::Noop,
@@ -831,6 +831,14 @@ where
             return;
         }
 
+        // Demoting failed voters rewrites the region's peer list, which can
+        // invalidate cached data for the region. Evict before proposing it.
+        self.ctx.coprocessor_host.on_region_changed(
+            self.region(),
+            RegionChangeEvent::Update(RegionChangeReason::UnsafeRecovery),
+            self.fsm.peer.get_role(),
+        );
+
         if self.fsm.peer.in_joint_state() {
             info!(
                 "Unsafe recovery, already in joint state, exit first";
@@ -1788,6 +1796,17 @@ where
         );
         assert_eq!(self.fsm.peer.get_role(), StateRole::Candidate);
 
+        // Forcing leadership without a real quorum can be followed by a
+        // recovery plan step that rolls back applied state, e.g. by
+        // truncating raft log entries the region cache already reflects. Evict
+        // before that can happen, rather than waiting for the normal apply
+        // pipeline to notice a change.
+        self.ctx.coprocessor_host.on_region_changed(
+            self.fsm.peer.region(),
+            RegionChangeEvent::Update(RegionChangeReason::UnsafeRecovery),
+            self.fsm.peer.get_role(),
+        );
+
         let failed_stores = match self.fsm.peer.force_leader.take() {
             Some(ForceLeaderState::PreForceLeader { failed_stores, .. }) => failed_stores,
             _ => unreachable!(),
@@ -2361,6 +2380,11 @@ where
             "peer_id" => self.fsm.peer_id(),
             "election_elapsed" => self.fsm.peer.raft_group.raft.election_elapsed);
         self.fsm.reset_hibernate_state(GroupState::Idle);
+        self.ctx.coprocessor_host.on_region_changed(
+            self.fsm.peer.region(),
+            RegionChangeEvent::Hibernate,
+            self.fsm.peer.get_role(),
+        );
         // Followers will stop ticking at L789. Keep ticking for followers
         // to allow it to campaign quickly when abnormal situation is detected.
         if !self.fsm.peer.is_leader() {
@@ -638,6 +638,14 @@ pub enum CasualMessage<EK: KvEngine> {
     },
 
     // Try renew leader lease
+    //
+    // Note this only runs a ReadIndex, which doesn't append a raft log entry.
+    // It's not a substitute for a message that forces an apply pass on this
+    // peer (see `RangeCacheMemoryEngine::prepare_for_apply`'s caller in
+    // `fsm::apply::ApplyContext::prepare_for`) -- that needs a command that
+    // actually commits, and every `CmdType`/`AdminCmdType` the vendored
+    // kvproto defines today has a real, non-no-op effect when applied. A
+    // genuine no-op command would need its own cmd type from kvproto first.
     RenewLease,
 
     // Snapshot is applied
@@ -5019,6 +5019,11 @@ where
                 region_id: region.id,
                 epoch_version: region.get_region_epoch().version,
                 read_ts,
+                // The request header only carries the encoded read_ts across this
+                // path (e.g. replica read), so a per-request force-disk override
+                // isn't available here; it only takes effect on the leader-local
+                // read path where the caller's SnapshotContext is used directly.
+                force_disk_read: false,
             })
         } else {
             None
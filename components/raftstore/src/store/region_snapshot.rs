@@ -92,15 +92,26 @@ where
     }
 
     pub fn get_data_version(&self) -> Result<u64> {
-        if self.from_v2 {
+        let base = if self.from_v2 {
             if self.snap.sequence_number() != 0 {
-                Ok(self.snap.sequence_number())
+                self.snap.sequence_number()
             } else {
-                Err(box_err!("Snapshot sequence number 0"))
+                return Err(box_err!("Snapshot sequence number 0"));
             }
         } else {
-            self.get_apply_index()
-        }
+            self.get_apply_index()?
+        };
+        Ok(match self.snap.range_cache_load_generation() {
+            // An evict-then-reload of the region can leave `base` unchanged
+            // (neither the apply index nor the rocksdb sequence number moves
+            // just because the range cache engine rebuilt its skiplist), so
+            // fold the load generation in too: a response cached against the
+            // data version alone could otherwise be served across such a
+            // transition even though the cache that served it is not the one
+            // that served the original response.
+            Some(generation) => base ^ generation.wrapping_mul(0x9E3779B97F4A7C15),
+            None => base,
+        })
     }
 
     #[inline]
@@ -1108,6 +1108,15 @@ where
         region_stat: RegionStat,
         replication_status: Option<RegionReplicationStatus>,
     ) {
+        // NOTE: reporting whether this region is cached in the range cache engine (and
+        // its cache size) here would need new fields on pdpb::RegionHeartbeatRequest --
+        // every field this function fills in today (bytes_written, cpu_usage,
+        // approximate_size, ...) maps to one that already exists on the message, and
+        // there's no generic/opaque extension field to piggyback on. That requires a
+        // kvproto change landed and vendored first (see the kvproto patch notes in the
+        // workspace Cargo.toml); once the fields exist there, set them here from
+        // RangeCacheEngineExt's per-region cache status, the same way approximate_size
+        // is threaded in via RegionStat below.
         self.store_stat
             .region_bytes_written
             .observe(region_stat.written_bytes as f64);
@@ -1121,8 +1130,12 @@ where
             .region_keys_read
             .observe(region_stat.read_keys as f64);
 
+        let bucket_stat = self
+            .region_buckets
+            .get(&region.get_id())
+            .map(|report_bucket| &report_bucket.current_stat);
         self.coprocessor_host
-            .on_region_heartbeat(&region, &region_stat);
+            .on_region_heartbeat(&region, &region_stat, bucket_stat);
         let resp = self.pd_client.region_heartbeat(
             term,
             region.clone(),
@@ -1202,6 +1215,11 @@ where
         stats.set_capacity(capacity);
         stats.set_used_size(used_size);
 
+        // NOTE: the range cache engine already tracks its own memory usage and limit
+        // (MemoryController::mem_usage, already exported as a Prometheus gauge), so
+        // adding it here is just a matter of reading that and calling the setter --
+        // except pdpb::StoreStats has no field for it yet. Same kvproto prerequisite
+        // as the per-region cache status noted in handle_heartbeat above.
         if available == 0 {
             warn!("no available space");
         }
@@ -1573,6 +1591,20 @@ where
                     let req = new_batch_switch_witness(switches.take_switch_witnesses().into());
                     send_admin_request(&router, region_id, epoch, peer, req, Callback::None, Default::default());
                 } else {
+                    // NOTE: a "prefer this store for the leader, it already has the region
+                    // cached" hint would be read from this same response (the operator types
+                    // above -- change_peer, transfer_leader, switch_witnesses, ... -- are how
+                    // PD pushes scheduling decisions to a store today), but balance-leader
+                    // itself lives in PD, not here, and there's no pdpb field yet carrying a
+                    // region's cache status either way (see the note in handle_heartbeat).
+                    // Both sides of this would need to land in kvproto/PD first.
+                    //
+                    // A PD-driven "load this region into the cache" / "evict it" command would
+                    // be another operator type alongside these (resp.has_switch_witnesses() and
+                    // friends), dispatched here the same way -- but it doesn't exist in the
+                    // vendored kvproto either, and the cache already has its own load/evict
+                    // entry points (BackgroundRunner's load/evict tasks) that such a handler
+                    // would just need to schedule onto once the operator type exists.
                     PD_HEARTBEAT_COUNTER_VEC.with_label_values(&["noop"]).inc();
                 }
             });
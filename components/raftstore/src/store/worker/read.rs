@@ -2606,6 +2606,7 @@ mod tests {
             epoch_version: 0,
             read_ts: 15,
             range: None,
+            force_disk_read: false,
         };
 
         let s = get_snapshot(Some(snap_ctx.clone()), &mut reader, cmd.clone(), &rx);
@@ -2688,6 +2689,7 @@ mod tests {
             range: None,
             region_id: 0,
             epoch_version: 0,
+            force_disk_read: false,
         };
         reader.propose_raft_command(Some(snap_ctx), read_id, task.request, task.callback);
         assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
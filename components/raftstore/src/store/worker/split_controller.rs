@@ -336,6 +336,9 @@ pub struct RegionInfo {
     pub peer: Peer,
     pub key_ranges: Vec<KeyRange>,
     pub flow: FlowStatistics,
+    // Number of iterator seek/next/prev/seek_for_prev calls served by this region,
+    // used to spot regions with pathological iterator amplification.
+    pub iterate_ops: u64,
 }
 
 impl RegionInfo {
@@ -346,6 +349,7 @@ impl RegionInfo {
             key_ranges: Vec::with_capacity(sample_num),
             peer: Peer::default(),
             flow: FlowStatistics::default(),
+            iterate_ops: 0,
         }
     }
 
@@ -444,6 +448,7 @@ impl ReadStats {
         end: Option<&[u8]>,
         write: &FlowStatistics,
         data: &FlowStatistics,
+        iterate_ops: u64,
     ) {
         let num = self.sample_num;
         let region_info = self
@@ -452,6 +457,7 @@ impl ReadStats {
             .or_insert_with(|| RegionInfo::new(num));
         region_info.flow.add(write);
         region_info.flow.add(data);
+        region_info.iterate_ops += iterate_ops;
         // the bucket of the follower only have the version info and not needs to be
         // recorded the hot bucket.
         if let Some(buckets) = buckets
@@ -1,12 +1,17 @@
 // Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
 
-use std::{collections::HashMap, fmt::Display, sync::Arc, thread::JoinHandle, time::Duration};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use bytes::Bytes;
-use crossbeam::{
-    channel::{bounded, tick, Sender},
-    epoch, select,
-};
+use crossbeam::epoch;
 use engine_rocks::{RocksEngine, RocksSnapshot};
 use engine_traits::{
     CacheRange, EvictReason, IterOptions, Iterable, Iterator, MiscExt, RangeHintService,
@@ -16,15 +21,21 @@ use hex::FromHexError;
 use kvproto::metapb::Region;
 use parking_lot::RwLock;
 use pd_client::{PdClient, RpcClient};
-use raftstore::coprocessor::RegionInfoProvider;
+use raftstore::{coprocessor::RegionInfoProvider, store::util::RegionReadProgressRegistry};
 use slog_global::{error, info, warn};
 use tikv_util::{
-    config::ReadableSize,
-    future::block_on_timeout,
+    config::{ReadableSize, VersionTrack},
+    future::{block_on_timeout, spawn_with_deadline},
     keybuilder::KeyBuilder,
-    time::Instant,
-    worker::{Builder, Runnable, RunnableWithTimer, ScheduleError, Scheduler, Worker},
+    slow_log,
+    time::{Instant, SlowTimer},
+    worker::{
+        BackoffConfig, Builder, CronScheduler, RestartPolicy, Runnable, RunnableWithTimer,
+        Schedule, ScheduleError, Scheduler, Worker,
+    },
 };
+use tracing::instrument;
+use tracing_active_tree::root;
 use txn_types::{Key, TimeStamp, WriteRef, WriteType};
 use yatp::Remote;
 
@@ -36,20 +47,42 @@ use crate::{
     },
     memory_controller::{MemoryController, MemoryUsage},
     metrics::{
-        GC_FILTERED_STATIC, RANGE_CACHE_COUNT, RANGE_CACHE_MEMORY_USAGE, RANGE_GC_TIME_HISTOGRAM,
+        DELAYED_DELETE_REGIONS_COUNT_STATIC, DELAYED_DELETE_REGIONS_GAUGE,
+        DELAYED_DELETE_REGIONS_MAX_AGE_SECONDS, GC_FILTERED_STATIC, IN_MEMORY_ENGINE_SEEK_DURATION,
+        RANGE_CACHE_COUNT, RANGE_CACHE_MEMORY_USAGE, RANGE_GC_TIME_HISTOGRAM,
         RANGE_LOAD_TIME_HISTOGRAM,
     },
+    persist::{load_persisted_ranges, persist_cached_ranges},
     range_manager::{RangeMeta, RegionState},
-    range_stats::{RangeStatsManager, DEFAULT_EVICT_MIN_DURATION},
+    range_stats::RangeStatsManager,
+    region_cache_stats::RegionCacheStatsTracker,
     region_label::{
         KeyRangeRule, LabelRule, RegionLabelAddedCb, RegionLabelRulesManager,
         RegionLabelServiceBuilder,
     },
+    statistics::{Statistics, Tickers},
     write_batch::RangeCacheWriteBatchEntry,
+    RangeCacheEngineConfig,
 };
 
 // 5 seconds should be long enough for getting a TSO from PD.
 const TIMTOUT_FOR_TSO: Duration = Duration::from_secs(5);
+// How long `BgWorkManager`/`BackgroundRunner` wait, when dropped, for already
+// queued gc/delete/evict/load tasks to finish running before giving up and
+// aborting the underlying workers outright.
+const DRAIN_DEADLINE: Duration = Duration::from_secs(5);
+// Bounds how long a single background load/gc/evict/lock-cleanup task may
+// run before it's abandoned, so a wedged PD call or a pathological region
+// can't tie up its worker forever. Generous because loading or gc-ing a
+// region can legitimately take a while; this is a backstop, not a normal
+// completion time.
+const BACKGROUND_TASK_DEADLINE: Duration = Duration::from_secs(5 * 60);
+
+// Bounds how many background tasks (gc, load/evict, delete-range, ...) may be
+// queued up before `schedule_with_backoff` starts waiting for the runner to
+// catch up, instead of letting the queue, and the regions/ranges it is
+// holding onto, grow without limit.
+const BACKGROUND_TASK_MAX_PENDING_TASKS: usize = 4096;
 
 /// Try to extract the key and `u64` timestamp from `encoded_key`.
 ///
@@ -83,6 +116,7 @@ pub enum BackgroundTask {
     TopRegionsLoadEvict,
     CleanLockTombstone(u64),
     SetRocksEngine(RocksEngine),
+    PersistCachedRegionList,
 }
 
 impl Display for BackgroundTask {
@@ -100,6 +134,9 @@ impl Display for BackgroundTask {
                 .field("seqno", r)
                 .finish(),
             BackgroundTask::SetRocksEngine(_) => f.debug_struct("SetDiskEngine").finish(),
+            BackgroundTask::PersistCachedRegionList => {
+                f.debug_struct("PersistCachedRegionList").finish()
+            }
         }
     }
 }
@@ -125,16 +162,18 @@ pub struct BgWorkManager {
     worker: Worker,
     scheduler: Scheduler<BackgroundTask>,
     delete_region_scheduler: Scheduler<BackgroundTask>,
-    tick_stopper: Option<(JoinHandle<()>, Sender<bool>)>,
+    // Stopped (and its thread joined) by simply dropping it.
+    tick_scheduler: Option<CronScheduler>,
     core: Arc<RwLock<RangeCacheMemoryEngineCore>>,
     region_info_provider: Option<Arc<dyn RegionInfoProvider>>,
 }
 
 impl Drop for BgWorkManager {
     fn drop(&mut self) {
-        let (h, tx) = self.tick_stopper.take().unwrap();
-        let _ = tx.send(true);
-        let _ = h.join();
+        self.tick_scheduler.take();
+        if !self.scheduler.stop_with_deadline(DRAIN_DEADLINE) {
+            warn!("[IME] background worker did not drain in time, aborting remaining tasks");
+        }
         self.worker.stop();
     }
 }
@@ -151,20 +190,29 @@ impl From<Arc<RpcClient>> for PdRangeHintService {
 
 const CACHE_LABEL_RULE_KEY: &str = "cache";
 const CACHE_LABEL_RULE_ALWAYS: &str = "always";
+const WRITE_POLICY_LABEL_RULE_KEY: &str = "write-policy";
+const WRITE_POLICY_RULE_WRITE_AROUND: &str = "write-around";
 
 /// This implementation starts a background task using to pull down region label
 /// rules from PD.
 impl PdRangeHintService {
     /// Spawn a background task on `remote` to continuosly watch for region
-    /// label rules that contain the label `cache`; if a new added for which
-    /// `cache` is set to `always`, request loading the label's keyranges using
-    /// `range_manager_load_cb`.
+    /// label rules that contain the label `cache` or `write-policy`: if a rule
+    /// is added with `cache` set to `always`, request loading the label's
+    /// keyranges using `range_manager_load_cb`; if a rule is added with
+    /// `write-policy` set to `write-around`, request the label's keyranges be
+    /// treated as write-around using `range_manager_write_around_cb`.
     ///
     /// TODO (afeinberg): Add support for evicting key ranges when the `cache`
     /// label is removed or no longer set to always.
-    pub fn start<F>(&self, remote: Remote<yatp::task::future::TaskCell>, range_manager_load_cb: F)
-    where
+    pub fn start<F, G>(
+        &self,
+        remote: Remote<yatp::task::future::TaskCell>,
+        range_manager_load_cb: F,
+        range_manager_write_around_cb: G,
+    ) where
         F: Fn(&[u8], &[u8]) + Send + Sync + 'static,
+        G: Fn(&[u8], &[u8]) + Send + Sync + 'static,
     {
         let parse_range = |key_range: &KeyRangeRule| {
             let start = hex::decode(&key_range.start_key)?;
@@ -174,21 +222,32 @@ impl PdRangeHintService {
 
         let pd_client = self.0.clone();
         let region_label_added_cb: RegionLabelAddedCb = Arc::new(move |label_rule: &LabelRule| {
-            if !label_rule
+            let cache_always = label_rule
                 .labels
                 .iter()
-                .any(|e| e.key == CACHE_LABEL_RULE_KEY && e.value == CACHE_LABEL_RULE_ALWAYS)
-            {
+                .any(|e| e.key == CACHE_LABEL_RULE_KEY && e.value == CACHE_LABEL_RULE_ALWAYS);
+            let write_around = label_rule.labels.iter().any(|e| {
+                e.key == WRITE_POLICY_LABEL_RULE_KEY && e.value == WRITE_POLICY_RULE_WRITE_AROUND
+            });
+            if !cache_always && !write_around {
                 // not related to caching, skip.
                 return;
             }
             for key_range in &label_rule.data {
                 match parse_range(key_range) {
                     Ok((start, end)) => {
-                        info!("Requested to cache range";
-                            "start" => ?log_wrappers::Value(&start),
-                            "end" => ?log_wrappers::Value(&end));
-                        range_manager_load_cb(&start, &end);
+                        if cache_always {
+                            info!("Requested to cache range";
+                                "start" => ?log_wrappers::Value(&start),
+                                "end" => ?log_wrappers::Value(&end));
+                            range_manager_load_cb(&start, &end);
+                        }
+                        if write_around {
+                            info!("Requested write-around policy for range";
+                                "start" => ?log_wrappers::Value(&start),
+                                "end" => ?log_wrappers::Value(&end));
+                            range_manager_write_around_cb(&start, &end);
+                        }
                     }
                     Err(e) => {
                         error!("Unable to convert key_range rule to cache range"; "err" => ?e);
@@ -207,7 +266,7 @@ impl PdRangeHintService {
             label_rule
                 .labels
                 .iter()
-                .any(|e| e.key == CACHE_LABEL_RULE_KEY)
+                .any(|e| e.key == CACHE_LABEL_RULE_KEY || e.key == WRITE_POLICY_LABEL_RULE_KEY)
         })
         .build()
         .unwrap();
@@ -222,32 +281,59 @@ impl BgWorkManager {
         gc_interval: Duration,
         load_evict_interval: Duration,
         expected_region_size: usize,
+        gc_safe_point: Option<Arc<AtomicU64>>,
         memory_controller: Arc<MemoryController>,
         region_info_provider: Option<Arc<dyn RegionInfoProvider>>,
+        region_read_progress: Option<RegionReadProgressRegistry>,
+        config: Arc<VersionTrack<RangeCacheEngineConfig>>,
+        statistics: Arc<Statistics>,
+        region_cache_stats: RegionCacheStatsTracker,
     ) -> Self {
-        let worker = Worker::new("range-cache-background-worker");
+        // Opt into catch-and-restart: a panic in one gc/load-evict task
+        // shouldn't take the whole in-memory cache engine down with it, the
+        // way a panic elsewhere in TiKV is meant to.
+        let worker = Builder::new("range-cache-background-worker")
+            .pending_capacity(BACKGROUND_TASK_MAX_PENDING_TASKS)
+            .restart_policy(RestartPolicy::default())
+            .create();
         let (runner, delete_range_scheduler) = BackgroundRunner::new(
             core.clone(),
             memory_controller,
             region_info_provider.clone(),
+            region_read_progress,
+            gc_safe_point,
             expected_region_size,
             gc_interval,
             pd_client.clone(),
+            config.clone(),
+            statistics,
+            region_cache_stats,
         );
         let scheduler = worker.start_with_timer("range-cache-engine-background", runner);
 
-        let (h, tx) = BgWorkManager::start_tick(
+        if config.value().persist_cached_region_list {
+            Self::restore_cached_region_list(
+                &core,
+                region_info_provider.as_deref(),
+                &config.value().cached_region_list_path,
+            );
+        }
+
+        let persist_interval = config.value().cached_region_list_persist_interval.0;
+        let tick_scheduler = BgWorkManager::start_tick(
             scheduler.clone(),
             pd_client,
             gc_interval,
             load_evict_interval,
+            persist_interval,
+            config,
         );
 
         Self {
             worker,
             scheduler,
             delete_region_scheduler: delete_range_scheduler,
-            tick_stopper: Some((h, tx)),
+            tick_scheduler: Some(tick_scheduler),
             core,
             region_info_provider,
         }
@@ -255,108 +341,205 @@ impl BgWorkManager {
 
     pub fn schedule_task(&self, task: BackgroundTask) -> Result<(), ScheduleError<BackgroundTask>> {
         match task {
-            task @ BackgroundTask::DeleteRegions(_) => {
-                self.delete_region_scheduler.schedule_force(task)
-            }
-            task => self.scheduler.schedule_force(task),
+            task @ BackgroundTask::DeleteRegions(_) => self
+                .delete_region_scheduler
+                .schedule_with_backoff(task, BackoffConfig::default()),
+            task => self
+                .scheduler
+                .schedule_with_backoff(task, BackoffConfig::default()),
         }
     }
 
-    pub fn start_bg_hint_service(&self, range_hint_service: PdRangeHintService) {
-        let core = self.core.clone();
-        let region_info_provider = self.region_info_provider.clone();
-        range_hint_service.start(self.worker.remote(), move |start: &[u8], end: &[u8]| {
-            let Some(ref info_provider) = region_info_provider else {
-                warn!("[IME] region info provider is none, skip load pinned range.");
+    /// Re-seeds the set of ranges worth keeping cached from what was
+    /// persisted before the last shutdown (see
+    /// `BackgroundTask::PersistCachedRegionList`). Every range is pinned, so
+    /// it's re-warmed the next time this store becomes leader of a region
+    /// overlapping it (`RangeCacheEngineRegionChangeObserver::on_role_change`)
+    /// even if nothing below succeeds. Additionally, for any range
+    /// `region_info_provider` already has an answer for this early in boot,
+    /// a load is requested immediately -- the same best-effort pattern
+    /// `start_bg_hint_service` uses for PD region-label hints, including its
+    /// same limitation: a region the info provider doesn't know about yet
+    /// simply falls back to waiting on the role-change path above.
+    fn restore_cached_region_list(
+        core: &Arc<RwLock<RangeCacheMemoryEngineCore>>,
+        region_info_provider: Option<&dyn RegionInfoProvider>,
+        path: &str,
+    ) {
+        let ranges = match load_persisted_ranges(path) {
+            Ok(ranges) => ranges,
+            Err(e) => {
+                warn!("load persisted cached region list failed"; "err" => ?e, "path" => path);
                 return;
-            };
+            }
+        };
+        if ranges.is_empty() {
+            return;
+        }
 
-            let regions = match info_provider.get_regions_in_range(start, end) {
+        let mut engine = core.write();
+        for range in ranges {
+            engine
+                .mut_range_manager()
+                .record_pinned_range(range.clone());
+
+            let Some(info_provider) = region_info_provider else {
+                continue;
+            };
+            let regions = match info_provider.get_regions_in_range(&range.start, &range.end) {
                 Ok(r) => r,
                 Err(e) => {
                     warn!(
-                        "get regions in range failed"; "err" => ?e,
-                        "start" => ?log_wrappers::Value(start),
-                        "end" => ?log_wrappers::Value(end)
+                        "get regions in range failed while restoring cached region list";
+                        "err" => ?e, "range" => ?range,
                     );
-                    return;
+                    continue;
                 }
             };
-
-            if regions.is_empty() {
-                return;
-            }
-
-            let mut engine = core.write();
             for r in regions {
                 if let Err(e) = engine.mut_range_manager().load_region(r.clone()) {
-                    warn!("load region by label failed"; "err" => ?e, "region" => ?r);
+                    warn!(
+                        "load region while restoring cached region list failed";
+                        "err" => ?e, "region" => ?r,
+                    );
                 }
             }
-            // TODO (afeinberg): This does not actually load the range. The load
-            // happens the apply thread begins to apply raft
-            // entries. To force this (for read-only use-cases) we
-            // should propose a No-Op command.
-        });
+        }
     }
 
+    pub fn start_bg_hint_service(&self, range_hint_service: PdRangeHintService) {
+        let core = self.core.clone();
+        let region_info_provider = self.region_info_provider.clone();
+        let write_around_core = self.core.clone();
+        range_hint_service.start(
+            self.worker.remote(),
+            move |start: &[u8], end: &[u8]| {
+                let Some(ref info_provider) = region_info_provider else {
+                    warn!("[IME] region info provider is none, skip load pinned range.");
+                    return;
+                };
+
+                let regions = match info_provider.get_regions_in_range(start, end) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        warn!(
+                            "get regions in range failed"; "err" => ?e,
+                            "start" => ?log_wrappers::Value(start),
+                            "end" => ?log_wrappers::Value(end)
+                        );
+                        return;
+                    }
+                };
+
+                if regions.is_empty() {
+                    return;
+                }
+
+                let mut engine = core.write();
+                engine
+                    .mut_range_manager()
+                    .record_pinned_range(CacheRange::new(start.to_vec(), end.to_vec()));
+                for r in regions {
+                    if let Err(e) = engine.mut_range_manager().load_region(r.clone()) {
+                        warn!("load region by label failed"; "err" => ?e, "region" => ?r);
+                    }
+                }
+                // TODO (afeinberg): This does not actually load the range. The load
+                // happens the apply thread begins to apply raft
+                // entries. To force this (for read-only use-cases) we
+                // should propose a No-Op command.
+                //
+                // There's no cmd type to propose yet that would force an apply
+                // pass without also doing something real once applied -- see the
+                // note by `CasualMessage::RenewLease` in raftstore's `msg.rs` for
+                // why renewing the lease (a ReadIndex) doesn't get us there
+                // either. Once kvproto grows a real no-op cmd type, this closure
+                // would want to reach `region_info_provider` for a router/peer
+                // handle to send it with, which `RegionInfoAccessor` (the only
+                // production `RegionInfoProvider`) doesn't carry today either.
+            },
+            move |start: &[u8], end: &[u8]| {
+                write_around_core
+                    .write()
+                    .mut_range_manager()
+                    .record_write_around_range(CacheRange::new(start.to_vec(), end.to_vec()));
+            },
+        );
+    }
+
+    // `gc_interval`/`load_evict_interval` set the tick cadence itself, which is
+    // fixed for the lifetime of this `CronScheduler` (rescheduling a running
+    // `CronScheduler` at a new interval isn't supported yet); a config change
+    // to either only takes effect on restart. `config` is kept around so the
+    // safe point computed on each gc tick reflects the *current*
+    // `gc_interval` in the meantime, rather than the value from when this
+    // engine started.
     fn start_tick(
         scheduler: Scheduler<BackgroundTask>,
         pd_client: Arc<dyn PdClient>,
         gc_interval: Duration,
         load_evict_interval: Duration,
-    ) -> (JoinHandle<()>, Sender<bool>) {
-        let (tx, rx) = bounded(0);
-        // TODO: Instead of spawning a new thread, we should run this task
-        //       in a shared background thread.
-        let h = std::thread::spawn(move || {
-            let gc_ticker = tick(gc_interval);
-            let load_evict_ticker = tick(load_evict_interval); // TODO (afeinberg): Use a real value.
-            let tso_timeout = std::cmp::min(gc_interval, TIMTOUT_FOR_TSO);
-            'LOOP: loop {
-                select! {
-                    recv(gc_ticker) -> _ => {
-                        let now = match block_on_timeout(pd_client.get_tso(), tso_timeout) {
-                            Ok(Ok(ts)) => ts,
-                            err => {
-                                error!(
-                                    "schedule range cache engine gc failed ";
-                                    "timeout_duration" => ?tso_timeout,
-                                    "error" => ?err,
-                                );
-                                continue 'LOOP;
-                            }
-                        };
-                        let safe_point = now.physical() - gc_interval.as_millis() as u64;
-                        let safe_point = TimeStamp::compose(safe_point, 0).into_inner();
-                        if let Err(e) = scheduler.schedule(BackgroundTask::Gc(GcTask {safe_point})) {
-                            error!(
-                                "schedule range cache engine gc failed";
-                                "err" => ?e,
-                            );
-                        }
-                    },
-                    recv(load_evict_ticker) -> _ => {
-                        if let Err(e) = scheduler.schedule(BackgroundTask::TopRegionsLoadEvict) {
-                            error!(
-                                "schedule load evict failed";
-                                "err" => ?e,
-                            );
-                        }
-                    },
-                    recv(rx) -> r => {
-                        if let Err(e) = r {
-                            error!(
-                                "receive error in range cache engien gc ticker";
-                                "err" => ?e,
-                            );
-                        }
-                        return;
-                    },
+        persist_interval: Duration,
+        config: Arc<VersionTrack<RangeCacheEngineConfig>>,
+    ) -> CronScheduler {
+        let cron = CronScheduler::new("range-cache-engine-tick");
+        let tso_timeout = std::cmp::min(gc_interval, TIMTOUT_FOR_TSO);
+
+        let gc_scheduler = scheduler.clone();
+        let persist_config = config.clone();
+        cron.schedule(Schedule::FixedRate(gc_interval), move || {
+            let now = match block_on_timeout(pd_client.get_tso(), tso_timeout) {
+                Ok(Ok(ts)) => ts,
+                err => {
+                    error!(
+                        "schedule range cache engine gc failed ";
+                        "timeout_duration" => ?tso_timeout,
+                        "error" => ?err,
+                    );
+                    return;
                 }
+            };
+            let gc_interval = config.value().gc_interval.0;
+            let safe_point = now.physical() - gc_interval.as_millis() as u64;
+            let safe_point = TimeStamp::compose(safe_point, 0).into_inner();
+            if let Err(e) = gc_scheduler.schedule(BackgroundTask::Gc(GcTask { safe_point })) {
+                error!(
+                    "schedule range cache engine gc failed";
+                    "err" => ?e,
+                );
             }
         });
-        (h, tx)
+
+        let persist_scheduler = scheduler.clone();
+        cron.schedule(Schedule::FixedRate(load_evict_interval), move || {
+            if let Err(e) = scheduler.schedule(BackgroundTask::TopRegionsLoadEvict) {
+                error!(
+                    "schedule load evict failed";
+                    "err" => ?e,
+                );
+            }
+        });
+
+        // `persist_cached_region_list` is read live, so the feature can be
+        // flipped on/off without a restart. `cached_region_list_path` is also
+        // re-read on every tick, but it skips online config, so repointing it
+        // takes a restart; the tick cadence itself is likewise fixed for this
+        // `CronScheduler`'s lifetime, same as `gc_interval`/`load_evict_interval`
+        // above.
+        cron.schedule(Schedule::FixedRate(persist_interval), move || {
+            let cfg = persist_config.value();
+            if !cfg.persist_cached_region_list || cfg.cached_region_list_path.is_empty() {
+                return;
+            }
+            if let Err(e) = persist_scheduler.schedule(BackgroundTask::PersistCachedRegionList) {
+                error!(
+                    "schedule persist cached region list failed";
+                    "err" => ?e,
+                );
+            }
+        });
+
+        cron
     }
 }
 
@@ -365,6 +548,22 @@ struct BackgroundRunnerCore {
     engine: Arc<RwLock<RangeCacheMemoryEngineCore>>,
     memory_controller: Arc<MemoryController>,
     range_stats_manager: Option<RangeStatsManager>,
+    // Consulted by `top_regions_load_evict` to keep a keyspace's cached region
+    // count back under its configured quota share (see
+    // `RangeCacheEngineConfig::keyspace_quotas`).
+    config: Arc<VersionTrack<RangeCacheEngineConfig>>,
+    // Read by `auto_tune_soft_limit` for the hit-rate signal driving
+    // `RangeCacheEngineConfig::soft_limit_auto_tune`. Never reset here (that's
+    // `flush_range_cache_engine_statistics`'s job); only cumulative counts are
+    // read, so the hit rate stays correct regardless of when that flush runs.
+    statistics: Arc<Statistics>,
+    // Used to lower-bound a region's gc safe point by its resolved ts, so gc never
+    // removes versions that a stale read or an unresolved lock may still need.
+    region_read_progress: Option<RegionReadProgressRegistry>,
+    // The safe point the storage GC worker's compaction filter is using, if known.
+    // Clamping against it keeps the in-memory engine from running its own gc ahead
+    // of (or behind) the disk engine's.
+    gc_safe_point: Option<Arc<AtomicU64>>,
 }
 
 impl BackgroundRunnerCore {
@@ -392,6 +591,23 @@ impl BackgroundRunnerCore {
             .collect()
     }
 
+    /// Returns every range currently worth re-warming on the next restart:
+    /// the pinned ranges (kept regardless of activity) plus every `Active`
+    /// region's own range. See `BackgroundTask::PersistCachedRegionList`.
+    fn cached_ranges_for_persist(&self) -> Vec<CacheRange> {
+        let core = self.engine.read();
+        let range_manager = core.range_manager();
+        let mut ranges = range_manager.pinned_ranges().to_vec();
+        ranges.extend(
+            range_manager
+                .regions()
+                .values()
+                .filter(|m| m.get_state() == RegionState::Active)
+                .map(|m| m.get_range().clone()),
+        );
+        ranges
+    }
+
     pub(crate) fn gc_region(
         &self,
         region: &Region,
@@ -422,7 +638,32 @@ impl BackgroundRunnerCore {
                 .region_snapshot_list()
                 .min_snapshot_ts()
                 .unwrap_or(u64::MAX);
-            let safe_point = safe_point.min(min_snapshot).min(historical_safe_point);
+            // `now - gc_interval` can run ahead of the region's resolved ts (e.g. a long
+            // running transaction is still holding a lock, or a follower is serving a
+            // stale read below it), which would let gc remove versions that are still
+            // needed. Clamp it with the region's safe_ts maintained by resolved-ts. A
+            // safe_ts of 0 means the peer's read progress isn't initialized yet, so it
+            // imposes no constraint.
+            let resolved_ts = self
+                .region_read_progress
+                .as_ref()
+                .and_then(|registry| registry.get_safe_ts(&region.id))
+                .filter(|ts| *ts != 0)
+                .unwrap_or(u64::MAX);
+            // Also clamp by the storage GC worker's own safe point, if it's wired in, so
+            // both sides agree on what's safe to remove instead of each computing it
+            // independently. A value of 0 means it hasn't been initialized yet.
+            let gc_worker_safe_point = self
+                .gc_safe_point
+                .as_ref()
+                .map(|sp| sp.load(Ordering::Relaxed))
+                .filter(|sp| *sp != 0)
+                .unwrap_or(u64::MAX);
+            let safe_point = safe_point
+                .min(min_snapshot)
+                .min(historical_safe_point)
+                .min(resolved_ts)
+                .min(gc_worker_safe_point);
             if safe_point <= region_meta.safe_point() {
                 info!(
                     "safe point not large enough";
@@ -479,6 +720,14 @@ impl BackgroundRunnerCore {
         if filter.cached_skiplist_delete_key.is_some() {
             metrics.filtered += 1;
         }
+
+        {
+            let mut core = self.engine.write();
+            if let Some(region_meta) = core.mut_range_manager().mut_region_meta(region.id) {
+                region_meta.add_filtered_versions(metrics.filtered as u64);
+            }
+        }
+
         metrics
     }
 
@@ -538,9 +787,10 @@ impl BackgroundRunnerCore {
         if !remove_regions.is_empty() {
             fail::fail_point!("in_memory_engine_snapshot_load_canceled");
 
-            if let Err(e) =
-                delete_range_scheduler.schedule_force(BackgroundTask::DeleteRegions(remove_regions))
-            {
+            if let Err(e) = delete_range_scheduler.schedule_with_backoff(
+                BackgroundTask::DeleteRegions(remove_regions),
+                BackoffConfig::default(),
+            ) {
                 error!(
                     "schedule delete range failed";
                     "err" => ?e,
@@ -591,9 +841,10 @@ impl BackgroundRunnerCore {
                 });
         }
 
-        if let Err(e) =
-            delete_range_scheduler.schedule_force(BackgroundTask::DeleteRegions(remove_regions))
-        {
+        if let Err(e) = delete_range_scheduler.schedule_with_backoff(
+            BackgroundTask::DeleteRegions(remove_regions),
+            BackoffConfig::default(),
+        ) {
             error!(
                 "schedule delete range failed";
                 "err" => ?e,
@@ -659,9 +910,10 @@ impl BackgroundRunnerCore {
         }
 
         if !regions_to_delete.is_empty() {
-            if let Err(e) = delete_range_scheduler
-                .schedule_force(BackgroundTask::DeleteRegions(regions_to_delete))
-            {
+            if let Err(e) = delete_range_scheduler.schedule_with_backoff(
+                BackgroundTask::DeleteRegions(regions_to_delete),
+                BackoffConfig::default(),
+            ) {
                 error!(
                     "schedule deletet range failed";
                     "err" => ?e,
@@ -696,6 +948,11 @@ impl BackgroundRunnerCore {
         let mut regions_to_add = Vec::with_capacity(256);
         let mut regions_to_remove = Vec::with_capacity(256);
         range_stats_manager.collect_changed_ranges(&mut regions_to_add, &mut regions_to_remove);
+        // `regions_to_remove` are all evictable, but we may run out of soft-limit
+        // headroom to evict for partway through; put the ones `eviction_policy`
+        // cares about most at the front so they're the ones that actually get
+        // evicted this tick.
+        range_stats_manager.rank_for_eviction(&mut regions_to_remove);
         let mut regions_to_delete = Vec::with_capacity(regions_to_remove.len());
         info!("load_evict"; "ranges_to_add" => ?&regions_to_add, "may_evict" => ?&regions_to_remove);
         for evict_region in regions_to_remove {
@@ -714,9 +971,10 @@ impl BackgroundRunnerCore {
         }
 
         if !regions_to_delete.is_empty() {
-            if let Err(e) = delete_range_scheduler
-                .schedule_force(BackgroundTask::DeleteRegions(regions_to_delete))
-            {
+            if let Err(e) = delete_range_scheduler.schedule_with_backoff(
+                BackgroundTask::DeleteRegions(regions_to_delete),
+                BackoffConfig::default(),
+            ) {
                 error!(
                     "schedule deletet range failed";
                     "err" => ?e,
@@ -730,9 +988,137 @@ impl BackgroundRunnerCore {
                 error!("error loading range"; "cache_range" => ?region, "err" => ?e);
             }
         }
+        self.evict_over_quota_keyspaces();
+        self.auto_tune_soft_limit();
+
         range_stats_manager.set_checking_top_regions(false);
         info!("load_evict complete");
     }
+
+    // Evicts regions from any keyspace that is currently over its configured
+    // quota share (`RangeCacheEngineConfig::keyspace_quotas`). A keyspace can
+    // end up over its share even though every individual load was rejected
+    // once the quota was reached, e.g. after one of its cached regions
+    // splits, so this is re-checked on every load/evict tick rather than
+    // relying solely on admission-time rejection. Regions are evicted oldest
+    // load generation first, so freshly (re)loaded data isn't the first thing
+    // thrown away.
+    fn evict_over_quota_keyspaces(&self) {
+        let config = self.config.value();
+        if config.keyspace_quotas.is_empty() {
+            return;
+        }
+        let region_budget = config.hard_limit_threshold() / config.expected_region_size().max(1);
+        for (keyspace_id, &share) in &config.keyspace_quotas {
+            let Ok(keyspace_id) = keyspace_id.parse::<u32>() else {
+                continue;
+            };
+            let keyspace_budget = (region_budget as f64 * share) as usize;
+            loop {
+                let mut core = self.engine.write();
+                let range_manager = core.mut_range_manager();
+                if range_manager.keyspace_region_count(keyspace_id) <= keyspace_budget {
+                    break;
+                }
+                let Some(victim) = range_manager.oldest_keyspace_region(keyspace_id) else {
+                    break;
+                };
+                info!(
+                    "evicting region over keyspace quota";
+                    "keyspace_id" => keyspace_id,
+                    "region_id" => victim.id,
+                );
+                range_manager.evict_region(&victim, EvictReason::AutoEvict);
+            }
+        }
+    }
+
+    // Nudges `soft_limit_threshold` up or down within
+    // `[soft_limit_auto_tune_min, soft_limit_auto_tune_max]`, based on the
+    // cache's observed hit rate and read latency, so it doesn't need to be
+    // retuned by hand as the workload shifts. Hit rate is approximated by the
+    // find-rate of the engine's seek/next/prev operations, and latency by the
+    // mean of `IN_MEMORY_ENGINE_SEEK_DURATION`, since this engine doesn't keep
+    // a percentile sketch; both are read as cumulative counts rather than
+    // since-last-tick deltas; that keeps this independent of
+    // `flush_range_cache_engine_statistics`'s own reset cadence, at the cost
+    // of reacting to a shift in the workload more slowly than a delta would.
+    // A low hit rate alongside healthy latency suggests there's room to grow
+    // the cache; a cache that's already slow gets shrunk instead, on the
+    // assumption that memory pressure, rather than cache size, is what's
+    // hurting it. Folded into `top_regions_load_evict`'s tick rather than
+    // given its own, since both only make sense to evaluate together with the
+    // current region composition.
+    fn auto_tune_soft_limit(&self) {
+        const HIT_RATE_LOW: f64 = 0.8;
+        const HIT_RATE_HIGH: f64 = 0.95;
+        const HEALTHY_LATENCY_SECS: f64 = 0.001;
+        const STEP_RATIO: f64 = 0.1;
+
+        let (min, max, current) = {
+            let config = self.config.value();
+            if !config.soft_limit_auto_tune {
+                return;
+            }
+            (
+                config.soft_limit_auto_tune_min(),
+                config.soft_limit_auto_tune_max(),
+                config.soft_limit_threshold(),
+            )
+        };
+        if min >= max {
+            return;
+        }
+
+        let found = self.statistics.get_ticker_count(Tickers::NumberDbSeekFound)
+            + self.statistics.get_ticker_count(Tickers::NumberDbNextFound)
+            + self.statistics.get_ticker_count(Tickers::NumberDbPrevFound);
+        let total = self.statistics.get_ticker_count(Tickers::NumberDbSeek)
+            + self.statistics.get_ticker_count(Tickers::NumberDbNext)
+            + self.statistics.get_ticker_count(Tickers::NumberDbPrev);
+        if total == 0 {
+            // Not enough signal yet to make a decision.
+            return;
+        }
+        let hit_rate = found as f64 / total as f64;
+
+        let sample_count = IN_MEMORY_ENGINE_SEEK_DURATION.get_sample_count();
+        let mean_latency_secs = if sample_count == 0 {
+            0.0
+        } else {
+            IN_MEMORY_ENGINE_SEEK_DURATION.get_sample_sum() / sample_count as f64
+        };
+
+        let step = ((max - min) as f64 * STEP_RATIO) as usize;
+        if step == 0 {
+            return;
+        }
+        let new_limit = if hit_rate < HIT_RATE_LOW && mean_latency_secs < HEALTHY_LATENCY_SECS {
+            current.saturating_add(step).min(max)
+        } else if hit_rate > HIT_RATE_HIGH || mean_latency_secs >= HEALTHY_LATENCY_SECS {
+            current.saturating_sub(step).max(min)
+        } else {
+            current
+        };
+        if new_limit == current {
+            return;
+        }
+
+        info!(
+            "auto-tuning range cache soft limit";
+            "previous" => current,
+            "new" => new_limit,
+            "hit_rate" => hit_rate,
+            "mean_seek_latency_secs" => mean_latency_secs,
+        );
+        let res: Result<(), String> = self.config.update(|cfg: &mut RangeCacheEngineConfig| {
+            cfg.soft_limit_threshold = Some(ReadableSize(new_limit as u64));
+            Ok(())
+        });
+        if let Err(e) = res {
+            warn!("failed to auto-tune range cache soft limit"; "err" => e);
+        }
+    }
 }
 
 // Flush epoch and pin enough times to make the delayed operations be executed
@@ -755,6 +1141,7 @@ pub struct BackgroundRunner {
 
     pd_client: Arc<dyn PdClient>,
     gc_interval: Duration,
+    config: Arc<VersionTrack<RangeCacheEngineConfig>>,
 
     // We have following four separate workers so that each type of task would not block each
     // others
@@ -784,6 +1171,9 @@ pub struct BackgroundRunner {
 impl Drop for BackgroundRunner {
     fn drop(&mut self) {
         self.range_load_worker.stop();
+        if !self.delete_range_scheduler.stop_with_deadline(DRAIN_DEADLINE) {
+            warn!("[IME] delete-range worker did not drain in time, aborting remaining tasks");
+        }
         self.delete_range_worker.stop();
         self.gc_range_worker.stop();
         self.load_evict_worker.stop();
@@ -796,26 +1186,49 @@ impl BackgroundRunner {
         engine: Arc<RwLock<RangeCacheMemoryEngineCore>>,
         memory_controller: Arc<MemoryController>,
         region_info_provider: Option<Arc<dyn RegionInfoProvider>>,
+        region_read_progress: Option<RegionReadProgressRegistry>,
+        gc_safe_point: Option<Arc<AtomicU64>>,
         expected_region_size: usize,
         gc_interval: Duration,
         pd_client: Arc<dyn PdClient>,
+        config: Arc<VersionTrack<RangeCacheEngineConfig>>,
+        statistics: Arc<Statistics>,
+        region_cache_stats: RegionCacheStatsTracker,
     ) -> (Self, Scheduler<BackgroundTask>) {
+        // Each of these workers gets its own, distinctly-named yatp pool so
+        // that, with `enable-thread-exclusive-arena` on (the default), the
+        // jemalloc per-thread-name arena stats in `iterate_arena_allocation_stats`
+        // can separate the cache engine's own background allocations (gc,
+        // range load, delete-range) from block cache or raftstore instead of
+        // lumping them all under one name. This only covers allocations made
+        // on these background threads; writes/reads done by apply or
+        // unified-read-pool threads straight into the skiplist are still
+        // attributed to those threads' arenas.
+        // Different regions may load concurrently across these threads; a
+        // single region never has more than one `LoadRegion` task in flight
+        // at a time regardless of thread count, since `RegionManager::
+        // load_region` already refuses a region that's already
+        // `Pending`/`ReadyToLoad`/`Loading`. Concurrent loads share one
+        // `MemoryController`, whose hard-limit check every load thread
+        // consults before each insert, so they can't collectively blow the
+        // hard limit. See `RangeCacheEngineConfig::load_threads`.
         let range_load_worker = Builder::new("background-range-load-worker")
-            // Range load now is implemented sequentially, so we must use exactly one thread to handle it.
-            // todo(SpadeA): if the load speed is a bottleneck, we may consider to use multiple threads to load ranges.
-            .thread_count(1)
+            .thread_count(config.value().load_threads.max(1))
             .create();
         let range_load_remote = range_load_worker.remote();
 
-        let delete_range_worker = Worker::new("background-delete-range-worker");
-        let delete_range_runner = DeleteRangeRunner::new(engine.clone());
+        let delete_range_worker = Builder::new("background-delete-range-worker")
+            .pending_capacity(BACKGROUND_TASK_MAX_PENDING_TASKS)
+            .restart_policy(RestartPolicy::default())
+            .create();
+        let delete_range_runner = DeleteRangeRunner::new(engine.clone(), config.clone());
         let delete_range_scheduler =
             delete_range_worker.start_with_timer("delete-range-runner", delete_range_runner);
 
         let lock_cleanup_worker = Worker::new("lock-cleanup-worker");
         let lock_cleanup_remote = lock_cleanup_worker.remote();
 
-        let gc_range_worker = Builder::new("background-range-load-worker")
+        let gc_range_worker = Builder::new("background-gc-range-worker")
             // Gc must also use exactly one thread to handle it.
             .thread_count(1)
             .create();
@@ -828,9 +1241,9 @@ impl BackgroundRunner {
         let range_stats_manager = region_info_provider.map(|region_info_provider| {
             RangeStatsManager::new(
                 num_regions_to_cache,
-                DEFAULT_EVICT_MIN_DURATION,
-                expected_region_size,
+                config.clone(),
                 region_info_provider,
+                region_cache_stats,
             )
         });
         (
@@ -839,9 +1252,14 @@ impl BackgroundRunner {
                     engine,
                     memory_controller,
                     range_stats_manager,
+                    region_read_progress,
+                    gc_safe_point,
+                    config: config.clone(),
+                    statistics,
                 },
                 pd_client,
                 gc_interval,
+                config,
                 range_load_worker,
                 range_load_remote,
                 delete_range_worker,
@@ -896,9 +1314,13 @@ impl Runnable for BackgroundRunner {
                     "oldest_sequence" => seqno,
                 );
                 let core = self.core.clone();
+                let config = self.config.clone();
                 let regions = core.regions_for_gc();
                 if !regions.is_empty() {
+                    let region_count = regions.len();
                     let f = async move {
+                        let slow_timer = SlowTimer::from(config.value().gc_slow_log_threshold.0);
+                        let mem_usage_before = core.memory_controller.mem_usage();
                         let mut metrics = FilterMetrics::default();
                         for region in &regions {
                             let m = core.gc_region(region, t.safe_point, seqno);
@@ -907,8 +1329,24 @@ impl Runnable for BackgroundRunner {
                         core.on_gc_finished();
                         metrics.flush();
                         fail::fail_point!("in_memory_engine_gc_finish");
+
+                        let mem_usage_after = core.memory_controller.mem_usage();
+                        slow_log!(
+                            T slow_timer,
+                            "range cache engine gc for safe_point {}, region_count {}, \
+                             mem_usage {}MB -> {}MB",
+                            t.safe_point,
+                            region_count,
+                            ReadableSize(mem_usage_before as u64).as_mb(),
+                            ReadableSize(mem_usage_after as u64).as_mb()
+                        );
                     };
-                    self.gc_range_remote.spawn(f);
+                    spawn_with_deadline(
+                        &self.gc_range_remote,
+                        "range-cache-engine-gc",
+                        root!("range-cache-engine-gc"; f; safe_point = t.safe_point, region_count),
+                        BACKGROUND_TASK_DEADLINE,
+                    );
                 } else {
                     core.on_gc_finished();
                 }
@@ -918,6 +1356,8 @@ impl Runnable for BackgroundRunner {
                 let delete_range_scheduler = self.delete_range_scheduler.clone();
                 let pd_client = self.pd_client.clone();
                 let gc_interval = self.gc_interval;
+                let config = self.config.clone();
+                let region_id = region.id;
                 let f = async move {
                     fail::fail_point!("on_start_loading_region");
                     let mut is_canceled = false;
@@ -957,14 +1397,29 @@ impl Runnable for BackgroundRunner {
                     }
 
                     info!("Loading region"; "region" => ?&region);
-                    let start = Instant::now();
+                    let slow_timer =
+                        SlowTimer::from(config.value().load_region_slow_log_threshold.0);
                     let iter_opt = IterOptions::new(
                         Some(KeyBuilder::from_slice(&region_range.start, 0, 0)),
                         Some(KeyBuilder::from_slice(&region_range.end, 0, 0)),
                         false,
                     );
 
+                    // Loading a region can take a while; periodically re-check whether
+                    // it's been canceled in the meantime (e.g. due to an eviction) so we
+                    // don't keep loading a region nobody wants anymore.
+                    const CANCEL_CHECK_INTERVAL: u32 = 1024;
+                    let is_load_canceled = || {
+                        core.engine
+                            .read()
+                            .range_manager()
+                            .region_meta(region.id)
+                            .map(|meta| meta.get_state() == RegionState::LoadingCanceled)
+                            .unwrap_or(false)
+                    };
+
                     let safe_point = 'load_snapshot: {
+                        let mut scanned = 0u32;
                         for &cf in DATA_CFS {
                             let handle = skiplist_engine.cf_handle(cf);
                             let seq = snapshot.sequence_number();
@@ -973,6 +1428,16 @@ impl Runnable for BackgroundRunner {
                                 Ok(mut iter) => {
                                     iter.seek_to_first().unwrap();
                                     while iter.valid().unwrap() {
+                                        scanned += 1;
+                                        if scanned % CANCEL_CHECK_INTERVAL == 0
+                                            && is_load_canceled()
+                                        {
+                                            info!(
+                                                "snapshot load canceled mid-load";
+                                                "region" => ?region,
+                                            );
+                                            break 'load_snapshot None;
+                                        }
                                         // use the sequence number from RocksDB snapshot here as
                                         // the kv is clearly visible
                                         let mut encoded_key =
@@ -1055,13 +1520,19 @@ impl Runnable for BackgroundRunner {
                             &delete_range_scheduler,
                             safe_point,
                         ) {
-                            let duration = start.saturating_elapsed();
+                            let duration = slow_timer.saturating_elapsed();
                             RANGE_LOAD_TIME_HISTOGRAM.observe(duration.as_secs_f64());
                             info!(
                                 "Loading region finished";
                                 "region" => ?region,
                                 "duration(sec)" => ?duration,
                             );
+                            slow_log!(
+                                T slow_timer,
+                                "loading region {} into range cache, mem_usage {}MB",
+                                region.id,
+                                ReadableSize(core.memory_controller.mem_usage() as u64).as_mb()
+                            );
                         } else {
                             info!("Loading region canceled";"region" => ?region);
                         }
@@ -1073,7 +1544,12 @@ impl Runnable for BackgroundRunner {
                         core.on_snapshot_load_failed(&region, &delete_range_scheduler, true);
                     }
                 };
-                self.range_load_remote.spawn(f);
+                spawn_with_deadline(
+                    &self.range_load_remote,
+                    "range-cache-engine-load",
+                    root!("range-cache-engine-load"; f; region_id),
+                    BACKGROUND_TASK_DEADLINE,
+                );
             }
             BackgroundTask::MemoryCheckAndEvict => {
                 let mem_usage = self.core.memory_controller.mem_usage();
@@ -1088,7 +1564,12 @@ impl Runnable for BackgroundRunner {
                         core.evict_on_soft_limit_reached(&delete_range_scheduler);
                         core.memory_controller.set_memory_checking(false);
                     };
-                    self.load_evict_remote.spawn(task);
+                    spawn_with_deadline(
+                        &self.load_evict_remote,
+                        "range-cache-engine-evict",
+                        task,
+                        BACKGROUND_TASK_DEADLINE,
+                    );
                 } else {
                     self.core.memory_controller.set_memory_checking(false);
                 }
@@ -1100,7 +1581,34 @@ impl Runnable for BackgroundRunner {
                 let delete_range_scheduler = self.delete_range_scheduler.clone();
                 let core = self.core.clone();
                 let task = async move { core.top_regions_load_evict(&delete_range_scheduler) };
-                self.load_evict_remote.spawn(task);
+                spawn_with_deadline(
+                    &self.load_evict_remote,
+                    "range-cache-engine-top-regions-load-evict",
+                    task,
+                    BACKGROUND_TASK_DEADLINE,
+                );
+            }
+            BackgroundTask::PersistCachedRegionList => {
+                let path = self.config.value().cached_region_list_path.clone();
+                if path.is_empty() {
+                    return;
+                }
+                let core = self.core.clone();
+                let task = async move {
+                    let ranges = core.cached_ranges_for_persist();
+                    if let Err(e) = persist_cached_ranges(&path, &ranges) {
+                        warn!(
+                            "persist cached region list failed";
+                            "err" => ?e, "path" => &path,
+                        );
+                    }
+                };
+                spawn_with_deadline(
+                    &self.load_evict_remote,
+                    "range-cache-engine-persist-cached-region-list",
+                    task,
+                    BACKGROUND_TASK_DEADLINE,
+                );
             }
             BackgroundTask::CleanLockTombstone(snapshot_seqno) => {
                 if snapshot_seqno < self.last_seqno {
@@ -1178,7 +1686,12 @@ impl Runnable for BackgroundRunner {
                     fail::fail_point!("clean_lock_tombstone_done");
                 };
 
-                self.lock_cleanup_remote.spawn(f);
+                spawn_with_deadline(
+                    &self.lock_cleanup_remote,
+                    "range-cache-engine-lock-cleanup",
+                    f,
+                    BACKGROUND_TASK_DEADLINE,
+                );
             }
         }
     }
@@ -1203,7 +1716,7 @@ impl RunnableWithTimer for BackgroundRunner {
     }
 
     fn get_interval(&self) -> Duration {
-        Duration::from_secs(10)
+        self.config.value().background_tick_interval.0
     }
 }
 
@@ -1214,17 +1727,43 @@ pub struct DeleteRangeRunner {
     // condition between them. Periodically, these delayed ranges will be checked to see if it is
     // ready to be deleted.
     delay_regions: Vec<Region>,
+    // First time each currently-delayed region entered `delay_regions`, keyed by
+    // region id. `on_timeout` reclassifies `delay_regions` from scratch on every
+    // tick, so this is kept separately to report the real wait time instead of
+    // one that resets on every retry.
+    delay_regions_since: HashMap<u64, Instant>,
+    config: Arc<VersionTrack<RangeCacheEngineConfig>>,
 }
 
 impl DeleteRangeRunner {
-    fn new(engine: Arc<RwLock<RangeCacheMemoryEngineCore>>) -> Self {
+    fn new(
+        engine: Arc<RwLock<RangeCacheMemoryEngineCore>>,
+        config: Arc<VersionTrack<RangeCacheEngineConfig>>,
+    ) -> Self {
         Self {
             engine,
             delay_regions: vec![],
+            delay_regions_since: HashMap::default(),
+            config,
         }
     }
 
+    // Keeps the delayed-delete-region gauges in sync with the current queue so
+    // operators can see when memory reclamation is being blocked.
+    fn update_delay_metrics(&self) {
+        DELAYED_DELETE_REGIONS_GAUGE.set(self.delay_regions.len() as i64);
+        let max_age = self
+            .delay_regions_since
+            .values()
+            .map(|since| since.saturating_elapsed().as_secs_f64())
+            .fold(0.0, f64::max);
+        DELAYED_DELETE_REGIONS_MAX_AGE_SECONDS.set(max_age);
+    }
+
+    #[instrument(skip_all, fields(region_count = regions.len()))]
     fn delete_regions(&mut self, regions: &[Region]) {
+        let slow_timer =
+            SlowTimer::from(self.config.value().delete_regions_slow_log_threshold.0);
         let skiplist_engine = self.engine.read().engine();
         for r in regions {
             let range = CacheRange::from_region(r);
@@ -1239,6 +1778,12 @@ impl DeleteRangeRunner {
 
         #[cfg(test)]
         flush_epoch();
+
+        slow_log!(
+            T slow_timer,
+            "deleting {} regions from range cache engine",
+            regions.len()
+        );
     }
 }
 
@@ -1261,19 +1806,29 @@ impl Runnable for DeleteRangeRunner {
                         assert_eq!(region_meta.get_state(), RegionState::Evicting);
                         // If the range is overlapped with ranges in `ranges_being_written`, the
                         // range has to be delayed to delete. See comment on `delay_ranges`.
-                        if region_meta.is_in_gc()
-                            || core
-                                .range_manager
-                                .is_overlapped_with_regions_being_written(region_meta.get_range())
-                        {
+                        let is_in_gc = region_meta.is_in_gc();
+                        let is_overlapped_with_being_written = core
+                            .range_manager
+                            .is_overlapped_with_regions_being_written(region_meta.get_range());
+                        if is_in_gc || is_overlapped_with_being_written {
+                            if !self.delay_regions_since.contains_key(&r.id) {
+                                self.delay_regions_since.insert(r.id, Instant::now());
+                                if is_in_gc {
+                                    DELAYED_DELETE_REGIONS_COUNT_STATIC.in_gc.inc();
+                                } else {
+                                    DELAYED_DELETE_REGIONS_COUNT_STATIC.overlapping_write.inc();
+                                }
+                            }
                             regions_to_delay.push(r);
                         } else {
+                            self.delay_regions_since.remove(&r.id);
                             regions_to_delete.push(r);
                         }
                     }
                     (regions_to_delay, regions_to_delete)
                 };
                 self.delay_regions.append(&mut regions_to_delay);
+                self.update_delay_metrics();
                 if !regions_to_delete.is_empty() {
                     self.delete_regions(&regions_to_delete);
                 }
@@ -1293,7 +1848,7 @@ impl RunnableWithTimer for DeleteRangeRunner {
     }
 
     fn get_interval(&self) -> Duration {
-        Duration::from_millis(500)
+        self.config.value().delete_range_check_interval.0
     }
 }
 
@@ -1419,6 +1974,13 @@ impl Filter {
         if commit_ts > self.safe_point {
             return Ok(());
         }
+        // This compares `commit_ts` against `safe_point` alone, same as the storage GC
+        // worker's own compaction filter does. A long-running transaction's reads need
+        // versions below `safe_point` to stay visible until it finishes, and `TxnStatusCache`
+        // (src/storage/txn/txn_status_cache.rs) is explicitly prepared, but not yet used,
+        // for recognizing this case. That cache lives in the main storage crate, several
+        // layers above this one, and the disk-side compaction filter doesn't consult it for
+        // this either - so there's no existing integration point to mirror here yet.
 
         // Just like what rocksdb compaction filter does, we do not handle internal
         // keys (representing different MVCC versions of the same user key) that have
@@ -1905,9 +2467,14 @@ pub mod tests {
             engine.core.clone(),
             memory_controller.clone(),
             None,
+            None,
+            None,
             engine.expected_region_size(),
             Duration::from_secs(100),
             Arc::new(MockPdClient {}),
+            Arc::new(VersionTrack::new(RangeCacheEngineConfig::config_for_test())),
+            Arc::default(),
+            RegionCacheStatsTracker::default(),
         );
         worker.core.gc_region(&region, 40, 100);
 
@@ -1982,9 +2549,14 @@ pub mod tests {
             engine.core.clone(),
             memory_controller.clone(),
             None,
+            None,
+            None,
             engine.expected_region_size(),
             Duration::from_secs(100),
             Arc::new(MockPdClient {}),
+            Arc::new(VersionTrack::new(RangeCacheEngineConfig::config_for_test())),
+            Arc::default(),
+            RegionCacheStatsTracker::default(),
         );
 
         // gc should not hanlde keys with larger seqno than oldest seqno
@@ -2143,9 +2715,14 @@ pub mod tests {
             engine.core.clone(),
             memory_controller.clone(),
             None,
+            None,
+            None,
             engine.expected_region_size(),
             Duration::from_secs(100),
             Arc::new(MockPdClient {}),
+            Arc::new(VersionTrack::new(RangeCacheEngineConfig::config_for_test())),
+            Arc::default(),
+            RegionCacheStatsTracker::default(),
         );
         let filter = worker.core.gc_region(&region1, 100, 100);
         assert_eq!(2, filter.filtered);
@@ -2160,9 +2737,14 @@ pub mod tests {
             engine.core.clone(),
             memory_controller.clone(),
             None,
+            None,
+            None,
             engine.expected_region_size(),
             Duration::from_secs(100),
             Arc::new(MockPdClient {}),
+            Arc::new(VersionTrack::new(RangeCacheEngineConfig::config_for_test())),
+            Arc::default(),
+            RegionCacheStatsTracker::default(),
         );
         worker.core.gc_region(&region2, 100, 100);
         assert_eq!(2, filter.filtered);
@@ -2210,9 +2792,14 @@ pub mod tests {
             engine.core.clone(),
             memory_controller.clone(),
             None,
+            None,
+            None,
             engine.expected_region_size(),
             Duration::from_secs(100),
             Arc::new(MockPdClient {}),
+            Arc::new(VersionTrack::new(RangeCacheEngineConfig::config_for_test())),
+            Arc::default(),
+            RegionCacheStatsTracker::default(),
         );
 
         let filter = worker.core.gc_region(&region, 20, 200);
@@ -2310,9 +2897,14 @@ pub mod tests {
             engine.core.clone(),
             memory_controller,
             None,
+            None,
+            None,
             engine.expected_region_size(),
             Duration::from_secs(100),
             Arc::new(MockPdClient {}),
+            Arc::new(VersionTrack::new(RangeCacheEngineConfig::config_for_test())),
+            Arc::default(),
+            RegionCacheStatsTracker::default(),
         );
         let range = CacheRange::from_region(&region);
         let s1 = engine.snapshot(1, 0, range.clone(), 10, u64::MAX);
@@ -2456,9 +3048,14 @@ pub mod tests {
             engine.core.clone(),
             memory_controller,
             None,
+            None,
+            None,
             engine.expected_region_size(),
             Duration::from_secs(100),
             Arc::new(MockPdClient {}),
+            Arc::new(VersionTrack::new(RangeCacheEngineConfig::config_for_test())),
+            Arc::default(),
+            RegionCacheStatsTracker::default(),
         );
 
         let regions: Vec<_> = engine
@@ -2619,9 +3216,14 @@ pub mod tests {
             engine.core.clone(),
             memory_controller,
             None,
+            None,
+            None,
             engine.expected_region_size(),
             Duration::from_secs(100),
             Arc::new(MockPdClient {}),
+            Arc::new(VersionTrack::new(RangeCacheEngineConfig::config_for_test())),
+            Arc::default(),
+            RegionCacheStatsTracker::default(),
         );
         let regions = runner.core.regions_for_gc();
         assert_eq!(2, regions.len());
@@ -3001,9 +3603,20 @@ pub mod tests {
         let pd_client = Arc::new(MockPdClient { tx: Mutex::new(tx) });
         let gc_interval = Duration::from_millis(100);
         let load_evict_interval = Duration::from_millis(200);
+        let persist_interval = Duration::from_millis(300);
         let (scheduler, mut rx) = dummy_scheduler();
-        let (handle, stop) =
-            BgWorkManager::start_tick(scheduler, pd_client, gc_interval, load_evict_interval);
+        let config = Arc::new(VersionTrack::new(RangeCacheEngineConfig {
+            gc_interval: ReadableDuration(gc_interval),
+            ..RangeCacheEngineConfig::config_for_test()
+        }));
+        let cron = BgWorkManager::start_tick(
+            scheduler,
+            pd_client,
+            gc_interval,
+            load_evict_interval,
+            persist_interval,
+            config,
+        );
 
         let Some(BackgroundTask::Gc(GcTask { safe_point })) =
             rx.recv_timeout(10 * gc_interval).unwrap()
@@ -3018,7 +3631,6 @@ pub mod tests {
         // Must get ts from PD.
         pd_client_rx.try_recv().unwrap();
 
-        stop.send(true).unwrap();
-        handle.join().unwrap();
+        drop(cron);
     }
 }
@@ -21,9 +21,21 @@ impl ConfigManager for RangeCacheConfigManager {
     ) -> std::result::Result<(), Box<dyn std::error::Error>> {
         {
             let change = change.clone();
-            self.0
-                .update(move |cfg: &mut RangeCacheEngineConfig| cfg.update(change))?;
+            self.0.update(move |cfg: &mut RangeCacheEngineConfig| {
+                cfg.update(change)?;
+                // Reject a change that would leave the config in a state this engine
+                // can't run with (e.g. soft-limit-threshold >= hard-limit-threshold),
+                // the same check applied at startup, rather than silently applying it
+                // and only finding out the next time it's consulted.
+                cfg.validate()
+            })?;
         }
+        // Every field above is either consulted live off this `VersionTrack`
+        // already (e.g. memory thresholds, `expected_region_size`,
+        // `evict_min_duration`) or, for `gc_interval`/`load_evict_interval`,
+        // partially live (see `BgWorkManager::start_tick`'s comment on its
+        // `config` parameter) — so no further fan-out to running components
+        // is needed here.
         info!(
             "range cache config changed";
             "change" => ?change,
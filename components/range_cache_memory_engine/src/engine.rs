@@ -10,6 +10,7 @@ use std::{
     },
 };
 
+use collections::HashMap;
 use crossbeam::epoch::{self, default_collector, Guard};
 use crossbeam_skiplist::{
     base::{Entry, OwnedIter},
@@ -17,23 +18,33 @@ use crossbeam_skiplist::{
 };
 use engine_rocks::RocksEngine;
 use engine_traits::{
-    CacheRange, EvictReason, FailedReason, IterOptions, Iterable, KvEngine, RangeCacheEngine,
-    RegionEvent, Result, CF_DEFAULT, CF_LOCK, CF_WRITE, DATA_CFS,
+    CacheConsistencySnapshot, CacheRange, EvictReason, FailedReason, IterOptions, Iterable,
+    KvEngine, RangeCacheEngine, RegionEvent, Result, CF_DEFAULT, CF_LOCK, CF_WRITE, DATA_CFS,
 };
 use kvproto::metapb::Region;
 use parking_lot::RwLock;
-use raftstore::coprocessor::RegionInfoProvider;
+use raftstore::{coprocessor::RegionInfoProvider, store::util::RegionReadProgressRegistry};
+use serde::Serialize;
 use slog_global::error;
-use tikv_util::{config::VersionTrack, info};
+use tikv_util::{config::VersionTrack, info, time::Instant, warn};
 
 use crate::{
-    background::{BackgroundTask, BgWorkManager, PdRangeHintService},
+    background::{BackgroundTask, BgWorkManager, GcTask, PdRangeHintService},
+    encryption::ValueEncryptionKey,
+    hot_keys::HotKeyTracker,
     keys::{
         encode_key_for_boundary_with_mvcc, encode_key_for_boundary_without_mvcc, InternalBytes,
     },
     memory_controller::MemoryController,
-    range_manager::{LoadFailedReason, RangeCacheStatus, RegionManager, RegionState},
+    metrics::{
+        IN_MEMORY_ENGINE_SELF_FENCE_COUNT, IN_MEMORY_ENGINE_SKIPLIST_INSERT_DURATION,
+        IN_MEMORY_ENGINE_SKIPLIST_REMOVE_DURATION, RANGE_CACHE_HIT_COUNT, RANGE_CACHE_MISS_COUNT,
+    },
+    range_manager::{
+        region_keyspace_id, LoadFailedReason, RangeCacheStatus, RegionManager, RegionState,
+    },
     read::{RangeCacheIterator, RangeCacheSnapshot},
+    region_cache_stats::{RegionCacheStats, RegionCacheStatsTracker},
     statistics::Statistics,
     RangeCacheEngineConfig, RangeCacheEngineContext,
 };
@@ -93,13 +104,17 @@ impl SkiplistHandle {
 
     pub fn insert(&self, key: InternalBytes, value: InternalBytes, guard: &Guard) {
         assert!(key.memory_controller_set() && value.memory_controller_set());
+        let start = Instant::now();
         self.0.insert(key, value, guard).release(guard);
+        IN_MEMORY_ENGINE_SKIPLIST_INSERT_DURATION.observe(start.saturating_elapsed_secs());
     }
 
     pub fn remove(&self, key: &InternalBytes, guard: &Guard) {
+        let start = Instant::now();
         if let Some(entry) = self.0.remove(key, guard) {
             entry.release(guard);
         }
+        IN_MEMORY_ENGINE_SKIPLIST_REMOVE_DURATION.observe(start.saturating_elapsed_secs());
     }
 
     pub fn iterator(
@@ -183,6 +198,30 @@ impl Debug for SkiplistEngine {
     }
 }
 
+/// Point-in-time snapshot returned by `RangeCacheMemoryEngine::status`.
+#[derive(Debug, Serialize)]
+pub struct RangeCacheEngineStatus {
+    pub memory_usage: usize,
+    pub memory_soft_limit_reached: bool,
+    pub gc_running: bool,
+    pub region_count_by_state: HashMap<&'static str, usize>,
+    // Sum of `CachedRegionStatus::filtered_versions` across `regions`, for
+    // callers that just want the in-memory engine's side of a combined GC
+    // report without summing the per-region breakdown themselves.
+    pub gc_filtered_versions: u64,
+    pub regions: Vec<CachedRegionStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CachedRegionStatus {
+    pub region_id: u64,
+    pub state: &'static str,
+    pub safe_point: u64,
+    // MVCC versions this region's gc pass has filtered out of the cache so
+    // far. See `RangeMeta::filtered_versions`.
+    pub filtered_versions: u64,
+}
+
 pub struct RangeCacheMemoryEngineCore {
     pub(crate) engine: SkiplistEngine,
     pub(crate) range_manager: RegionManager,
@@ -190,15 +229,17 @@ pub struct RangeCacheMemoryEngineCore {
 
 impl Default for RangeCacheMemoryEngineCore {
     fn default() -> Self {
-        Self::new()
+        Self::new(0)
     }
 }
 
 impl RangeCacheMemoryEngineCore {
-    pub fn new() -> RangeCacheMemoryEngineCore {
+    pub fn new(store_id: u64) -> RangeCacheMemoryEngineCore {
+        let mut range_manager = RegionManager::default();
+        range_manager.set_store_id(store_id);
         RangeCacheMemoryEngineCore {
             engine: SkiplistEngine::new(),
-            range_manager: RegionManager::default(),
+            range_manager,
         }
     }
 
@@ -248,6 +289,27 @@ pub struct RangeCacheMemoryEngine {
 
     // `write_batch_id_allocator` is used to allocate id for each write batch
     write_batch_id_allocator: Arc<AtomicU64>,
+
+    // Set when the config enables encryption and a key manager was supplied;
+    // `None` otherwise, in which case values are stored and read back as
+    // plaintext.
+    value_encryption_key: Option<Arc<ValueEncryptionKey>>,
+
+    // Backs `record_hot_key`/`top_hot_keys`. Always constructed, but only
+    // populated while `RangeCacheEngineConfig::hot_key_collection_enabled` is
+    // set, so holding one costs nothing for the common case where it's off.
+    hot_key_tracker: HotKeyTracker,
+
+    // Backs `region_cache_stats`. Always constructed and always populated --
+    // unlike `hot_key_tracker` this isn't gated by a config flag, since the
+    // counters it holds are plain integers rather than a per-key sketch.
+    region_cache_stats: RegionCacheStatsTracker,
+
+    // Backs `resolved_ts`, consulted by `RangeCacheSnapshot::new` to bound a
+    // stale read served off a learner peer (see
+    // `RangeCacheEngineConfig::cache_on_learner`). `None` if this store
+    // wasn't given a registry to begin with.
+    region_read_progress: Option<RegionReadProgressRegistry>,
 }
 
 impl RangeCacheMemoryEngine {
@@ -260,25 +322,55 @@ impl RangeCacheMemoryEngine {
         region_info_provider: Option<Arc<dyn RegionInfoProvider>>,
     ) -> Self {
         info!("init range cache memory engine";);
-        let core = Arc::new(RwLock::new(RangeCacheMemoryEngineCore::new()));
+        let core = Arc::new(RwLock::new(RangeCacheMemoryEngineCore::new(
+            range_cache_engine_context.store_id,
+        )));
         let skiplist_engine = { core.read().engine().clone() };
 
         let RangeCacheEngineContext {
             config,
             statistics,
             pd_client,
+            region_read_progress,
+            gc_safe_point,
+            store_id: _,
+            key_manager,
         } = range_cache_engine_context;
         assert!(config.value().enabled);
+        core.write()
+            .mut_range_manager()
+            .set_cache_on_learner(config.value().cache_on_learner);
         let memory_controller = Arc::new(MemoryController::new(config.clone(), skiplist_engine));
 
+        let value_encryption_key = if config.value().enable_encryption {
+            key_manager.as_deref().and_then(|key_manager| {
+                match ValueEncryptionKey::new(key_manager) {
+                    Ok(key) => key.map(Arc::new),
+                    Err(e) => {
+                        warn!("failed to set up range cache value encryption, values will be \
+                               stored in plaintext"; "err" => ?e);
+                        None
+                    }
+                }
+            })
+        } else {
+            None
+        };
+
+        let region_cache_stats = RegionCacheStatsTracker::default();
         let bg_work_manager = Arc::new(BgWorkManager::new(
             core.clone(),
             pd_client,
             config.value().gc_interval.0,
             config.value().load_evict_interval.0,
             config.value().expected_region_size(),
+            gc_safe_point,
             memory_controller.clone(),
             region_info_provider,
+            region_read_progress.clone(),
+            config.clone(),
+            statistics.clone(),
+            region_cache_stats.clone(),
         ));
 
         Self {
@@ -290,6 +382,10 @@ impl RangeCacheMemoryEngine {
             config,
             lock_modification_bytes: Arc::default(),
             write_batch_id_allocator: Arc::default(),
+            value_encryption_key,
+            hot_key_tracker: HotKeyTracker::default(),
+            region_cache_stats,
+            region_read_progress,
         }
     }
 
@@ -297,18 +393,69 @@ impl RangeCacheMemoryEngine {
         self.config.value().expected_region_size()
     }
 
+    pub(crate) fn value_encryption_key(&self) -> Option<Arc<ValueEncryptionKey>> {
+        self.value_encryption_key.clone()
+    }
+
     pub fn new_region(&self, region: Region) {
         self.core.write().range_manager.new_region(region);
     }
 
     pub fn load_region(&self, region: Region) -> result::Result<(), LoadFailedReason> {
-        self.core.write().mut_range_manager().load_region(region)
+        let region_id = region.id;
+        let result = if let Some(keyspace_id) = region_keyspace_id(&region) {
+            if let Some(share) = self.config.value().keyspace_quota(keyspace_id) {
+                let region_budget = self.config.value().hard_limit_threshold()
+                    / self.config.value().expected_region_size().max(1);
+                let keyspace_budget = (region_budget as f64 * share) as usize;
+                let mut core = self.core.write();
+                if core.range_manager().keyspace_region_count(keyspace_id) >= keyspace_budget {
+                    return Err(LoadFailedReason::KeyspaceQuotaExceeded);
+                }
+                core.mut_range_manager().load_region(region)
+            } else {
+                self.core.write().mut_range_manager().load_region(region)
+            }
+        } else {
+            self.core.write().mut_range_manager().load_region(region)
+        };
+        if result.is_ok() {
+            self.region_cache_stats.record_load(region_id);
+        }
+        result
+    }
+
+    /// Record `range` as requested to always be cached (see `PdRangeHintService`),
+    /// so that `pinned_ranges` can be consulted later, e.g. to re-pin a region as
+    /// soon as this store becomes its leader.
+    pub fn record_pinned_range(&self, range: CacheRange) {
+        self.core.write().mut_range_manager().record_pinned_range(range);
+    }
+
+    pub fn pinned_ranges(&self) -> Vec<CacheRange> {
+        self.core.read().range_manager().pinned_ranges().to_vec()
+    }
+
+    /// Record `range` as configured for the write-around policy (see
+    /// `PdRangeHintService`), so `RangeCacheWriteBatch` evicts rather than
+    /// writes through any region overlapping it.
+    pub fn record_write_around_range(&self, range: CacheRange) {
+        self.core
+            .write()
+            .mut_range_manager()
+            .record_write_around_range(range);
+    }
+
+    pub(crate) fn is_write_around(&self, range: &CacheRange) -> bool {
+        self.core.read().range_manager().is_write_around_range(range)
     }
 
     /// Evict a region from the in-memory engine. After this call, the region
     /// will not be readable, but the data of the region may not be deleted
     /// immediately due to some ongoing snapshots.
     pub fn evict_region(&self, region: &Region, evict_reason: EvictReason) {
+        self.hot_key_tracker.remove_region(region.get_id());
+        self.region_cache_stats.record_eviction(region.get_id());
         let deleteable_regions = self
             .core
             .write()
@@ -329,8 +476,50 @@ impl RangeCacheMemoryEngine {
         }
     }
 
+    /// Fences the engine off after the caller (the consistency checker,
+    /// checksum verification, or shadow reads) detected cached data
+    /// diverging from the disk engine beyond its configured threshold:
+    /// evicts every currently cached region, pauses admission so none of
+    /// them come back, and bumps a critical metric for alerting. The engine
+    /// keeps running and `HybridEngine` falls back to serving reads from
+    /// RocksDB as it already does for any region that isn't cached; only an
+    /// operator calling `resume_admission` (e.g. via a debug endpoint) lifts
+    /// the fence.
+    pub fn fence_for_corruption(&self, reason: &str) {
+        error!(
+            "range cache engine fencing itself off after detected corruption";
+            "reason" => reason,
+        );
+        IN_MEMORY_ENGINE_SELF_FENCE_COUNT.inc();
+        self.pause_admission();
+        let regions: Vec<Region> = self
+            .core
+            .read()
+            .range_manager()
+            .regions()
+            .values()
+            .map(|meta| meta.region().clone())
+            .collect();
+        for region in regions {
+            self.evict_region(&region, EvictReason::CorruptionDetected);
+        }
+    }
+
     // It handles the pending range and check whether to buffer write for this
     // range.
+    //
+    // This takes `self.core.write()` once per call, and today it is called once
+    // per region per apply poll round (via `RangeCacheWriteBatch::prepare_for_region`
+    // from `ApplyContext::prepare_for`). Collapsing that into a single batched call
+    // covering every region touched in a round would require the raftstore apply
+    // loop to know the full set of affected regions before it starts applying any
+    // of them; in practice `ApplyPoller::handle_normal` drives one `ApplyFsm` (one
+    // region) at a time and only learns the round's full FSM set in `end()`, after
+    // entries have already been applied and `prepare_for` has already run for each
+    // of them. Moving the call out of the per-region path would mean restructuring
+    // the apply loop itself, which every deployment runs, not just ones with the
+    // range cache engine enabled. Given that, the write-lock hold here is kept
+    // short and per-region rather than attempting to batch across regions.
     pub(crate) fn prepare_for_apply(
         &self,
         write_batch_id: u64,
@@ -392,6 +581,16 @@ impl RangeCacheMemoryEngine {
         &self.bg_work_manager
     }
 
+    /// Schedules an immediate gc pass with the given `safe_point`, the same
+    /// task the periodic cron in `BgWorkManager::start_tick` schedules on its
+    /// own, for an operator to request one ahead of schedule (e.g. from a
+    /// debug endpoint) without waiting out `gc_interval`.
+    pub fn trigger_gc(&self, safe_point: u64) -> result::Result<(), String> {
+        self.bg_work_manager
+            .schedule_task(BackgroundTask::Gc(GcTask { safe_point }))
+            .map_err(|e| format!("{:?}", e))
+    }
+
     pub fn memory_controller(&self) -> Arc<MemoryController> {
         self.memory_controller.clone()
     }
@@ -400,6 +599,84 @@ impl RangeCacheMemoryEngine {
         self.statistics.clone()
     }
 
+    /// `region_id`'s resolved ts as tracked by resolved-ts, or `0` if it isn't
+    /// known (no registry was supplied, or the peer's read progress isn't
+    /// initialized yet). Consulted by `RangeCacheSnapshot::new` to bound stale
+    /// reads served off a learner peer; see
+    /// `RangeCacheEngineConfig::cache_on_learner`. `0` is deliberately a
+    /// fail-closed sentinel here, not "no bound": `region_snapshot` rejects
+    /// any `read_ts > resolved_ts`, and `read_ts` is never `0`, so an unknown
+    /// resolved-ts rejects every stale read instead of admitting all of them.
+    pub(crate) fn resolved_ts(&self, region_id: u64) -> u64 {
+        self.region_read_progress
+            .as_ref()
+            .and_then(|registry| registry.get_safe_ts(&region_id))
+            .unwrap_or(0)
+    }
+
+    /// Records one read of `key` in `region_id` for hot-key diagnostics, a
+    /// no-op unless `RangeCacheEngineConfig::hot_key_collection_enabled` is
+    /// set. Called from the read path (see `RangeCacheSnapshot`), so it
+    /// avoids doing any work beyond the config check when collection is off.
+    pub(crate) fn record_hot_key(&self, region_id: u64, key: &[u8]) {
+        if self.config.value().hot_key_collection_enabled {
+            self.hot_key_tracker.record(region_id, key);
+        }
+    }
+
+    /// Returns up to `top` of `region_id`'s hottest keys observed via
+    /// `record_hot_key`, sorted by descending approximate read count. See
+    /// `hot_keys::HotKeyTracker`.
+    pub fn top_hot_keys(&self, region_id: u64, top: usize) -> Vec<(Vec<u8>, u64)> {
+        self.hot_key_tracker.top_keys(region_id, top)
+    }
+
+    /// Records `bytes` served out of the cache for a value read in
+    /// `region_id`, for `region_cache_stats`. Called from the read path
+    /// alongside `record_hot_key`.
+    pub(crate) fn record_bytes_served(&self, region_id: u64, bytes: u64) {
+        self.region_cache_stats.record_bytes_served(region_id, bytes);
+    }
+
+    /// Returns hit/miss counts, bytes served, time since last load, and
+    /// eviction count for every region this engine has ever recorded
+    /// activity for. See `region_cache_stats::RegionCacheStatsTracker`.
+    pub fn region_cache_stats(&self) -> Vec<RegionCacheStats> {
+        self.region_cache_stats.all_stats()
+    }
+
+    /// Snapshot of the engine's memory usage, per-region state, and whether a
+    /// gc pass is currently running, meant for surfacing on a debug endpoint
+    /// rather than for anything on the read/write path. Per-region byte sizes
+    /// aren't tracked today, so only region id, state, safe point, and gc
+    /// filtered version count are reported per region.
+    pub fn status(&self) -> RangeCacheEngineStatus {
+        let core = self.core.read();
+        let range_manager = core.range_manager();
+        let mut region_count_by_state: HashMap<&'static str, usize> = HashMap::default();
+        let mut regions = Vec::with_capacity(range_manager.regions().len());
+        let mut gc_filtered_versions = 0;
+        for meta in range_manager.regions().values() {
+            let state = meta.get_state();
+            *region_count_by_state.entry(state.as_str()).or_insert(0) += 1;
+            gc_filtered_versions += meta.filtered_versions();
+            regions.push(CachedRegionStatus {
+                region_id: meta.region().id,
+                state: state.as_str(),
+                safe_point: meta.safe_point(),
+                filtered_versions: meta.filtered_versions(),
+            });
+        }
+        RangeCacheEngineStatus {
+            memory_usage: self.memory_controller.mem_usage(),
+            memory_soft_limit_reached: self.memory_controller.reached_soft_limit(),
+            gc_running: range_manager.is_gc_running(),
+            region_count_by_state,
+            gc_filtered_versions,
+            regions,
+        }
+    }
+
     pub fn alloc_write_batch_id(&self) -> u64 {
         self.write_batch_id_allocator
             .fetch_add(1, Ordering::Relaxed)
@@ -468,6 +745,10 @@ impl RangeCacheEngine for RangeCacheMemoryEngine {
         self.config.value().enabled
     }
 
+    fn should_evict_on_hibernate(&self) -> bool {
+        self.config.value().evict_on_hibernate
+    }
+
     fn on_region_event(&self, event: RegionEvent) {
         match event {
             RegionEvent::Eviction { region, reason } => {
@@ -498,6 +779,75 @@ impl RangeCacheEngine for RangeCacheMemoryEngine {
             }
         }
     }
+
+    fn region_cached_size_and_keys(&self, range: &CacheRange) -> Option<(u64, u64)> {
+        if !self.core.read().range_manager().region_actively_cached(range) {
+            return None;
+        }
+
+        let skiplist_engine = self.core.read().engine();
+        let guard = &epoch::pin();
+        let mut size = 0u64;
+        let mut keys = 0u64;
+        for &cf in DATA_CFS {
+            let (start, end) = if cf == CF_LOCK {
+                encode_key_for_boundary_without_mvcc(range)
+            } else {
+                encode_key_for_boundary_with_mvcc(range)
+            };
+            let handle = skiplist_engine.cf_handle(cf);
+            let mut iter = handle.iterator();
+            iter.seek(&start, guard);
+            while iter.valid() && iter.key() < &end {
+                size += (iter.key().as_slice().len() + iter.value().as_slice().len()) as u64;
+                keys += 1;
+                iter.next(guard);
+            }
+        }
+        Some((size, keys))
+    }
+
+    fn record_region_cache_hit(&self, region_id: u64) {
+        RANGE_CACHE_HIT_COUNT.inc();
+        self.region_cache_stats.record_hit(region_id);
+    }
+
+    fn record_region_cache_miss(&self, region_id: u64) {
+        RANGE_CACHE_MISS_COUNT.inc();
+        self.region_cache_stats.record_miss(region_id);
+    }
+
+    fn pause_admission(&self) {
+        self.core.write().mut_range_manager().set_admission_paused(true);
+    }
+
+    fn resume_admission(&self) {
+        self.core.write().mut_range_manager().set_admission_paused(false);
+    }
+
+    fn consistency_snapshot(&self) -> CacheConsistencySnapshot {
+        // Held across both reads below so a concurrent eviction, split, or GC
+        // can't leave `safe_point` and `region_epochs` describing different
+        // moments in time.
+        let core = self.core.write();
+        let mut snapshot = CacheConsistencySnapshot::default();
+        for meta in core
+            .range_manager()
+            .regions()
+            .values()
+            .filter(|meta| meta.get_state() == RegionState::Active)
+        {
+            snapshot.safe_point = snapshot.safe_point.min(meta.safe_point());
+            snapshot
+                .region_epochs
+                .insert(meta.region().id, meta.region().get_region_epoch().version);
+        }
+        snapshot
+    }
+
+    fn fence_for_corruption(&self, reason: &str) {
+        self.fence_for_corruption(reason)
+    }
 }
 
 impl Iterable for RangeCacheMemoryEngine {
@@ -514,7 +864,7 @@ pub mod tests {
     use std::sync::Arc;
 
     use crossbeam::epoch;
-    use engine_traits::{CacheRange, CF_DEFAULT, CF_LOCK, CF_WRITE};
+    use engine_traits::{CacheRange, RangeCacheEngine, CF_DEFAULT, CF_LOCK, CF_WRITE};
     use tikv_util::config::{ReadableSize, VersionTrack};
 
     use super::SkiplistEngine;
@@ -562,6 +912,70 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_fence_for_corruption_evicts_all_and_pauses_admission() {
+        let engine = RangeCacheMemoryEngine::new(RangeCacheEngineContext::new_for_tests(Arc::new(
+            VersionTrack::new(RangeCacheEngineConfig::config_for_test()),
+        )));
+        let region1 = new_region(1, b"k1", b"k3");
+        let region2 = new_region(2, b"k3", b"k5");
+        engine.new_region(region1);
+        engine.new_region(region2);
+        assert_eq!(
+            count_region(engine.core.read().range_manager(), |m| m.get_state() == Active),
+            2
+        );
+
+        engine.fence_for_corruption("test corruption");
+
+        assert_eq!(
+            count_region(engine.core.read().range_manager(), |m| m.get_state() == Active),
+            0
+        );
+        // Admission stays paused until an operator calls `resume_admission`.
+        let region3 = new_region(3, b"k5", b"k7");
+        assert!(engine.load_region(region3).is_err());
+    }
+
+    #[test]
+    fn test_fence_for_corruption_reachable_through_trait() {
+        // Callers that only hold a `&dyn RangeCacheEngine` (e.g. storage, via
+        // `RangeCacheEngineExt::fence_range_cache_for_corruption`) must still reach
+        // the real fencing logic, not the trait's no-op default.
+        let engine = RangeCacheMemoryEngine::new(RangeCacheEngineContext::new_for_tests(Arc::new(
+            VersionTrack::new(RangeCacheEngineConfig::config_for_test()),
+        )));
+        let region1 = new_region(1, b"k1", b"k3");
+        engine.new_region(region1);
+
+        RangeCacheEngine::fence_for_corruption(&engine, "test corruption");
+
+        assert_eq!(
+            count_region(engine.core.read().range_manager(), |m| m.get_state() == Active),
+            0
+        );
+    }
+
+    #[test]
+    fn test_consistency_snapshot_covers_only_active_regions() {
+        let engine = RangeCacheMemoryEngine::new(RangeCacheEngineContext::new_for_tests(Arc::new(
+            VersionTrack::new(RangeCacheEngineConfig::config_for_test()),
+        )));
+        let empty = engine.consistency_snapshot();
+        assert_eq!(empty.safe_point, u64::MAX);
+        assert!(empty.region_epochs.is_empty());
+
+        let region1 = new_region(1, b"k1", b"k3");
+        engine.new_region(region1);
+        let region2 = new_region(2, b"k3", b"k5");
+        engine.load_region(region2).unwrap();
+
+        let snapshot = engine.consistency_snapshot();
+        assert_eq!(snapshot.safe_point, 0);
+        assert_eq!(snapshot.region_epochs.len(), 1);
+        assert_eq!(snapshot.region_epochs.get(&1), Some(&0));
+    }
+
     #[test]
     fn test_delete_range() {
         let delete_range_cf = |cf| {
@@ -572,9 +986,14 @@ pub mod tests {
                 enabled: true,
                 gc_interval: Default::default(),
                 load_evict_interval: Default::default(),
+                background_tick_interval: Default::default(),
+                delete_range_check_interval: Default::default(),
                 soft_limit_threshold: Some(ReadableSize(300)),
                 hard_limit_threshold: Some(ReadableSize(500)),
                 expected_region_size: Some(ReadableSize::mb(20)),
+                evict_min_duration: Default::default(),
+                evict_on_hibernate: false,
+                ..Default::default()
             }));
             let mem_controller = Arc::new(MemoryController::new(config.clone(), skiplist.clone()));
 
@@ -627,9 +1046,14 @@ pub mod tests {
             enabled: true,
             gc_interval: Default::default(),
             load_evict_interval: Default::default(),
+            background_tick_interval: Default::default(),
+            delete_range_check_interval: Default::default(),
             soft_limit_threshold: Some(ReadableSize(300)),
             hard_limit_threshold: Some(ReadableSize(500)),
             expected_region_size: Some(ReadableSize::mb(20)),
+            evict_min_duration: Default::default(),
+            evict_on_hibernate: false,
+            ..Default::default()
         }));
         let mem_controller = Arc::new(MemoryController::new(config.clone(), skiplist.clone()));
 
@@ -0,0 +1,199 @@
+// Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! An approximate per-region hot-key tracker, fed from the in-memory
+//! engine's read path and queried by the
+//! `/debug/range_cache/region/<id>/hot_keys` debug endpoint (`tikv-ctl
+//! range-cache hot-keys` does not read it; it reports itself unsupported).
+//!
+//! This is purely a diagnostic aid: nothing in the engine reads it back to
+//! make an eviction or admission decision. So, rather than an exact per-key
+//! counter (memory proportional to the keyspace), each region gets a
+//! fixed-size count-min sketch for estimating any key's read count, plus a
+//! small bounded "candidates" map holding the best estimates seen so far as
+//! the approximate top-K.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use collections::HashMap;
+use parking_lot::Mutex;
+
+/// Number of independent hash rows in the sketch. More rows trade memory for
+/// a tighter (lower-variance) count estimate.
+const SKETCH_DEPTH: usize = 4;
+
+/// Number of counters per row.
+const SKETCH_WIDTH: usize = 2048;
+
+/// Default number of candidate keys retained per region, i.e. the largest
+/// `top` that `HotKeyTracker::top_keys` can usefully serve.
+pub const DEFAULT_TRACKED_KEYS_PER_REGION: usize = 100;
+
+struct CountMinSketch {
+    rows: Vec<[u32; SKETCH_WIDTH]>,
+}
+
+impl CountMinSketch {
+    fn new() -> Self {
+        CountMinSketch {
+            rows: vec![[0; SKETCH_WIDTH]; SKETCH_DEPTH],
+        }
+    }
+
+    fn slot(row: usize, key: &[u8]) -> usize {
+        let mut hasher = DefaultHasher::new();
+        row.hash(&mut hasher);
+        key.hash(&mut hasher);
+        (hasher.finish() as usize) % SKETCH_WIDTH
+    }
+
+    /// Records one observation of `key`, returning the updated (approximate,
+    /// never-under-counting) estimate of its total observation count.
+    fn record(&mut self, key: &[u8]) -> u64 {
+        let mut estimate = u32::MAX;
+        for (row, counters) in self.rows.iter_mut().enumerate() {
+            let slot = &mut counters[Self::slot(row, key)];
+            *slot = slot.saturating_add(1);
+            estimate = estimate.min(*slot);
+        }
+        estimate as u64
+    }
+}
+
+struct RegionHotKeys {
+    sketch: CountMinSketch,
+    // Keyed on the user key; value is the sketch estimate as of the last time
+    // this key was recorded. Bounded to `max_candidates` entries: once full,
+    // a newly-seen key only displaces the current weakest candidate if its
+    // estimate is higher.
+    candidates: HashMap<Vec<u8>, u64>,
+    max_candidates: usize,
+}
+
+impl RegionHotKeys {
+    fn new(max_candidates: usize) -> Self {
+        RegionHotKeys {
+            sketch: CountMinSketch::new(),
+            candidates: HashMap::default(),
+            max_candidates,
+        }
+    }
+
+    fn record(&mut self, key: &[u8]) {
+        let estimate = self.sketch.record(key);
+        if let Some(slot) = self.candidates.get_mut(key) {
+            *slot = estimate;
+            return;
+        }
+        if self.candidates.len() < self.max_candidates {
+            self.candidates.insert(key.to_vec(), estimate);
+            return;
+        }
+        let weakest = self
+            .candidates
+            .iter()
+            .min_by_key(|(_, &count)| count)
+            .map(|(k, &count)| (k.clone(), count));
+        if let Some((weakest_key, weakest_count)) = weakest
+            && estimate > weakest_count
+        {
+            self.candidates.remove(&weakest_key);
+            self.candidates.insert(key.to_vec(), estimate);
+        }
+    }
+
+    fn top_keys(&self, top: usize) -> Vec<(Vec<u8>, u64)> {
+        let mut keys: Vec<_> = self
+            .candidates
+            .iter()
+            .map(|(k, &count)| (k.clone(), count))
+            .collect();
+        keys.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+        keys.truncate(top);
+        keys
+    }
+}
+
+/// Tracks approximate per-region key read frequency for diagnostic purposes.
+/// Cheap to hold even when unused: recording only takes place when
+/// `RangeCacheEngineConfig::hot_key_collection_enabled` is set, checked by
+/// the caller before calling `record`.
+#[derive(Clone)]
+pub struct HotKeyTracker {
+    per_region: Arc<Mutex<HashMap<u64, RegionHotKeys>>>,
+    max_candidates: usize,
+}
+
+impl HotKeyTracker {
+    pub fn new(max_candidates: usize) -> Self {
+        HotKeyTracker {
+            per_region: Arc::new(Mutex::new(HashMap::default())),
+            max_candidates,
+        }
+    }
+
+    pub fn record(&self, region_id: u64, key: &[u8]) {
+        self.per_region
+            .lock()
+            .entry(region_id)
+            .or_insert_with(|| RegionHotKeys::new(self.max_candidates))
+            .record(key);
+    }
+
+    /// Returns up to `top` of the region's hottest keys seen so far, sorted
+    /// by descending approximate read count. Empty if the region has no
+    /// recorded reads, e.g. hot-key collection was disabled or the region
+    /// isn't cached.
+    pub fn top_keys(&self, region_id: u64, top: usize) -> Vec<(Vec<u8>, u64)> {
+        self.per_region
+            .lock()
+            .get(&region_id)
+            .map_or_else(Vec::new, |r| r.top_keys(top))
+    }
+
+    pub fn remove_region(&self, region_id: u64) {
+        self.per_region.lock().remove(&region_id);
+    }
+}
+
+impl Default for HotKeyTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRACKED_KEYS_PER_REGION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_keys_tracks_hottest() {
+        let tracker = HotKeyTracker::new(2);
+        for _ in 0..10 {
+            tracker.record(1, b"hot1");
+        }
+        for _ in 0..5 {
+            tracker.record(1, b"hot2");
+        }
+        tracker.record(1, b"cold");
+
+        let top = tracker.top_keys(1, 2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].0, b"hot1".to_vec());
+        assert_eq!(top[1].0, b"hot2".to_vec());
+        assert!(top[0].1 >= 10);
+        assert!(top[1].1 >= 5);
+    }
+
+    #[test]
+    fn test_remove_region_clears_candidates() {
+        let tracker = HotKeyTracker::new(10);
+        tracker.record(1, b"k1");
+        assert_eq!(tracker.top_keys(1, 10).len(), 1);
+        tracker.remove_region(1);
+        assert!(tracker.top_keys(1, 10).is_empty());
+    }
+}
@@ -6,40 +6,56 @@
 #![feature(core_intrinsics)]
 #![feature(slice_pattern)]
 
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{atomic::AtomicU64, Arc},
+    time::Duration,
+};
 
+use collections::HashMap;
+use encryption::DataKeyManager;
 use futures::future::ready;
 use online_config::OnlineConfig;
 use pd_client::PdClient;
+use raftstore::store::util::RegionReadProgressRegistry;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tikv_util::config::{ReadableDuration, ReadableSize, VersionTrack};
 
 mod background;
 pub mod config;
+mod encryption;
 mod engine;
+mod hot_keys;
 mod keys;
 mod memory_controller;
 mod metrics;
+mod observer;
 mod perf_context;
+pub mod persist;
 #[cfg(test)]
 mod prop_test;
 mod range_manager;
 mod range_stats;
 mod read;
+mod region_cache_stats;
 mod region_label;
 mod statistics;
 pub mod test_util;
 mod write_batch;
 
 pub use background::{BackgroundRunner, BackgroundTask, GcTask};
-pub use engine::{RangeCacheMemoryEngine, SkiplistHandle};
+pub use engine::{
+    CachedRegionStatus, RangeCacheEngineStatus, RangeCacheMemoryEngine, SkiplistHandle,
+};
 pub use keys::{
     decode_key, encode_key_for_boundary_without_mvcc, encoding_for_filter, InternalBytes,
     InternalKey, ValueType,
 };
 pub use metrics::flush_range_cache_engine_statistics;
+pub use observer::{register_range_cache_engine_observer, RangeCacheEngineRegionChangeObserver};
 pub use range_manager::{RangeCacheStatus, RegionState};
+pub use range_stats::EvictionPolicy;
+pub use region_cache_stats::RegionCacheStats;
 pub use statistics::Statistics as RangeCacheMemoryEngineStatistics;
 use txn_types::TimeStamp;
 pub use write_batch::RangeCacheWriteBatch;
@@ -56,9 +72,124 @@ pub struct RangeCacheEngineConfig {
     pub enabled: bool,
     pub gc_interval: ReadableDuration,
     pub load_evict_interval: ReadableDuration,
+    // How often the background runner refreshes memory-usage and per-state
+    // region-count metrics. Consulted live by `BackgroundRunner::get_interval`,
+    // so it can be tuned online without a restart.
+    pub background_tick_interval: ReadableDuration,
+    // How often the delete-range worker re-checks regions it delayed deleting
+    // because they overlapped with an in-flight write. Consulted live by
+    // `DeleteRangeRunner::get_interval`.
+    pub delete_range_check_interval: ReadableDuration,
     pub soft_limit_threshold: Option<ReadableSize>,
     pub hard_limit_threshold: Option<ReadableSize>,
     pub expected_region_size: Option<ReadableSize>,
+    // Caps each API v2 keyspace's share of the engine's region budget, as a
+    // fraction of `hard_limit_threshold` / `expected_region_size`. Keyed by
+    // the keyspace id formatted as a decimal string, since config file
+    // formats require string map keys. A keyspace with no entry here is
+    // unbounded. Consulted live by admission (rejecting a load that would
+    // push the keyspace over its share) and by the load/evict background
+    // task (evicting the keyspace's own regions back under its share), so
+    // one noisy tenant can't evict everyone else's hot data to make room for
+    // its own.
+    #[online_config(skip)]
+    pub keyspace_quotas: HashMap<String, f64>,
+    // Do not evict a region that's been cached for less than this duration,
+    // even if it falls out of the top-N active regions, so a region isn't
+    // loaded and then immediately evicted again by a transient dip in
+    // activity. Consulted live by `RangeStatsManager`.
+    pub evict_min_duration: Option<ReadableDuration>,
+    // Enables a controller that nudges `soft_limit_threshold` up or down,
+    // within `[soft_limit_auto_tune_min, soft_limit_auto_tune_max]`, based on
+    // the cache's observed hit rate and read latency, so the cache's memory
+    // budget tracks the workload instead of needing to be retuned by hand as
+    // it shifts. Off by default. Never tunes outside of the configured
+    // bounds, and the configured max is clamped to `hard_limit_threshold`.
+    // Consulted live by `BackgroundRunnerCore::auto_tune_soft_limit`, folded
+    // into the same tick as `top_regions_load_evict`.
+    pub soft_limit_auto_tune: bool,
+    // Bounds for `soft_limit_auto_tune`, both inclusive. Default to `0` and
+    // `hard_limit_threshold` respectively, i.e. unbounded.
+    pub soft_limit_auto_tune_min: Option<ReadableSize>,
+    pub soft_limit_auto_tune_max: Option<ReadableSize>,
+    // Evict a region's cached data once its peer enters hibernation, since a
+    // hibernating region is idle by definition and keeping it cached wastes
+    // the memory budget. Off by default: re-admission after the region wakes
+    // up again only happens through the normal PD region-label path, so
+    // enabling this can cause hot-but-currently-idle regions to be reloaded
+    // repeatedly.
+    pub evict_on_hibernate: bool,
+    // Gc/LoadRegion/DeleteRegions background tasks that take longer than
+    // these thresholds are reported through a slow log, the same way a slow
+    // foreground request is. Consulted live by `BackgroundRunner::run` and
+    // `DeleteRangeRunner::delete_regions`.
+    pub gc_slow_log_threshold: ReadableDuration,
+    pub load_region_slow_log_threshold: ReadableDuration,
+    pub delete_regions_slow_log_threshold: ReadableDuration,
+    // Encrypt values before inserting them into the skiplist, and decrypt them
+    // on read, using the data key supplied by the cluster's encryption-at-rest
+    // key manager. Off by default, and silently has no effect if no key
+    // manager was supplied when the engine was created: clusters that don't
+    // enable encryption-at-rest shouldn't pay for it here either. Only read
+    // once, at engine construction; like `enabled`, flipping it through
+    // online config has no effect on an already-running engine.
+    pub enable_encryption: bool,
+    // Maintains an approximate, bounded-memory per-region count of how often
+    // each key is read through the in-memory engine (a count-min sketch plus
+    // a small top-K candidate set), queryable via the
+    // `/debug/range_cache/region/<id>/hot_keys` endpoint (`tikv-ctl
+    // range-cache hot-keys` does not read it; it reports itself unsupported).
+    // Off by default: it's purely a diagnostic aid and every read pays a
+    // small sketch-update cost while it's on. Consulted live by
+    // `RangeCacheMemoryEngine::record_hot_key`.
+    pub hot_key_collection_enabled: bool,
+    // Allow a learner peer to be admitted to the cache, for stores dedicated
+    // to serving stale reads off a learner (e.g. a read-only replica) rather
+    // than leader traffic. Off by default: admitting a learner is only safe
+    // once the caller also enforces that stale reads never ask for a
+    // `read_ts` past the region's resolved ts, which `region_snapshot`
+    // consults `region_read_progress` for whenever this is set. Witness peers
+    // stay unconditionally ineligible regardless of this flag, since they
+    // hold no data to cache. Only read once, at engine construction; like
+    // `enabled`, flipping it through online config has no effect on an
+    // already-running engine.
+    pub cache_on_learner: bool,
+    // Periodically checkpoints the engine's pinned ranges and every currently
+    // `Active` region's range to `cached_region_list_path`, and on startup
+    // re-seeds that set (pinning each range and, where `RegionInfoProvider`
+    // already knows the region covering it, immediately requesting a load)
+    // so a restart doesn't leave previously-hot data cold until the normal
+    // load/evict pass or a leader election happens to notice it again. Off
+    // by default; requires `cached_region_list_path` to be set. See
+    // `BgWorkManager::restore_cached_region_list`.
+    pub persist_cached_region_list: bool,
+    // Where `persist_cached_region_list` reads from and writes to. Required
+    // if `persist_cached_region_list` is enabled.
+    #[online_config(skip)]
+    pub cached_region_list_path: String,
+    // How often the persisted cached-region-list checkpoint is refreshed.
+    // Like `gc_interval`/`load_evict_interval`, the cadence itself is fixed
+    // at startup; only `persist_cached_region_list` and
+    // `cached_region_list_path` are re-read on every tick.
+    pub cached_region_list_persist_interval: ReadableDuration,
+    // Number of threads backing the range-load worker pool, so loading many
+    // regions (e.g. after a restart, or a burst of PD region-label hints)
+    // doesn't serialize behind a single thread. Different regions may load
+    // concurrently; a single region never has more than one `LoadRegion`
+    // task in flight at a time regardless of this setting, since
+    // `RegionManager::load_region` already refuses a region that's
+    // `Pending`/`ReadyToLoad`/`Loading`, so no extra ordering is needed here.
+    // Concurrent loads share one `MemoryController`, whose hard-limit check
+    // is consulted by every load thread, so they can't collectively blow the
+    // hard limit even though each only checks its own progress. Only read
+    // once, at engine construction; like `enabled`, flipping it through
+    // online config has no effect on an already-running engine.
+    pub load_threads: usize,
+    // Which candidate `RangeStatsManager` evicts first when it has to choose,
+    // consulted by both `evict_on_soft_limit_reached` and
+    // `top_regions_load_evict`. See `range_stats::EvictionPolicy`. Read live,
+    // so it can be changed without a restart.
+    pub eviction_policy: EvictionPolicy,
 }
 
 impl Default for RangeCacheEngineConfig {
@@ -68,9 +199,28 @@ impl Default for RangeCacheEngineConfig {
             gc_interval: ReadableDuration(Duration::from_secs(180)),
             // Each load/evict operation should run within five minutes.
             load_evict_interval: ReadableDuration(Duration::from_secs(300)),
+            background_tick_interval: ReadableDuration(Duration::from_secs(10)),
+            delete_range_check_interval: ReadableDuration(Duration::from_millis(500)),
             soft_limit_threshold: None,
             hard_limit_threshold: None,
             expected_region_size: None,
+            keyspace_quotas: HashMap::default(),
+            evict_min_duration: None,
+            soft_limit_auto_tune: false,
+            soft_limit_auto_tune_min: None,
+            soft_limit_auto_tune_max: None,
+            evict_on_hibernate: false,
+            gc_slow_log_threshold: ReadableDuration(Duration::from_secs(30)),
+            load_region_slow_log_threshold: ReadableDuration(Duration::from_secs(30)),
+            delete_regions_slow_log_threshold: ReadableDuration(Duration::from_secs(1)),
+            enable_encryption: false,
+            hot_key_collection_enabled: false,
+            cache_on_learner: false,
+            persist_cached_region_list: false,
+            cached_region_list_path: String::new(),
+            cached_region_list_persist_interval: ReadableDuration(Duration::from_secs(600)),
+            load_threads: 1,
+            eviction_policy: EvictionPolicy::default(),
         }
     }
 }
@@ -101,9 +251,46 @@ impl RangeCacheEngineConfig {
             )));
         }
 
+        for (keyspace_id, share) in &self.keyspace_quotas {
+            if keyspace_id.parse::<u32>().is_err() {
+                return Err(Error::InvalidArgument(format!(
+                    "keyspace-quotas key {} is not a valid keyspace id",
+                    keyspace_id
+                )));
+            }
+            if !(0.0..=1.0).contains(share) {
+                return Err(Error::InvalidArgument(format!(
+                    "keyspace-quotas share {} for keyspace {} is not in [0, 1]",
+                    share, keyspace_id
+                )));
+            }
+        }
+        if self.keyspace_quotas.values().sum::<f64>() > 1.0 {
+            return Err(Error::InvalidArgument(format!(
+                "keyspace-quotas shares sum to more than 1: {:?}",
+                self.keyspace_quotas
+            )));
+        }
+
+        if self.soft_limit_auto_tune
+            && self.soft_limit_auto_tune_min() > self.soft_limit_auto_tune_max()
+        {
+            return Err(Error::InvalidArgument(format!(
+                "soft-limit-auto-tune-min {} is larger than soft-limit-auto-tune-max {}",
+                self.soft_limit_auto_tune_min(),
+                self.soft_limit_auto_tune_max()
+            )));
+        }
+
         Ok(())
     }
 
+    pub fn keyspace_quota(&self, keyspace_id: u32) -> Option<f64> {
+        self.keyspace_quotas
+            .get(&keyspace_id.to_string())
+            .copied()
+    }
+
     pub fn soft_limit_threshold(&self) -> usize {
         self.soft_limit_threshold.map_or(0, |r| r.0 as usize)
     }
@@ -119,15 +306,50 @@ impl RangeCacheEngineConfig {
         )
     }
 
+    pub fn evict_min_duration(&self) -> Duration {
+        self.evict_min_duration
+            .map_or(range_stats::DEFAULT_EVICT_MIN_DURATION, |r| r.0)
+    }
+
+    pub fn soft_limit_auto_tune_min(&self) -> usize {
+        self.soft_limit_auto_tune_min.map_or(0, |r| r.0 as usize)
+    }
+
+    pub fn soft_limit_auto_tune_max(&self) -> usize {
+        self.soft_limit_auto_tune_max
+            .map_or(self.hard_limit_threshold(), |r| {
+                (r.0 as usize).min(self.hard_limit_threshold())
+            })
+    }
+
     pub fn config_for_test() -> RangeCacheEngineConfig {
         RangeCacheEngineConfig {
             enabled: true,
             gc_interval: ReadableDuration(Duration::from_secs(180)),
             load_evict_interval: ReadableDuration(Duration::from_secs(300)), /* Should run within
                                                                               * five minutes */
+            background_tick_interval: ReadableDuration(Duration::from_secs(10)),
+            delete_range_check_interval: ReadableDuration(Duration::from_millis(500)),
             soft_limit_threshold: Some(ReadableSize::gb(1)),
             hard_limit_threshold: Some(ReadableSize::gb(2)),
             expected_region_size: Some(ReadableSize::mb(20)),
+            keyspace_quotas: HashMap::default(),
+            evict_min_duration: None,
+            soft_limit_auto_tune: false,
+            soft_limit_auto_tune_min: None,
+            soft_limit_auto_tune_max: None,
+            evict_on_hibernate: false,
+            gc_slow_log_threshold: ReadableDuration(Duration::from_secs(30)),
+            load_region_slow_log_threshold: ReadableDuration(Duration::from_secs(30)),
+            delete_regions_slow_log_threshold: ReadableDuration(Duration::from_secs(1)),
+            enable_encryption: false,
+            hot_key_collection_enabled: false,
+            cache_on_learner: false,
+            persist_cached_region_list: false,
+            cached_region_list_path: String::new(),
+            cached_region_list_persist_interval: ReadableDuration(Duration::from_secs(600)),
+            load_threads: 1,
+            eviction_policy: EvictionPolicy::default(),
         }
     }
 }
@@ -136,17 +358,31 @@ pub struct RangeCacheEngineContext {
     config: Arc<VersionTrack<RangeCacheEngineConfig>>,
     statistics: Arc<RangeCacheMemoryEngineStatistics>,
     pd_client: Arc<dyn PdClient>,
+    region_read_progress: Option<RegionReadProgressRegistry>,
+    gc_safe_point: Option<Arc<AtomicU64>>,
+    store_id: u64,
+    key_manager: Option<Arc<DataKeyManager>>,
 }
 
 impl RangeCacheEngineContext {
+    // `store_id` is used to find this store's own peer in a region's peer list,
+    // so admission can skip regions where the local peer is a witness or a
+    // learner that never serves reads. Pass 0 if it isn't known yet (e.g. a
+    // store that hasn't bootstrapped); that disables the check instead of
+    // rejecting every region.
     pub fn new(
         config: Arc<VersionTrack<RangeCacheEngineConfig>>,
         pd_client: Arc<dyn PdClient>,
+        store_id: u64,
     ) -> RangeCacheEngineContext {
         RangeCacheEngineContext {
             config,
             statistics: Arc::default(),
             pd_client,
+            region_read_progress: None,
+            gc_safe_point: None,
+            store_id,
+            key_manager: None,
         }
     }
 
@@ -163,9 +399,42 @@ impl RangeCacheEngineContext {
             config,
             statistics: Arc::default(),
             pd_client: Arc::new(MockPdClient),
+            region_read_progress: None,
+            gc_safe_point: None,
+            store_id: 0,
+            key_manager: None,
         }
     }
 
+    // Lets the gc background task lower-bound a region's safe point by the
+    // resolved ts tracked in `registry`, instead of relying purely on
+    // `now - gc_interval`.
+    pub fn with_region_read_progress(mut self, registry: RegionReadProgressRegistry) -> Self {
+        self.region_read_progress = Some(registry);
+        self
+    }
+
+    // Lets the gc background task clamp a region's safe point by `safe_point`,
+    // the same value the storage GC worker's compaction filter uses, instead of
+    // deriving its own from `now - gc_interval`. Without this, the in-memory
+    // engine's gc could run ahead of or lag behind the disk engine's, each
+    // redundantly scanning for versions the other has already determined are (or
+    // are not yet) safe to remove.
+    pub fn with_gc_safe_point(mut self, safe_point: Arc<AtomicU64>) -> Self {
+        self.gc_safe_point = Some(safe_point);
+        self
+    }
+
+    // Lets the engine encrypt values before inserting them into the skiplist,
+    // and decrypt them on read, using `key_manager`'s data key. Only takes
+    // effect when `enable_encryption` is also set in the config; the key
+    // manager is accepted unconditionally here so callers don't need to know
+    // the config value to decide whether to wire it in.
+    pub fn with_key_manager(mut self, key_manager: Arc<DataKeyManager>) -> Self {
+        self.key_manager = Some(key_manager);
+        self
+    }
+
     pub fn statistics(&self) -> Arc<RangeCacheMemoryEngineStatistics> {
         self.statistics.clone()
     }
@@ -128,9 +128,14 @@ mod tests {
             enabled: true,
             gc_interval: Default::default(),
             load_evict_interval: Default::default(),
+            background_tick_interval: Default::default(),
+            delete_range_check_interval: Default::default(),
             soft_limit_threshold: Some(ReadableSize(300)),
             hard_limit_threshold: Some(ReadableSize(500)),
             expected_region_size: Default::default(),
+            evict_min_duration: Default::default(),
+            evict_on_hibernate: false,
+            ..Default::default()
         }));
         let mc = MemoryController::new(config, skiplist_engine.clone());
         assert_eq!(mc.acquire(100), MemoryUsage::NormalUsage(100));
@@ -40,6 +40,19 @@ make_auto_flush_static_metric! {
         become_follower,
         memory_limit_reached,
         disabled,
+        destroyed,
+        hibernated,
+        flashback,
+        ineligible_peer,
+        unsafe_recovery,
+        manual,
+        write_around,
+        corruption_detected,
+    }
+
+    pub label_enum DelayedDeleteRegionCause {
+        in_gc,
+        overlapping_write,
     }
 
     pub struct GcFilteredCountVec: LocalIntCounter {
@@ -53,6 +66,10 @@ make_auto_flush_static_metric! {
     pub struct EvictionDurationVec: LocalHistogram {
         "type" => EvictReasonType,
     }
+
+    pub struct DelayedDeleteRegionCountVec: LocalIntCounter {
+        "cause" => DelayedDeleteRegionCause,
+    }
 }
 
 lazy_static! {
@@ -122,6 +139,80 @@ lazy_static! {
         exponential_buckets(0.00001, 2.0, 26).unwrap()
     )
     .unwrap();
+    pub static ref IN_MEMORY_ENGINE_SEEK_SKIPPED_VERSIONS: Histogram = register_histogram!(
+        "tikv_range_cache_memory_engine_seek_skipped_versions",
+        "Histogram of the number of internal key/delete versions skipped over before a seek-like \
+         call settles on a visible key",
+        exponential_buckets(1.0, 2.0, 20).unwrap()
+    )
+    .unwrap();
+    pub static ref IN_MEMORY_ENGINE_SKIPLIST_INSERT_DURATION: Histogram = register_histogram!(
+        "tikv_range_cache_memory_engine_skiplist_insert_duration",
+        "Histogram of skiplist insert duration",
+        exponential_buckets(0.00001, 2.0, 26).unwrap()
+    )
+    .unwrap();
+    pub static ref IN_MEMORY_ENGINE_SKIPLIST_REMOVE_DURATION: Histogram = register_histogram!(
+        "tikv_range_cache_memory_engine_skiplist_remove_duration",
+        "Histogram of skiplist remove duration",
+        exponential_buckets(0.00001, 2.0, 26).unwrap()
+    )
+    .unwrap();
+    pub static ref IN_MEMORY_ENGINE_ENCRYPT_DURATION: Histogram = register_histogram!(
+        "tikv_range_cache_memory_engine_encrypt_duration",
+        "Histogram of the time spent encrypting a value before inserting it into the range \
+         cache",
+        exponential_buckets(0.00001, 2.0, 26).unwrap()
+    )
+    .unwrap();
+    pub static ref IN_MEMORY_ENGINE_DECRYPT_DURATION: Histogram = register_histogram!(
+        "tikv_range_cache_memory_engine_decrypt_duration",
+        "Histogram of the time spent decrypting a value read from the range cache",
+        exponential_buckets(0.00001, 2.0, 26).unwrap()
+    )
+    .unwrap();
+    pub static ref DELAYED_DELETE_REGIONS_GAUGE: IntGauge = register_int_gauge!(
+        "tikv_range_cache_memory_engine_delayed_delete_regions",
+        "Number of regions currently delayed from being deleted from the range cache engine"
+    )
+    .unwrap();
+    pub static ref DELAYED_DELETE_REGIONS_MAX_AGE_SECONDS: Gauge = register_gauge!(
+        "tikv_range_cache_memory_engine_delayed_delete_regions_max_age_seconds",
+        "Age of the oldest region currently delayed from being deleted from the range cache \
+         engine"
+    )
+    .unwrap();
+    pub static ref DELAYED_DELETE_REGIONS_COUNT: IntCounterVec = register_int_counter_vec!(
+        "tikv_range_cache_memory_engine_delayed_delete_regions_total",
+        "Count of regions delayed from being deleted from the range cache engine, by cause",
+        &["cause"]
+    )
+    .unwrap();
+    // Bumped by `RangeCacheMemoryEngine::fence_for_corruption`. Should stay at
+    // 0 in a healthy cluster; any increase means cached data was found (or is
+    // suspected) to have diverged from the disk engine and needs operator
+    // attention before range cache admission is resumed.
+    pub static ref IN_MEMORY_ENGINE_SELF_FENCE_COUNT: IntCounter = register_int_counter!(
+        "tikv_range_cache_memory_engine_self_fence_total",
+        "Count of times the range cache engine fenced itself off after detecting corruption"
+    )
+    .unwrap();
+    // Global counterparts of `RangeCacheMemoryEngine::region_cache_stats`'
+    // per-region hit/miss counts. Kept as plain, unlabeled counters rather
+    // than labeled by region id, since the number of distinct regions a
+    // store sees over its lifetime makes a per-region label unbounded
+    // cardinality; use `region_cache_stats` for the per-region breakdown.
+    pub static ref RANGE_CACHE_HIT_COUNT: IntCounter = register_int_counter!(
+        "tikv_range_cache_memory_engine_hit_total",
+        "Count of snapshot reads served out of the range cache engine"
+    )
+    .unwrap();
+    pub static ref RANGE_CACHE_MISS_COUNT: IntCounter = register_int_counter!(
+        "tikv_range_cache_memory_engine_miss_total",
+        "Count of snapshot reads that fell back to the disk engine instead of being served out \
+         of the range cache engine"
+    )
+    .unwrap();
 }
 
 lazy_static! {
@@ -133,6 +224,8 @@ lazy_static! {
         auto_flush_from!(IN_MEMORY_ENGINE_LOCATE, InMemoryEngineTickerMetrics);
     pub static ref RANGE_EVICTION_DURATION_HISTOGRAM_STATIC: EvictionDurationVec =
         auto_flush_from!(RANGE_EVICTION_DURATION_HISTOGRAM, EvictionDurationVec);
+    pub static ref DELAYED_DELETE_REGIONS_COUNT_STATIC: DelayedDeleteRegionCountVec =
+        auto_flush_from!(DELAYED_DELETE_REGIONS_COUNT, DelayedDeleteRegionCountVec);
 }
 
 pub fn flush_range_cache_engine_statistics(statistics: &Arc<RangeCacheMemoryEngineStatistics>) {
@@ -204,5 +297,29 @@ pub(crate) fn observe_eviction_duration(secs: f64, evict_reason: EvictReason) {
         EvictReason::Disabled => RANGE_EVICTION_DURATION_HISTOGRAM_STATIC
             .disabled
             .observe(secs),
+        EvictReason::Destroyed => RANGE_EVICTION_DURATION_HISTOGRAM_STATIC
+            .destroyed
+            .observe(secs),
+        EvictReason::Hibernated => RANGE_EVICTION_DURATION_HISTOGRAM_STATIC
+            .hibernated
+            .observe(secs),
+        EvictReason::Flashback => RANGE_EVICTION_DURATION_HISTOGRAM_STATIC
+            .flashback
+            .observe(secs),
+        EvictReason::IneligiblePeer => RANGE_EVICTION_DURATION_HISTOGRAM_STATIC
+            .ineligible_peer
+            .observe(secs),
+        EvictReason::UnsafeRecovery => RANGE_EVICTION_DURATION_HISTOGRAM_STATIC
+            .unsafe_recovery
+            .observe(secs),
+        EvictReason::Manual => RANGE_EVICTION_DURATION_HISTOGRAM_STATIC
+            .manual
+            .observe(secs),
+        EvictReason::WriteAround => RANGE_EVICTION_DURATION_HISTOGRAM_STATIC
+            .write_around
+            .observe(secs),
+        EvictReason::CorruptionDetected => RANGE_EVICTION_DURATION_HISTOGRAM_STATIC
+            .corruption_detected
+            .observe(secs),
     }
 }
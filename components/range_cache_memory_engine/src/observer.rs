@@ -0,0 +1,84 @@
+// Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
+
+use engine_traits::{CacheRange, EvictReason, KvEngine, RangeCacheEngine, RegionEvent};
+use raft::StateRole;
+use raftstore::coprocessor::{
+    BoxRegionChangeObserver, BoxRoleObserver, Coprocessor, CoprocessorHost, ObserverContext,
+    RegionChangeEvent, RegionChangeObserver, RoleChange, RoleObserver,
+};
+
+use crate::RangeCacheMemoryEngine;
+
+/// Bridges raftstore region lifecycle events to the range cache engine so
+/// that a region's cached data is dropped as soon as the peer serving it is
+/// destroyed, instead of lingering until the next GC/auto-evict pass notices
+/// the region is gone.
+#[derive(Clone)]
+pub struct RangeCacheEngineRegionChangeObserver {
+    range_cache_engine: RangeCacheMemoryEngine,
+}
+
+impl RangeCacheEngineRegionChangeObserver {
+    pub fn new(range_cache_engine: RangeCacheMemoryEngine) -> Self {
+        Self { range_cache_engine }
+    }
+}
+
+impl Coprocessor for RangeCacheEngineRegionChangeObserver {}
+
+impl RegionChangeObserver for RangeCacheEngineRegionChangeObserver {
+    fn on_region_changed(
+        &self,
+        context: &mut ObserverContext<'_>,
+        event: RegionChangeEvent,
+        _role: StateRole,
+    ) {
+        if event != RegionChangeEvent::Destroy {
+            return;
+        }
+        self.range_cache_engine.on_region_event(RegionEvent::Eviction {
+            region: context.region().clone(),
+            reason: EvictReason::Destroyed,
+        });
+    }
+}
+
+impl RoleObserver for RangeCacheEngineRegionChangeObserver {
+    /// Pre-warms the cache as soon as this store becomes leader of a region
+    /// that falls inside a "cache always" pinned range (see
+    /// `PdRangeHintService`), so a failover doesn't leave reads of that range
+    /// hitting a cold cache until the next load/evict pass notices it.
+    fn on_role_change(&self, context: &mut ObserverContext<'_>, role_change: &RoleChange) {
+        if role_change.state != StateRole::Leader {
+            return;
+        }
+        let region = context.region();
+        let region_range = CacheRange::from_region(region);
+        let pinned = self.range_cache_engine.pinned_ranges();
+        if !pinned.iter().any(|r| overlaps(r, &region_range)) {
+            return;
+        }
+        if let Err(e) = self.range_cache_engine.load_region(region.clone()) {
+            tikv_util::warn!("pre-warm cache on leader election failed"; "err" => ?e, "region" => ?region);
+        }
+    }
+}
+
+fn overlaps(a: &CacheRange, b: &CacheRange) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+/// Registers a [`RangeCacheEngineRegionChangeObserver`] so that destroying a
+/// region on this store evicts it from `range_cache_engine` as well, and so
+/// that becoming leader of a region in a pinned range immediately schedules a
+/// cache load for it.
+pub fn register_range_cache_engine_observer(
+    host: &mut CoprocessorHost<impl KvEngine>,
+    range_cache_engine: RangeCacheMemoryEngine,
+) {
+    let observer = RangeCacheEngineRegionChangeObserver::new(range_cache_engine);
+    host.registry
+        .register_region_change_observer(1, BoxRegionChangeObserver::new(observer.clone()));
+    host.registry
+        .register_role_observer(1, BoxRoleObserver::new(observer));
+}
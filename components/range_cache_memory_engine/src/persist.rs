@@ -0,0 +1,114 @@
+// Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! Persists the set of ranges the engine considers worth keeping cached (its
+//! pinned ranges and every currently `Active` region's range) to a small
+//! file, so a restart can re-seed that set instead of starting completely
+//! cold. See `RangeCacheEngineConfig::persist_cached_region_list` and
+//! `BgWorkManager::restore_cached_region_list`.
+
+use std::{
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+use engine_traits::CacheRange;
+use file_system::{rename, File, OpenOptions};
+use slog_global::warn;
+
+const TMP_FILE_SUFFIX: &str = "tmp";
+
+/// Writes `ranges` to `path`, one hex-encoded `start\tend` pair per line.
+/// Replaces the previous contents atomically (write to a tmp file, then
+/// rename over `path`) so a crash mid-write can't leave a corrupt file
+/// behind for the next restart to choke on.
+pub(crate) fn persist_cached_ranges(path: &str, ranges: &[CacheRange]) -> io::Result<()> {
+    let path = Path::new(path);
+    let tmp_path = path.with_extension(TMP_FILE_SUFFIX);
+    let mut tmp_file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(&tmp_path)?;
+    for range in ranges {
+        writeln!(
+            tmp_file,
+            "{}\t{}",
+            hex::encode(&range.start),
+            hex::encode(&range.end)
+        )?;
+    }
+    tmp_file.sync_all()?;
+    rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Reads back the ranges written by `persist_cached_ranges`. A missing file
+/// (e.g. the first startup, or the feature having just been enabled) is
+/// treated as "nothing persisted yet", not an error. A line that fails to
+/// parse is skipped rather than failing the whole read, since one stale or
+/// truncated entry shouldn't prevent every other persisted range from being
+/// restored.
+pub fn load_persisted_ranges(path: &str) -> io::Result<Vec<CacheRange>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+    let mut ranges = Vec::new();
+    for line in io::BufReader::new(file).lines() {
+        let line = line?;
+        let Some((start, end)) = line.split_once('\t') else {
+            warn!("skipping malformed persisted cached range line"; "line" => &line);
+            continue;
+        };
+        match (hex::decode(start), hex::decode(end)) {
+            (Ok(start), Ok(end)) => ranges.push(CacheRange::new(start, end)),
+            _ => {
+                warn!("skipping persisted cached range line with invalid hex"; "line" => &line);
+            }
+        }
+    }
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_persist_and_load_round_trip() {
+        let dir = tempfile::Builder::new()
+            .prefix("test_persist_cached_ranges")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join("cached_ranges").to_str().unwrap().to_owned();
+
+        // Missing file is "nothing persisted yet", not an error.
+        assert!(load_persisted_ranges(&path).unwrap().is_empty());
+
+        let ranges = vec![
+            CacheRange::new(b"k1".to_vec(), b"k3".to_vec()),
+            CacheRange::new(b"k5".to_vec(), b"k9".to_vec()),
+        ];
+        persist_cached_ranges(&path, &ranges).unwrap();
+        assert_eq!(load_persisted_ranges(&path).unwrap(), ranges);
+
+        // Persisting again overwrites rather than appending.
+        let ranges2 = vec![CacheRange::new(b"k2".to_vec(), b"k4".to_vec())];
+        persist_cached_ranges(&path, &ranges2).unwrap();
+        assert_eq!(load_persisted_ranges(&path).unwrap(), ranges2);
+    }
+
+    #[test]
+    fn test_load_skips_malformed_lines() {
+        let dir = tempfile::Builder::new()
+            .prefix("test_load_skips_malformed")
+            .tempdir()
+            .unwrap();
+        let path = dir.path().join("cached_ranges");
+        std::fs::write(&path, "not-a-valid-line\nzz\tzz\n6b31\t6b33\n").unwrap();
+
+        let ranges = load_persisted_ranges(path.to_str().unwrap()).unwrap();
+        assert_eq!(ranges, vec![CacheRange::new(b"k1".to_vec(), b"k3".to_vec())]);
+    }
+}
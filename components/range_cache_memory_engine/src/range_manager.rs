@@ -10,9 +10,10 @@ use std::{
     sync::atomic::{AtomicBool, Ordering},
 };
 
+use api_version::{keyspace::Keyspace, ApiV2};
 use collections::HashMap;
 use engine_traits::{CacheRange, EvictReason, FailedReason};
-use kvproto::metapb::Region;
+use kvproto::metapb::{PeerRole, Region};
 use tikv_util::{info, time::Instant};
 
 use crate::{metrics::observe_eviction_duration, read::RangeCacheSnapshotMeta};
@@ -103,6 +104,18 @@ pub struct RangeMeta {
     in_gc: bool,
     // region eviction triggers info, used for logging.
     evict_info: Option<EvictInfo>,
+    // Bumped by `RegionManager::new_region_meta` every time this region id is
+    // (re)admitted to the cache. Unlike `safe_point`/the region epoch, this
+    // changes even when a region is evicted and reloaded with otherwise
+    // identical region state, so it lets callers outside this crate (the
+    // coprocessor cache, notably) detect that cached data was rebuilt from
+    // scratch across a load/evict cycle.
+    load_generation: u64,
+    // Cumulative count of MVCC versions this region's gc pass has filtered
+    // out of the cache (see `Filter`/`FilterMetrics`). Surfaced alongside the
+    // disk-side compaction filter's counters on the status server so an
+    // operator can see both engines' GC progress for a region side by side.
+    filtered_versions: u64,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -122,9 +135,23 @@ impl RangeMeta {
             state: RegionState::Pending,
             in_gc: false,
             evict_info: None,
+            load_generation: 0,
+            filtered_versions: 0,
         }
     }
 
+    pub(crate) fn load_generation(&self) -> u64 {
+        self.load_generation
+    }
+
+    pub(crate) fn filtered_versions(&self) -> u64 {
+        self.filtered_versions
+    }
+
+    pub(crate) fn add_filtered_versions(&mut self, filtered: u64) {
+        self.filtered_versions += filtered;
+    }
+
     #[inline]
     pub fn region(&self) -> &Region {
         &self.region
@@ -226,6 +253,11 @@ impl RangeMeta {
             state: source_meta.state,
             in_gc: source_meta.in_gc,
             evict_info: source_meta.evict_info,
+            load_generation: source_meta.load_generation,
+            // A child region's cached range is freshly derived from the
+            // source's data rather than re-gc'd, so it doesn't inherit a
+            // share of the source's filtered count.
+            filtered_versions: 0,
         }
     }
 
@@ -293,13 +325,150 @@ pub struct RegionManager {
     // all ranges of it are cleared from `ranges_being_written`.
     // write_batch_id --> Vec<cached_range>
     regions_being_written: HashMap<u64, Vec<CacheRange>>,
+    // The local store ID, used to find this store's own peer in a region's peer
+    // list so admission can be restricted to peers that actually serve reads.
+    // 0 means unknown (e.g. in tests, or a store that hasn't bootstrapped yet)
+    // and disables the role check rather than rejecting every region.
+    store_id: u64,
+    // Set from `RangeCacheEngineConfig::cache_on_learner`. Lets a learner peer
+    // be cached, for stores dedicated to serving stale reads, instead of the
+    // default of rejecting every non-voter peer. Witness peers are rejected
+    // unconditionally regardless of this flag, since they hold no data to
+    // cache at all.
+    cache_on_learner: bool,
+    // Ranges requested via a PD region label with `cache` set to `always` (see
+    // `PdRangeHintService`). Kept around, beyond the one-shot load triggered when
+    // the label first arrives, so that a region in one of these ranges can be
+    // reloaded immediately whenever this store becomes its leader, instead of
+    // waiting to be noticed by the periodic top-regions load/evict pass.
+    pinned_ranges: Vec<CacheRange>,
+    // Ranges requested via a PD region label with `write-policy` set to
+    // `write-around` (see `PdRangeHintService`). Writes into these ranges
+    // evict their region instead of being written through to the cache, so
+    // write-heavy, rarely re-read ranges don't churn the cache's memory
+    // budget or hold stale data after being overwritten.
+    write_around_ranges: Vec<CacheRange>,
+    // region_id --> number of times this id has been admitted to the cache
+    // (i.e. gained a `RangeMeta`) since this store started. Kept across a
+    // region's eviction, unlike `regions` itself, so a later reload of the
+    // same id is assigned a new value instead of restarting from the one a
+    // still-lingering coprocessor cache entry may have observed before the
+    // eviction.
+    region_load_generations: HashMap<u64, u64>,
+    // Set while the store is in import mode (see `SstImporter`'s switch-mode
+    // RPC). Bulk-load traffic rewrites the data it touches almost
+    // immediately, so admitting it into the cache just means evicting some
+    // other, genuinely hot region to make room for data that is about to
+    // change anyway. Existing cached regions keep serving reads as normal;
+    // only new admission is suspended.
+    admission_paused: bool,
 }
 
 impl RegionManager {
+    pub(crate) fn set_store_id(&mut self, store_id: u64) {
+        self.store_id = store_id;
+    }
+
+    pub(crate) fn set_cache_on_learner(&mut self, cache_on_learner: bool) {
+        self.cache_on_learner = cache_on_learner;
+    }
+
+    pub(crate) fn record_pinned_range(&mut self, range: CacheRange) {
+        if !self.pinned_ranges.contains(&range) {
+            self.pinned_ranges.push(range);
+        }
+    }
+
+    pub(crate) fn pinned_ranges(&self) -> &[CacheRange] {
+        &self.pinned_ranges
+    }
+
+    pub(crate) fn record_write_around_range(&mut self, range: CacheRange) {
+        if !self.write_around_ranges.contains(&range) {
+            self.write_around_ranges.push(range);
+        }
+    }
+
+    pub(crate) fn is_write_around_range(&self, range: &CacheRange) -> bool {
+        self.write_around_ranges
+            .iter()
+            .any(|r| r.contains_range(range))
+    }
+
+    pub(crate) fn set_admission_paused(&mut self, paused: bool) {
+        self.admission_paused = paused;
+    }
+
+    pub(crate) fn admission_paused(&self) -> bool {
+        self.admission_paused
+    }
+
+    // Witness peers hold no data, so caching a region on one just wastes the
+    // memory budget for data that will never be read from the cache; that
+    // rejection is unconditional. Learner peers are rejected too, unless
+    // `cache_on_learner` is set: a store dedicated to serving stale reads off
+    // a learner has no leader traffic to warm the cache from leadership
+    // transfer, so it needs learner peers to be admissible. `store_id` being
+    // unknown (0) disables this check rather than rejecting every region.
+    fn local_peer_is_cacheable(&self, region: &Region) -> bool {
+        if self.store_id == 0 {
+            return true;
+        }
+        let Some(peer) = region
+            .get_peers()
+            .iter()
+            .find(|p| p.get_store_id() == self.store_id)
+        else {
+            return true;
+        };
+        !peer.get_is_witness() && (self.cache_on_learner || peer.get_role() != PeerRole::Learner)
+    }
+
+    // Whether this store's own peer in `region` is a learner, used to decide
+    // whether `region_snapshot` needs to enforce the resolved-ts bound that
+    // only applies to stale reads served off a learner. An unknown `store_id`
+    // (0) reports `false`, consistent with `local_peer_is_cacheable` treating
+    // it as "no role restriction".
+    fn local_peer_is_learner(&self, region: &Region) -> bool {
+        if self.store_id == 0 {
+            return false;
+        }
+        region
+            .get_peers()
+            .iter()
+            .any(|p| p.get_store_id() == self.store_id && p.get_role() == PeerRole::Learner)
+    }
+
     pub(crate) fn regions(&self) -> &HashMap<u64, RangeMeta> {
         &self.regions
     }
 
+    // Number of regions in `regions` (in any state, including ones already
+    // being evicted) that belong to `keyspace_id`, used to enforce
+    // `RangeCacheEngineConfig::keyspace_quotas` at admission and on every
+    // load/evict tick. Computed on demand by scanning `regions` rather than
+    // maintained incrementally: neither is a hot read/write path, so the O(n)
+    // cost isn't worth the correctness risk
+    // of keeping a counter in sync with every eviction/split/merge site.
+    pub(crate) fn keyspace_region_count(&self, keyspace_id: u32) -> usize {
+        self.regions
+            .values()
+            .filter(|meta| region_keyspace_id(meta.region()) == Some(keyspace_id))
+            .count()
+    }
+
+    // The cached region belonging to `keyspace_id` with the oldest
+    // `load_generation`, i.e. the one that's gone longest without being
+    // (re)admitted. Used to pick an eviction victim when the keyspace is over
+    // its configured quota share.
+    pub(crate) fn oldest_keyspace_region(&self, keyspace_id: u32) -> Option<Region> {
+        self.regions
+            .values()
+            .filter(|meta| region_keyspace_id(meta.region()) == Some(keyspace_id))
+            .min_by_key(|meta| meta.load_generation())
+            .map(|meta| meta.region().clone())
+    }
+
     // load a new region directly in the active state.
     // This fucntion is used for unit/integration tests only.
     pub fn new_region(&mut self, region: Region) {
@@ -308,10 +477,13 @@ impl RegionManager {
         self.new_region_meta(range_meta);
     }
 
-    fn new_region_meta(&mut self, meta: RangeMeta) {
+    fn new_region_meta(&mut self, mut meta: RangeMeta) {
         assert!(!self.overlaps_with(&meta.range));
         let id = meta.region.id;
         let data_end_key = meta.range.end.clone();
+        let generation = self.region_load_generations.entry(id).or_default();
+        *generation += 1;
+        meta.load_generation = *generation;
         self.regions.insert(id, meta);
         self.regions_by_range.insert(data_end_key, id);
     }
@@ -343,6 +515,18 @@ impl RegionManager {
         }
     }
 
+    /// Whether `range` is exactly covered by a single `Active` cached region,
+    /// i.e. its size/key count can be read straight out of the in-memory
+    /// engine instead of needing a disk property scan.
+    pub fn region_actively_cached(&self, range: &CacheRange) -> bool {
+        let mut matched = false;
+        self.iter_overlapped_regions(range, |meta| {
+            matched = meta.state == RegionState::Active && &meta.range == range;
+            false
+        });
+        matched
+    }
+
     pub fn iter_overlapped_regions_mut(
         &mut self,
         range: &CacheRange,
@@ -458,11 +642,19 @@ impl RegionManager {
 
     // Acquire a snapshot of the `range` with `read_ts`. If the range is not
     // accessable, None will be returned. Otherwise, the range id will be returned.
+    //
+    // `resolved_ts` is the region's resolved ts as tracked by resolved-ts
+    // (`0` if unknown/untracked, which fails every stale read closed since
+    // `read_ts` is never `0`), and is only enforced against `read_ts` when
+    // this store's own peer is a learner: a stale read dispatched to a
+    // learner must never observe a write the dispatcher hasn't resolved yet,
+    // even though the cache itself may already hold that write's data.
     pub(crate) fn region_snapshot(
         &mut self,
         region_id: u64,
         region_epoch: u64,
         read_ts: u64,
+        resolved_ts: u64,
     ) -> result::Result<(), FailedReason> {
         let Some(meta) = self.regions.get_mut(&region_id) else {
             return Err(FailedReason::NotCached);
@@ -480,6 +672,13 @@ impl RegionManager {
             return Err(FailedReason::TooOldRead);
         }
 
+        if self.cache_on_learner
+            && read_ts > resolved_ts
+            && self.local_peer_is_learner(&meta.region)
+        {
+            return Err(FailedReason::TooNewRead);
+        }
+
         meta.region_snapshot_list.new_snapshot(read_ts);
         Ok(())
     }
@@ -738,12 +937,22 @@ impl RegionManager {
     }
 
     // return whether the operation is successful.
+    //
+    // This is a single global "is a gc pass running" guard rather than a
+    // per-task dedup, since gc here is driven by a tick loop rather than
+    // discrete scheduled tasks (see `tikv_util::worker::DedupScheduler` for
+    // the latter, which new per-region background work should prefer over
+    // hand-rolling another ad hoc flag like this one).
     pub fn try_set_regions_in_gc(&self, in_gc: bool) -> bool {
         self.is_gc_task_running
             .compare_exchange(!in_gc, in_gc, Ordering::AcqRel, Ordering::Relaxed)
             .is_ok()
     }
 
+    pub fn is_gc_running(&self) -> bool {
+        self.is_gc_task_running.load(Ordering::Relaxed)
+    }
+
     pub(crate) fn is_overlapped_with_regions_being_written(&self, range: &CacheRange) -> bool {
         self.regions_being_written.iter().any(|(_, ranges)| {
             ranges
@@ -776,6 +985,12 @@ impl RegionManager {
 
     pub fn load_region(&mut self, region: Region) -> Result<(), LoadFailedReason> {
         use RegionState::*;
+        if self.admission_paused {
+            return Err(LoadFailedReason::AdmissionPaused);
+        }
+        if !self.local_peer_is_cacheable(&region) {
+            return Err(LoadFailedReason::IneligiblePeer);
+        }
         if let Some(state) = self.check_overlap_with_region(&region) {
             let reason = match state {
                 Pending | ReadyToLoad | Loading => LoadFailedReason::PendingRange,
@@ -790,6 +1005,15 @@ impl RegionManager {
     }
 
     // return `true` is the region is evicted.
+    //
+    // Note this only repartitions the metadata (range boundaries, safe_point,
+    // state, in_gc, evict_info via `RangeMeta::derive_from`) across the child
+    // regions; the cached data itself lives in the shared, key-ordered
+    // `SkiplistEngine` and is not copied or reloaded, so each child keeps
+    // serving cached reads for its share of the split range immediately. This
+    // is driven from `hybrid_engine::observer::Observer::post_exec_cmd`, which
+    // recognizes a `BatchSplit`/`Split` admin command via `state.new_regions`
+    // and raises `RegionEvent::Split` instead of evicting the source range.
     pub(crate) fn split_region(
         &mut self,
         source_region: &Region,
@@ -857,6 +1081,23 @@ pub enum LoadFailedReason {
     Overlapped,
     PendingRange,
     Evicting,
+    // The local peer for this region is a witness, or a learner that never
+    // serves reads, so there is nothing for the cache to do there.
+    IneligiblePeer,
+    // The store is in import mode; admission is suspended until it exits.
+    AdmissionPaused,
+    // This region's API v2 keyspace is already at or over its configured
+    // share of the engine's region budget (`RangeCacheEngineConfig::keyspace_quotas`).
+    KeyspaceQuotaExceeded,
+}
+
+// Derives the API v2 keyspace a region's data belongs to from its start key,
+// for `RangeCacheEngineConfig::keyspace_quotas` enforcement. `None` for
+// non-API-v2 clusters (no keyspace prefix) and for the very first region,
+// whose start key is empty.
+pub(crate) fn region_keyspace_id(region: &Region) -> Option<u32> {
+    let (keyspace, _) = ApiV2::parse_keyspace(region.get_start_key()).ok()?;
+    keyspace.map(|id| id.into_inner())
 }
 
 #[derive(PartialEq, Debug)]
@@ -881,15 +1122,15 @@ mod tests {
         range_mgr.new_region(r1.clone());
         range_mgr.set_safe_point(r1.id, 5);
         assert_eq!(
-            range_mgr.region_snapshot(r1.id, 0, 5).unwrap_err(),
+            range_mgr.region_snapshot(r1.id, 0, 5, u64::MAX).unwrap_err(),
             FailedReason::TooOldRead
         );
-        range_mgr.region_snapshot(r1.id, 0, 8).unwrap();
+        range_mgr.region_snapshot(r1.id, 0, 8, u64::MAX).unwrap();
         let snapshot1 = RangeCacheSnapshotMeta::new(1, 0, CacheRange::from_region(&r1), 8, 1);
-        range_mgr.region_snapshot(r1.id, 0, 10).unwrap();
+        range_mgr.region_snapshot(r1.id, 0, 10, u64::MAX).unwrap();
         let snapshot2 = RangeCacheSnapshotMeta::new(1, 0, CacheRange::from_region(&r1), 10, 2);
         assert_eq!(
-            range_mgr.region_snapshot(2, 0, 8).unwrap_err(),
+            range_mgr.region_snapshot(2, 0, 8, u64::MAX).unwrap_err(),
             FailedReason::NotCached
         );
 
@@ -919,7 +1160,7 @@ mod tests {
         assert!(meta1.safe_point == meta2.safe_point && meta1.safe_point == meta3.safe_point);
 
         // evict a range with accurate match
-        range_mgr.region_snapshot(r_left.id, 2, 10).unwrap();
+        range_mgr.region_snapshot(r_left.id, 2, 10, u64::MAX).unwrap();
         let snapshot3 =
             RangeCacheSnapshotMeta::new(r_left.id, 2, CacheRange::from_region(&r1), 10, 3);
         range_mgr.evict_region(&r_left, EvictReason::AutoEvict);
@@ -976,6 +1217,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_range_load_skips_ineligible_peer() {
+        let mut range_mgr = RegionManager::default();
+        range_mgr.set_store_id(1);
+
+        let mut witness_region = new_region(1, b"k00", b"k10");
+        witness_region.mut_peers()[0].set_store_id(1);
+        witness_region.mut_peers()[0].set_is_witness(true);
+        assert_eq!(
+            range_mgr.load_region(witness_region).unwrap_err(),
+            LoadFailedReason::IneligiblePeer
+        );
+
+        let mut learner_region = new_region(2, b"k10", b"k20");
+        learner_region.mut_peers()[0].set_store_id(1);
+        learner_region.mut_peers()[0].set_role(PeerRole::Learner);
+        assert_eq!(
+            range_mgr.load_region(learner_region).unwrap_err(),
+            LoadFailedReason::IneligiblePeer
+        );
+
+        // A normal voter peer on the local store is still admitted.
+        let mut voter_region = new_region(3, b"k20", b"k30");
+        voter_region.mut_peers()[0].set_store_id(1);
+        range_mgr.load_region(voter_region).unwrap();
+
+        // An unknown store_id (e.g. in tests that don't call `set_store_id`)
+        // disables the check rather than rejecting everything.
+        let mut default_mgr = RegionManager::default();
+        let mut other_witness_region = new_region(4, b"k30", b"k40");
+        other_witness_region.mut_peers()[0].set_is_witness(true);
+        default_mgr.load_region(other_witness_region).unwrap();
+    }
+
+    #[test]
+    fn test_cache_on_learner_admits_learner_but_not_witness() {
+        let mut range_mgr = RegionManager::default();
+        range_mgr.set_store_id(1);
+        range_mgr.set_cache_on_learner(true);
+
+        let mut learner_region = new_region(1, b"k00", b"k10");
+        learner_region.mut_peers()[0].set_store_id(1);
+        learner_region.mut_peers()[0].set_role(PeerRole::Learner);
+        range_mgr.load_region(learner_region).unwrap();
+
+        let mut witness_region = new_region(2, b"k10", b"k20");
+        witness_region.mut_peers()[0].set_store_id(1);
+        witness_region.mut_peers()[0].set_is_witness(true);
+        assert_eq!(
+            range_mgr.load_region(witness_region).unwrap_err(),
+            LoadFailedReason::IneligiblePeer
+        );
+    }
+
+    #[test]
+    fn test_region_snapshot_rejects_stale_read_past_resolved_ts_on_learner() {
+        let mut range_mgr = RegionManager::default();
+        range_mgr.set_store_id(1);
+        range_mgr.set_cache_on_learner(true);
+
+        let mut r1 = new_region(1, b"k00", b"k10");
+        r1.mut_peers()[0].set_store_id(1);
+        r1.mut_peers()[0].set_role(PeerRole::Learner);
+        range_mgr.new_region(r1.clone());
+
+        // A read_ts beyond the region's resolved ts is rejected on a learner...
+        assert_eq!(
+            range_mgr.region_snapshot(r1.id, 0, 10, 8).unwrap_err(),
+            FailedReason::TooNewRead
+        );
+        // ...but is fine once it's no later than the resolved ts.
+        range_mgr.region_snapshot(r1.id, 0, 8, 8).unwrap();
+
+        // The same region, if it were a voter peer, isn't bound by resolved_ts.
+        let mut voter_mgr = RegionManager::default();
+        voter_mgr.set_store_id(1);
+        voter_mgr.set_cache_on_learner(true);
+        let mut r2 = new_region(2, b"k10", b"k20");
+        r2.mut_peers()[0].set_store_id(1);
+        voter_mgr.new_region(r2.clone());
+        voter_mgr.region_snapshot(r2.id, 0, 10, 8).unwrap();
+    }
+
+    #[test]
+    fn test_region_snapshot_rejects_learner_read_when_resolved_ts_unknown() {
+        // `resolved_ts` unknown (e.g. a learner region just admitted, before
+        // resolved-ts has advanced for it) must fail every stale read closed
+        // rather than let it through as if nothing bounded it.
+        let mut range_mgr = RegionManager::default();
+        range_mgr.set_store_id(1);
+        range_mgr.set_cache_on_learner(true);
+
+        let mut r1 = new_region(1, b"k00", b"k10");
+        r1.mut_peers()[0].set_store_id(1);
+        r1.mut_peers()[0].set_role(PeerRole::Learner);
+        range_mgr.new_region(r1.clone());
+
+        assert_eq!(
+            range_mgr.region_snapshot(r1.id, 0, 10, 0).unwrap_err(),
+            FailedReason::TooNewRead
+        );
+    }
+
     #[test]
     fn test_range_load_overlapped() {
         let mut range_mgr = RegionManager::default();
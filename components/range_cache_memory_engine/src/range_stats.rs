@@ -13,8 +13,44 @@ use std::{
 use crossbeam::sync::ShardedLock;
 use kvproto::metapb::Region;
 use parking_lot::Mutex;
-use raftstore::coprocessor::RegionInfoProvider;
-use tikv_util::info;
+use raftstore::coprocessor::{RegionActivity, RegionInfoProvider};
+use serde::{Deserialize, Serialize};
+use tikv_util::{config::VersionTrack, debug, info};
+
+use crate::{region_cache_stats::RegionCacheStatsTracker, RangeCacheEngineConfig};
+
+/// Which eviction candidate `RangeStatsManager` picks first when it has to
+/// choose, consulted by both `BackgroundRunnerCore::evict_on_soft_limit_reached`
+/// and `BackgroundRunnerCore::top_regions_load_evict`. Selected via
+/// `RangeCacheEngineConfig::eviction_policy`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EvictionPolicy {
+    /// Defer entirely to `RegionInfoProvider::get_top_regions`'s own
+    /// ranking (least-read-keys-first), which is also what picks which
+    /// regions this manager tracks as "top" in the first place. This is the
+    /// default, and matches this manager's behavior before `EvictionPolicy`
+    /// existed.
+    #[default]
+    Activity,
+    /// Evict whichever candidate was loaded into the cache longest ago,
+    /// regardless of `RegionInfoProvider`'s activity ranking. Approximates a
+    /// region-granularity LRU using `region_loaded_at`, the same timestamp
+    /// `evict_min_duration` already tracks. A candidate this manager never
+    /// recorded loading (e.g. one admitted via a region-label hint instead
+    /// of this manager) is treated as the oldest, so it's evicted first.
+    Lru,
+    /// Weighs how little value a candidate's cache residency is delivering:
+    /// its `region_cache_stats` hit ratio (hits / (hits + misses)) divided by
+    /// how long ago it was loaded, so a region that's been in cache a long
+    /// time without earning a good hit ratio is a better target than one
+    /// that's merely had a rough last few reads but was just paid for. A
+    /// candidate with no recorded hits or misses yet is treated as earning
+    /// the full ratio (1.0), so a freshly-loaded region isn't penalized for
+    /// not having proven itself either way. Like `Lru`, a candidate with no
+    /// recorded load time is treated as the cheapest to evict.
+    CostAware,
+}
 
 #[derive(Clone)]
 pub(crate) struct RangeStatsManager {
@@ -23,26 +59,58 @@ pub(crate) struct RangeStatsManager {
     prev_top_regions: Arc<Mutex<BTreeMap<u64, Region>>>,
     checking_top_regions: Arc<AtomicBool>,
     region_loaded_at: Arc<ShardedLock<BTreeMap<u64, Instant>>>,
-    evict_min_duration: Duration,
-    expected_region_size: usize,
+    // `evict_min_duration` and `expected_region_size` are read live from here
+    // on every use, the same way `MemoryController` reads its thresholds, so
+    // that config changes take effect without recreating the manager.
+    config: Arc<VersionTrack<RangeCacheEngineConfig>>,
+    // Backs `EvictionPolicy::CostAware`'s real per-region hit ratio. Shared
+    // with (not owned by) `RangeCacheMemoryEngine::region_cache_stats`.
+    region_cache_stats: RegionCacheStatsTracker,
 }
 
 /// Do not evict a region if has been cached for less than this duration.
 pub const DEFAULT_EVICT_MIN_DURATION: Duration = Duration::from_secs(60 * 3);
 
+/// Only log a region's bucket read skew on eviction once it's at least this
+/// skewed, so routine, evenly-read regions don't spam the log.
+const MIN_LOGGED_BUCKET_READ_SKEW: f64 = 4.0;
+
+/// Ratio of a region's hottest bucket's read bytes to its average per-bucket
+/// read bytes, or `None` if the region hasn't reported buckets yet or only
+/// has a single one (nothing to be skewed against).
+///
+/// The cache only ever admits or evicts a region as a whole, so this is
+/// purely informational today: it lets us see that a region marked "cold" at
+/// region granularity may actually have a hot sub-range, which is the data
+/// bucket-level caching would need to act on it.
+fn bucket_read_skew(activity: &RegionActivity) -> Option<f64> {
+    let read_bytes = &activity.bucket_stat.as_ref()?.stats.read_bytes;
+    if read_bytes.len() < 2 {
+        return None;
+    }
+    let total: u64 = read_bytes.iter().sum();
+    if total == 0 {
+        return None;
+    }
+    let max = *read_bytes.iter().max().unwrap();
+    Some(max as f64 / (total as f64 / read_bytes.len() as f64))
+}
+
 impl RangeStatsManager {
     /// Creates a new RangeStatsManager that retrieves state from
     /// `info_provider`.
     ///
     /// * `num_regions` Initial number of top regions to track and cache. This
     ///   may change, see `adjust_max_num_regions` below.
-    /// * `evict_min_duration` - do not evict regions that have been loaded for
-    ///   less than this duration.
+    /// * `config` - supplies `evict_min_duration` (do not evict regions that
+    ///   have been loaded for less than this duration) and
+    ///   `expected_region_size`, both read live so config changes apply
+    ///   without restarting.
     pub fn new(
         num_regions: usize,
-        evict_min_duration: Duration,
-        expected_region_size: usize,
+        config: Arc<VersionTrack<RangeCacheEngineConfig>>,
         info_provider: Arc<dyn RegionInfoProvider>,
+        region_cache_stats: RegionCacheStatsTracker,
     ) -> Self {
         RangeStatsManager {
             num_regions: Arc::new(AtomicUsize::new(num_regions)),
@@ -50,11 +118,63 @@ impl RangeStatsManager {
             prev_top_regions: Arc::new(Mutex::new(BTreeMap::new())),
             checking_top_regions: Arc::new(AtomicBool::new(false)),
             region_loaded_at: Arc::new(ShardedLock::new(BTreeMap::new())),
-            evict_min_duration,
-            expected_region_size,
+            config,
+            region_cache_stats,
+        }
+    }
+
+    fn evict_min_duration(&self) -> Duration {
+        self.config.value().evict_min_duration()
+    }
+
+    fn expected_region_size(&self) -> usize {
+        self.config.value().expected_region_size()
+    }
+
+    fn eviction_policy(&self) -> EvictionPolicy {
+        self.config.value().eviction_policy
+    }
+
+    /// Reorders `regions` in place so the ones `self.eviction_policy()`
+    /// considers best to evict come first. A no-op for
+    /// `EvictionPolicy::Activity`, since the order callers already have
+    /// (from `RegionInfoProvider::get_top_regions`, or simply "no longer in
+    /// the top-N window") already reflects that policy.
+    fn sort_for_eviction(&self, regions: &mut [Region]) {
+        match self.eviction_policy() {
+            EvictionPolicy::Activity => {}
+            EvictionPolicy::Lru => {
+                let loaded_at = self.region_loaded_at.read().unwrap();
+                regions.sort_by_key(|r| loaded_at.get(&r.get_id()).copied());
+            }
+            EvictionPolicy::CostAware => {
+                let loaded_at = self.region_loaded_at.read().unwrap();
+                let now = Instant::now();
+                regions.sort_by(|a, b| {
+                    self.cost_score(a, &loaded_at, now)
+                        .total_cmp(&self.cost_score(b, &loaded_at, now))
+                });
+            }
         }
     }
 
+    /// A region's real cache hit ratio (see `region_cache_stats`) divided by
+    /// how long it's been loaded, or `0.0` if this manager never recorded
+    /// loading it -- the same "evict this first" treatment `Lru` gives an
+    /// untracked region. A region with no recorded hits or misses yet scores
+    /// as if it had earned a perfect ratio, so it isn't penalized before it's
+    /// had a chance to prove itself either way.
+    fn cost_score(&self, region: &Region, loaded_at: &BTreeMap<u64, Instant>, now: Instant) -> f64 {
+        let Some(&loaded_at) = loaded_at.get(&region.get_id()) else {
+            return 0.0;
+        };
+        let hit_ratio = self
+            .region_cache_stats
+            .hit_ratio(region.get_id())
+            .unwrap_or(1.0);
+        hit_ratio / now.saturating_duration_since(loaded_at).as_secs_f64().max(1.0)
+    }
+
     /// Prevents two instances of this from running concurrently.
     /// Return the previous checking status.
     pub fn set_checking_top_regions(&self, v: bool) -> bool {
@@ -72,15 +192,18 @@ impl RangeStatsManager {
         self.num_regions.load(Ordering::Relaxed)
     }
 
-    /// Collect candidates for eviction sorted by activity in creasing order:
+    /// Collect candidates for eviction, ordered with the ones
+    /// `self.eviction_policy()` would evict first at the front:
     ///
     /// 1. Get all the regions sorted (decreasing) by region activity using
     ///    [raftstore::coprocessor::RegionCollector::handle_get_top_regions].
     /// 2. Remove all regions where `is_cached_pred` returns false when passed
     ///    the region's range or those that have been loaded for less than
     ///    `self.evict_min_duration`.
-    /// 3. Reverse the list so that it is now sorted in the order of increasing
-    ///    activity.
+    /// 3. For `EvictionPolicy::Activity` (the default), reverse the list so
+    ///    it's sorted in order of increasing activity -- this is the only
+    ///    ranking this manager has ever done. Other policies reorder it via
+    ///    `sort_for_eviction` instead.
     /// 4. Store the results in `ranges_out` using [Vec::extend].
     pub fn collect_candidates_for_eviction<F>(
         &self,
@@ -89,34 +212,67 @@ impl RangeStatsManager {
     ) where
         F: Fn(&Region) -> bool,
     {
-        // Gets all of the regions, sorted by activity.
-        let all_regions = self.info_provider.get_top_regions(None).unwrap();
         let regions_loaded = self.region_loaded_at.read().unwrap();
-        regions_out.extend(
-            all_regions
-                .iter()
-                .filter_map(|(region, approx_size)| {
-                    is_cached_pred(region)
-                        .then(|| {
-                            match regions_loaded.get(&region.get_id()) {
-                                // Do not evict ranges that were loaded less than
-                                // `EVICT_MIN_DURATION` ago.
-                                Some(&time_loaded)
-                                    if Instant::now() - time_loaded < self.evict_min_duration =>
-                                {
-                                    None
-                                }
-                                Some(_) | None =>
-                                // None indicates range loaded from a hint, not by this manager.
+        let top_regions = self.info_provider.get_top_regions(None).unwrap();
+        let filtered: Vec<(Region, u64)> = top_regions
+            .iter()
+            .filter_map(|(region, approx_size)| {
+                is_cached_pred(region)
+                    .then(|| {
+                        match regions_loaded.get(&region.get_id()) {
+                            // Do not evict ranges that were loaded less than
+                            // `EVICT_MIN_DURATION` ago.
+                            Some(&time_loaded)
+                                if Instant::now() - time_loaded < self.evict_min_duration() =>
+                            {
+                                None
+                            }
+                            Some(_) | None =>
+                            // None indicates range loaded from a hint, not by this manager.
+                            {
+                                if let Ok(Some(activity)) =
+                                    self.info_provider.region_activity(region.get_id())
+                                    && let Some(skew) = bucket_read_skew(&activity)
+                                    && skew >= MIN_LOGGED_BUCKET_READ_SKEW
                                 {
-                                    Some((region.clone(), *approx_size))
+                                    debug!(
+                                        "evicting region with skewed bucket reads";
+                                        "region_id" => region.get_id(),
+                                        "hottest_bucket_read_skew" => skew,
+                                    );
                                 }
+                                Some((region.clone(), *approx_size))
                             }
-                        })
-                        .flatten()
-                })
-                .rev(),
-        );
+                        }
+                    })
+                    .flatten()
+            })
+            .collect();
+        drop(regions_loaded);
+
+        match self.eviction_policy() {
+            EvictionPolicy::Activity => regions_out.extend(filtered.into_iter().rev()),
+            EvictionPolicy::Lru | EvictionPolicy::CostAware => {
+                let mut regions: Vec<Region> = filtered.iter().map(|(r, _)| r.clone()).collect();
+                self.sort_for_eviction(&mut regions);
+                let sizes: BTreeMap<u64, u64> =
+                    filtered.into_iter().map(|(r, s)| (r.get_id(), s)).collect();
+                regions_out.extend(regions.into_iter().map(|r| {
+                    let size = sizes[&r.get_id()];
+                    (r, size)
+                }));
+            }
+        }
+    }
+
+    /// Reorders `regions` -- the ones `collect_changed_ranges` has already
+    /// decided fell out of the top-N activity window -- so the ones
+    /// `self.eviction_policy()` would most want gone come first. Unlike
+    /// `collect_candidates_for_eviction`, this doesn't filter anything: it's
+    /// purely about which of an already-decided set of evictable regions a
+    /// caller should spend its eviction budget on first.
+    pub fn rank_for_eviction(&self, regions: &mut [Region]) {
+        self.sort_for_eviction(regions);
     }
 
     /// This method should be called when `evicted_range` is succesfully evicted
@@ -147,10 +303,11 @@ impl RangeStatsManager {
         match curr_memory_usage.cmp(&threshold) {
             cmp::Ordering::Less => {
                 let room_to_grow = threshold - curr_memory_usage;
-                if room_to_grow > self.expected_region_size * 3 {
+                let expected_region_size = self.expected_region_size();
+                if room_to_grow > expected_region_size * 3 {
                     let curr_num_regions = self.max_num_regions();
                     let next_num_regions =
-                        curr_num_regions + room_to_grow / (self.expected_region_size * 3);
+                        curr_num_regions + room_to_grow / (expected_region_size * 3);
                     info!("increasing number of top regions to cache";
                         "from" => curr_num_regions,
                         "to" => next_num_regions,
@@ -162,7 +319,7 @@ impl RangeStatsManager {
                 let to_shrink_by = curr_memory_usage - threshold;
                 let curr_num_regions = self.max_num_regions();
                 let next_num_regions = curr_num_regions
-                    .checked_sub(1.max(to_shrink_by / self.expected_region_size))
+                    .checked_sub(1.max(to_shrink_by / self.expected_region_size()))
                     .unwrap_or(1)
                     .max(1);
                 info!("decreasing number of top regions to cache";
@@ -233,7 +390,7 @@ impl RangeStatsManager {
                 match regions_loaded.get(&id) {
                     // Do not evict ranges that were loaded less than `EVICT_MIN_DURATION` ago.
                     Some(&time_loaded)
-                        if Instant::now() - time_loaded < self.evict_min_duration =>
+                        if Instant::now() - time_loaded < self.evict_min_duration() =>
                     {
                         let mut mut_prev_top_regions = self.prev_top_regions.lock();
                         let _ = mut_prev_top_regions.insert(id, region.clone());
@@ -253,11 +410,21 @@ impl RangeStatsManager {
 #[cfg(test)]
 pub mod tests {
     use raftstore::coprocessor::{self, region_info_accessor::TopRegions, RegionInfoProvider};
-    use tikv_util::box_err;
+    use tikv_util::{
+        box_err,
+        config::{ReadableDuration, VersionTrack},
+    };
 
     use super::*;
     use crate::{test_util::new_region, RangeCacheEngineConfig};
 
+    fn test_config(evict_min_duration: Duration) -> Arc<VersionTrack<RangeCacheEngineConfig>> {
+        Arc::new(VersionTrack::new(RangeCacheEngineConfig {
+            evict_min_duration: Some(ReadableDuration(evict_min_duration)),
+            ..RangeCacheEngineConfig::config_for_test()
+        }))
+    }
+
     struct RegionInfoSimulator {
         regions: Mutex<TopRegions>,
     }
@@ -306,9 +473,9 @@ pub mod tests {
         // 10 ms min duration eviction for testing purposes.
         let rsm = RangeStatsManager::new(
             5,
-            Duration::from_millis(10),
-            RangeCacheEngineConfig::config_for_test().expected_region_size(),
+            test_config(Duration::from_millis(10)),
             sim.clone(),
+            RegionCacheStatsTracker::default(),
         );
         let mut added = Vec::new();
         let mut removed = Vec::new();
@@ -370,9 +537,9 @@ pub mod tests {
         // 10 ms min duration eviction for testing purposes.
         let rsm = RangeStatsManager::new(
             5,
-            Duration::from_millis(10),
-            RangeCacheEngineConfig::config_for_test().expected_region_size(),
+            test_config(Duration::from_millis(10)),
             sim.clone(),
+            RegionCacheStatsTracker::default(),
         );
         let r_i_p: Arc<dyn RegionInfoProvider> = sim.clone();
         let check_is_cached = move |r: &Region| -> bool {
@@ -399,4 +566,95 @@ pub mod tests {
             .collect::<Vec<_>>();
         assert_eq!(expected_candidates_for_eviction, candidates_for_eviction);
     }
+
+    #[test]
+    fn test_collect_candidates_for_eviction_lru_policy() {
+        let region_1 = new_region(1, b"k1", b"k2");
+        let region_2 = new_region(2, b"k3", b"k4");
+        let region_3 = new_region(3, b"k5", b"k6");
+        // `RegionInfoSimulator` reports these in activity order (region_1 most
+        // active), the opposite of the load order set up below.
+        let all_regions = vec![
+            (region_1.clone(), 42),
+            (region_2.clone(), 42),
+            (region_3.clone(), 42),
+        ];
+        let sim = Arc::new(RegionInfoSimulator {
+            regions: Mutex::new(all_regions),
+        });
+        let config = Arc::new(VersionTrack::new(RangeCacheEngineConfig {
+            evict_min_duration: Some(ReadableDuration(Duration::from_millis(10))),
+            eviction_policy: EvictionPolicy::Lru,
+            ..RangeCacheEngineConfig::config_for_test()
+        }));
+        let rsm = RangeStatsManager::new(5, config, sim, RegionCacheStatsTracker::default());
+
+        // Load region_3 first, then region_2, then region_1, so region_3 is the
+        // oldest and should be the first LRU eviction candidate despite being
+        // ranked least active above.
+        for region in [&region_3, &region_2, &region_1] {
+            rsm.region_loaded_at
+                .write()
+                .unwrap()
+                .insert(region.get_id(), Instant::now());
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        std::thread::sleep(Duration::from_millis(20));
+        let mut candidates_for_eviction = Vec::new();
+        rsm.collect_candidates_for_eviction(&mut candidates_for_eviction, &|_| true);
+        let ids: Vec<u64> = candidates_for_eviction
+            .iter()
+            .map(|(r, _)| r.get_id())
+            .collect();
+        assert_eq!(ids, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_collect_candidates_for_eviction_cost_aware_policy() {
+        let region_1 = new_region(1, b"k1", b"k2");
+        let region_2 = new_region(2, b"k3", b"k4");
+        let region_3 = new_region(3, b"k5", b"k6");
+        let all_regions = vec![
+            (region_1.clone(), 42),
+            (region_2.clone(), 42),
+            (region_3.clone(), 42),
+        ];
+        let sim = Arc::new(RegionInfoSimulator {
+            regions: Mutex::new(all_regions),
+        });
+        let config = Arc::new(VersionTrack::new(RangeCacheEngineConfig {
+            evict_min_duration: Some(ReadableDuration(Duration::from_millis(10))),
+            eviction_policy: EvictionPolicy::CostAware,
+            ..RangeCacheEngineConfig::config_for_test()
+        }));
+        let region_cache_stats = RegionCacheStatsTracker::default();
+        let rsm = RangeStatsManager::new(5, config, sim, region_cache_stats.clone());
+
+        // All three loaded together, so `cost_score` is ordered by hit ratio
+        // alone: region_3 has never recorded a hit or miss (scores as if it
+        // earned a perfect ratio), region_1 has a poor ratio, and region_2 has
+        // a good one.
+        for region in [&region_1, &region_2, &region_3] {
+            rsm.region_loaded_at
+                .write()
+                .unwrap()
+                .insert(region.get_id(), Instant::now());
+        }
+        region_cache_stats.record_hit(1);
+        region_cache_stats.record_miss(1);
+        region_cache_stats.record_miss(1);
+        region_cache_stats.record_hit(2);
+        region_cache_stats.record_hit(2);
+        region_cache_stats.record_miss(2);
+
+        std::thread::sleep(Duration::from_millis(20));
+        let mut candidates_for_eviction = Vec::new();
+        rsm.collect_candidates_for_eviction(&mut candidates_for_eviction, &|_| true);
+        let ids: Vec<u64> = candidates_for_eviction
+            .iter()
+            .map(|(r, _)| r.get_id())
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
 }
@@ -1,7 +1,7 @@
 // Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
 
 use core::slice::SlicePattern;
-use std::{fmt::Debug, ops::Deref, result, sync::Arc};
+use std::{cell::RefCell, fmt::Debug, ops::Deref, result, sync::Arc};
 
 use bytes::Bytes;
 use crossbeam::epoch::{self};
@@ -18,12 +18,13 @@ use tikv_util::{box_err, time::Instant};
 
 use crate::{
     background::BackgroundTask,
+    encryption::{decrypt_value, ValueEncryptionKey},
     engine::{cf_to_id, SkiplistEngine},
     keys::{
         decode_key, encode_seek_for_prev_key, encode_seek_key, InternalBytes, InternalKey,
         ValueType,
     },
-    metrics::IN_MEMORY_ENGINE_SEEK_DURATION,
+    metrics::{IN_MEMORY_ENGINE_SEEK_DURATION, IN_MEMORY_ENGINE_SEEK_SKIPPED_VERSIONS},
     perf_context::PERF_CONTEXT,
     perf_counter_add,
     statistics::{LocalStatistics, Statistics, Tickers},
@@ -73,6 +74,9 @@ impl RangeCacheSnapshotMeta {
 #[derive(Debug)]
 pub struct RangeCacheSnapshot {
     snapshot_meta: RangeCacheSnapshotMeta,
+    // The region's `RangeMeta::load_generation` at the time this snapshot was
+    // taken. See `engine_traits::Snapshot::range_cache_load_generation`.
+    load_generation: u64,
     skiplist_engine: SkiplistEngine,
     engine: RangeCacheMemoryEngine,
 }
@@ -86,9 +90,14 @@ impl RangeCacheSnapshot {
         read_ts: u64,
         seq_num: u64,
     ) -> result::Result<Self, FailedReason> {
+        let resolved_ts = engine.resolved_ts(region_id);
         let mut core = engine.core.write();
         core.range_manager
-            .region_snapshot(region_id, region_epoch, read_ts)?;
+            .region_snapshot(region_id, region_epoch, read_ts, resolved_ts)?;
+        let load_generation = core
+            .range_manager
+            .region_meta(region_id)
+            .map_or(0, |meta| meta.load_generation());
         Ok(RangeCacheSnapshot {
             snapshot_meta: RangeCacheSnapshotMeta::new(
                 region_id,
@@ -97,6 +106,7 @@ impl RangeCacheSnapshot {
                 read_ts,
                 seq_num,
             ),
+            load_generation,
             skiplist_engine: core.engine.clone(),
             engine: engine.clone(),
         })
@@ -126,7 +136,11 @@ impl Drop for RangeCacheSnapshot {
     }
 }
 
-impl Snapshot for RangeCacheSnapshot {}
+impl Snapshot for RangeCacheSnapshot {
+    fn range_cache_load_generation(&self) -> Option<u64> {
+        Some(self.load_generation)
+    }
+}
 
 impl Iterable for RangeCacheSnapshot {
     type Iterator = RangeCacheIterator;
@@ -158,6 +172,10 @@ impl Iterable for RangeCacheSnapshot {
             )));
         }
 
+        // Every CF's values are encrypted when encryption is enabled, see
+        // `write_to_memory`.
+        let encryption_key = self.engine.value_encryption_key();
+
         Ok(RangeCacheIterator {
             valid: false,
             prefix: None,
@@ -172,6 +190,8 @@ impl Iterable for RangeCacheSnapshot {
             prefix_extractor,
             local_stats: LocalStatistics::default(),
             seek_duration: IN_MEMORY_ENGINE_SEEK_DURATION.local(),
+            encryption_key,
+            decrypted_value: RefCell::new(Vec::new()),
         })
     }
 }
@@ -190,6 +210,8 @@ impl Peekable for RangeCacheSnapshot {
         key: &[u8],
     ) -> Result<Option<Self::DbVector>> {
         fail::fail_point!("on_range_cache_get_value");
+        self.engine
+            .record_hot_key(self.snapshot_meta.region_id, key);
         if !self.snapshot_meta.range.contains_key(key) {
             return Err(Error::Other(box_err!(
                 "key {} not in range[{}, {}]",
@@ -217,7 +239,14 @@ impl Peekable for RangeCacheSnapshot {
                 self.engine
                     .statistics()
                     .record_ticker(Tickers::BytesRead, value.len() as u64);
+                self.engine
+                    .record_bytes_served(self.snapshot_meta.region_id, value.len() as u64);
                 perf_counter_add!(get_read_bytes, value.len() as u64);
+                file_system::record_cache_read_bytes(value.len() as u64);
+                let value = match self.engine.value_encryption_key() {
+                    Some(encryption_key) => Bytes::from(decrypt_value(&encryption_key, &value)?),
+                    None => value,
+                };
                 Ok(Some(RangeCacheDbVector(value)))
             }
             _ => Ok(None),
@@ -263,6 +292,13 @@ pub struct RangeCacheIterator {
     statistics: Arc<Statistics>,
     local_stats: LocalStatistics,
     seek_duration: LocalHistogram,
+
+    // Set when the engine has value encryption enabled; `None` otherwise, in
+    // which case `value()` returns the skiplist's bytes unchanged.
+    encryption_key: Option<Arc<ValueEncryptionKey>>,
+    // Scratch buffer `value()` decrypts into when `encryption_key` is set, so
+    // it has somewhere owned to hand out a `&[u8]` from despite taking `&self`.
+    decrypted_value: RefCell<Vec<u8>>,
 }
 
 impl Drop for RangeCacheIterator {
@@ -288,6 +324,7 @@ impl Drop for RangeCacheIterator {
             self.local_stats.number_db_prev_found,
         );
         perf_counter_add!(iter_read_bytes, self.local_stats.bytes_read);
+        file_system::record_cache_read_bytes(self.local_stats.bytes_read);
         self.seek_duration.flush();
     }
 }
@@ -480,6 +517,16 @@ impl RangeCacheIterator {
     }
 }
 
+// Sum of the per-thread internal key/delete skip counters, used to derive how
+// many versions a single seek-like call skipped over by diffing this before
+// and after the call.
+fn total_skipped_versions() -> u64 {
+    PERF_CONTEXT.with(|perf_context| {
+        let perf_context = perf_context.borrow();
+        perf_context.internal_key_skipped_count + perf_context.internal_delete_skipped_count
+    })
+}
+
 impl Iterator for RangeCacheIterator {
     fn key(&self) -> &[u8] {
         assert!(self.valid);
@@ -488,11 +535,25 @@ impl Iterator for RangeCacheIterator {
 
     fn value(&self) -> &[u8] {
         assert!(self.valid);
-        if self.direction == Direction::Backward {
+        let raw = if self.direction == Direction::Backward {
             self.saved_value.as_ref().unwrap().as_slice()
         } else {
             self.iter.value().as_slice()
-        }
+        };
+        let Some(encryption_key) = self.encryption_key.as_ref() else {
+            return raw;
+        };
+        let plaintext = decrypt_value(encryption_key, raw)
+            .unwrap_or_else(|e| panic!("failed to decrypt range cache value: {:?}", e));
+        *self.decrypted_value.borrow_mut() = plaintext;
+        // SAFETY: the returned slice points into `self.decrypted_value`, a
+        // field of `self`, so its data lives exactly as long as `&self` does.
+        // `RangeCacheIterator` is never accessed concurrently (the crate it
+        // implements `engine_traits::Iterator` for requires `&mut self` for
+        // every method that could invalidate this buffer), so no other
+        // borrow of `decrypted_value` can be active at the same time.
+        let buf = self.decrypted_value.borrow();
+        unsafe { std::slice::from_raw_parts(buf.as_ptr(), buf.len()) }
     }
 
     fn next(&mut self) -> Result<bool> {
@@ -543,6 +604,7 @@ impl Iterator for RangeCacheIterator {
 
     fn seek(&mut self, key: &[u8]) -> Result<bool> {
         let begin = Instant::now();
+        let skipped_before = total_skipped_versions();
         self.direction = Direction::Forward;
         if let Some(ref mut extractor) = self.prefix_extractor {
             assert!(key.len() >= 8);
@@ -562,12 +624,15 @@ impl Iterator for RangeCacheIterator {
             self.local_stats.number_db_seek_found += 1;
         }
         self.seek_duration.observe(begin.saturating_elapsed_secs());
+        IN_MEMORY_ENGINE_SEEK_SKIPPED_VERSIONS
+            .observe((total_skipped_versions() - skipped_before) as f64);
 
         Ok(self.valid)
     }
 
     fn seek_for_prev(&mut self, key: &[u8]) -> Result<bool> {
         let begin = Instant::now();
+        let skipped_before = total_skipped_versions();
         self.direction = Direction::Backward;
         if let Some(ref mut extractor) = self.prefix_extractor {
             assert!(key.len() >= 8);
@@ -586,12 +651,15 @@ impl Iterator for RangeCacheIterator {
             self.local_stats.number_db_seek_found += 1;
         }
         self.seek_duration.observe(begin.saturating_elapsed_secs());
+        IN_MEMORY_ENGINE_SEEK_SKIPPED_VERSIONS
+            .observe((total_skipped_versions() - skipped_before) as f64);
 
         Ok(self.valid)
     }
 
     fn seek_to_first(&mut self) -> Result<bool> {
         let begin = Instant::now();
+        let skipped_before = total_skipped_versions();
         assert!(self.prefix_extractor.is_none());
         self.direction = Direction::Forward;
         let seek_key = encode_seek_key(&self.lower_bound, self.sequence_number);
@@ -602,12 +670,15 @@ impl Iterator for RangeCacheIterator {
             self.local_stats.number_db_seek_found += 1;
         }
         self.seek_duration.observe(begin.saturating_elapsed_secs());
+        IN_MEMORY_ENGINE_SEEK_SKIPPED_VERSIONS
+            .observe((total_skipped_versions() - skipped_before) as f64);
 
         Ok(self.valid)
     }
 
     fn seek_to_last(&mut self) -> Result<bool> {
         let begin = Instant::now();
+        let skipped_before = total_skipped_versions();
         assert!(self.prefix_extractor.is_none());
         self.direction = Direction::Backward;
         let seek_key = encode_seek_for_prev_key(&self.upper_bound, u64::MAX);
@@ -622,6 +693,8 @@ impl Iterator for RangeCacheIterator {
             self.local_stats.number_db_seek_found += 1;
         }
         self.seek_duration.observe(begin.saturating_elapsed_secs());
+        IN_MEMORY_ENGINE_SEEK_SKIPPED_VERSIONS
+            .observe((total_skipped_versions() - skipped_before) as f64);
 
         Ok(self.valid)
     }
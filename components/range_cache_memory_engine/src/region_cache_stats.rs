@@ -0,0 +1,168 @@
+// Copyright 2026 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! Per-region cache hit/miss, bytes-served, load time, and eviction-count
+//! bookkeeping, fed from `HybridEngine::snapshot` (via
+//! `RangeCacheMemoryEngine::record_region_cache_hit`/`record_region_cache_miss`),
+//! the read path (`RangeCacheMemoryEngine::record_bytes_served`), and
+//! `RangeCacheMemoryEngine::{load_region, evict_region}`. Queried via
+//! `RangeCacheMemoryEngine::region_cache_stats`.
+//!
+//! Unlike `HotKeyTracker`, this isn't purely a diagnostic aid: `hit_ratio` is
+//! also consulted by `RangeStatsManager` to score eviction candidates under
+//! `EvictionPolicy::CostAware`.
+
+use std::{sync::Arc, time::Instant};
+
+use collections::HashMap;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// Snapshot of one region's cache stats as of the moment it was queried. See
+/// the module doc comment for how each field is populated.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RegionCacheStats {
+    pub region_id: u64,
+    pub hits: u64,
+    pub misses: u64,
+    pub bytes_served: u64,
+    pub eviction_count: u64,
+    // Seconds since this region was last (re)loaded into the cache, or
+    // `None` if this tracker hasn't seen it loaded.
+    pub seconds_since_load: Option<f64>,
+}
+
+#[derive(Default)]
+struct Counters {
+    hits: u64,
+    misses: u64,
+    bytes_served: u64,
+    eviction_count: u64,
+    loaded_at: Option<Instant>,
+}
+
+impl Counters {
+    fn to_stats(&self, region_id: u64) -> RegionCacheStats {
+        RegionCacheStats {
+            region_id,
+            hits: self.hits,
+            misses: self.misses,
+            bytes_served: self.bytes_served,
+            eviction_count: self.eviction_count,
+            seconds_since_load: self.loaded_at.map(|loaded_at| {
+                Instant::now()
+                    .saturating_duration_since(loaded_at)
+                    .as_secs_f64()
+            }),
+        }
+    }
+}
+
+/// Tracks approximate per-region cache hit/miss counts, bytes served, last
+/// load time, and eviction count for diagnostic purposes. Cheap to hold even
+/// when unused. Unlike `HotKeyTracker`, entries survive eviction (an evicted
+/// region may be reloaded later, and `eviction_count` needs to keep counting
+/// across that cycle), so this only grows with the number of distinct
+/// regions ever seen, not the number currently cached.
+#[derive(Clone, Default)]
+pub struct RegionCacheStatsTracker {
+    per_region: Arc<Mutex<HashMap<u64, Counters>>>,
+}
+
+impl RegionCacheStatsTracker {
+    pub fn record_hit(&self, region_id: u64) {
+        self.per_region.lock().entry(region_id).or_default().hits += 1;
+    }
+
+    pub fn record_miss(&self, region_id: u64) {
+        self.per_region.lock().entry(region_id).or_default().misses += 1;
+    }
+
+    pub fn record_bytes_served(&self, region_id: u64, bytes: u64) {
+        self.per_region
+            .lock()
+            .entry(region_id)
+            .or_default()
+            .bytes_served += bytes;
+    }
+
+    pub fn record_load(&self, region_id: u64) {
+        self.per_region
+            .lock()
+            .entry(region_id)
+            .or_default()
+            .loaded_at = Some(Instant::now());
+    }
+
+    pub fn record_eviction(&self, region_id: u64) {
+        self.per_region
+            .lock()
+            .entry(region_id)
+            .or_default()
+            .eviction_count += 1;
+    }
+
+    /// Returns stats for every region this tracker has ever seen.
+    pub fn all_stats(&self) -> Vec<RegionCacheStats> {
+        self.per_region
+            .lock()
+            .iter()
+            .map(|(&region_id, c)| c.to_stats(region_id))
+            .collect()
+    }
+
+    /// `region_id`'s hit ratio (`hits / (hits + misses)`), or `None` if this
+    /// tracker has never recorded a hit or miss for it. Consulted by
+    /// `EvictionPolicy::CostAware`; see the module doc comment.
+    pub fn hit_ratio(&self, region_id: u64) -> Option<f64> {
+        let per_region = self.per_region.lock();
+        let counters = per_region.get(&region_id)?;
+        let total = counters.hits + counters.misses;
+        if total == 0 {
+            return None;
+        }
+        Some(counters.hits as f64 / total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracks_hits_misses_and_bytes() {
+        let tracker = RegionCacheStatsTracker::default();
+        tracker.record_hit(1);
+        tracker.record_hit(1);
+        tracker.record_miss(1);
+        tracker.record_bytes_served(1, 100);
+        tracker.record_bytes_served(1, 50);
+
+        let stats = tracker.all_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].hits, 2);
+        assert_eq!(stats[0].misses, 1);
+        assert_eq!(stats[0].bytes_served, 150);
+        assert_eq!(stats[0].eviction_count, 0);
+        assert!(stats[0].seconds_since_load.is_none());
+    }
+
+    #[test]
+    fn test_eviction_count_survives_reload() {
+        let tracker = RegionCacheStatsTracker::default();
+        tracker.record_load(1);
+        tracker.record_eviction(1);
+        tracker.record_load(1);
+        tracker.record_eviction(1);
+
+        let stats = tracker.all_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].eviction_count, 2);
+        assert!(stats[0].seconds_since_load.unwrap() < 1.0);
+    }
+
+    #[test]
+    fn test_unknown_region_has_no_stats() {
+        let tracker = RegionCacheStatsTracker::default();
+        assert!(tracker.all_stats().is_empty());
+    }
+}
@@ -21,7 +21,10 @@ use tikv_util::{error, info, timer::GLOBAL_TIMER_HANDLE};
 /// https://github.com/tikv/pd/blob/783d060861cef37c38cbdcab9777fe95c17907fe/server/schedule/labeler/rules.go#L31.
 ///
 /// Convention: ranges that should always be cached by the in-memory engine
-/// should be labeled with key "cache" set to value "always".
+/// should be labeled with key "cache" set to value "always". Ranges that
+/// should use the write-around policy (writes evict rather than write
+/// through) should be labeled with key "write-policy" set to value
+/// "write-around".
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RegionLabel {
     pub key: String,
@@ -16,6 +16,7 @@ use tikv_util::{box_err, config::ReadableSize, error, info, time::Instant, warn}
 
 use crate::{
     background::BackgroundTask,
+    encryption::{encrypt_value, ValueEncryptionKey},
     engine::{cf_to_id, id_to_cf, is_lock_cf, SkiplistEngine},
     keys::{encode_key, InternalBytes, ValueType, ENC_KEY_SEQ_LENGTH},
     memory_controller::{MemoryController, MemoryUsage},
@@ -189,7 +190,13 @@ impl RangeCacheWriteBatch {
                     lock_modification += e.data_size() as u64;
                 }
                 seq += 1;
-                e.write_to_memory(seq - 1, &engine, self.memory_controller.clone(), guard)
+                e.write_to_memory(
+                    seq - 1,
+                    &engine,
+                    self.memory_controller.clone(),
+                    self.engine.value_encryption_key().as_deref(),
+                    guard,
+                )
             });
         let duration = start.saturating_elapsed_secs();
         WRITE_DURATION_HISTOGRAM.observe(duration);
@@ -408,11 +415,24 @@ impl RangeCacheWriteBatchEntry {
         seq: u64,
         skiplist_engine: &SkiplistEngine,
         memory_controller: Arc<MemoryController>,
+        encryption_key: Option<&ValueEncryptionKey>,
         guard: &epoch::Guard,
     ) -> Result<()> {
         let handle = skiplist_engine.cf_handle(id_to_cf(self.cf));
 
         let (mut key, mut value) = self.encode(seq);
+        // CF_LOCK and CF_WRITE are mostly short, structured metadata, but both
+        // can embed real user data inline: a `txn_types::Lock` or
+        // `txn_types::Write` whose value is at most `SHORT_VALUE_MAX_LEN`
+        // bytes carries it as `short_value` rather than pointing into
+        // CF_DEFAULT. So every CF's put values get encrypted here, not just
+        // CF_DEFAULT's; the deletion sentinel value is excluded via
+        // `is_put_value` since there's nothing to protect in it.
+        let is_put_value = matches!(self.inner, WriteBatchEntryInternal::PutValue(_));
+        if let (Some(encryption_key), true) = (encryption_key, is_put_value) {
+            let ciphertext = encrypt_value(encryption_key, value.as_slice())?;
+            value = InternalBytes::from_bytes(Bytes::from(ciphertext));
+        }
         key.set_memory_controller(memory_controller.clone());
         value.set_memory_controller(memory_controller);
         handle.insert(key, value, guard);
@@ -502,10 +522,19 @@ impl WriteBatch for RangeCacheWriteBatch {
         self.current_region = Some(region.clone());
         // TODO: remote range.
         let range = CacheRange::from_region(region);
-        self.set_range_cache_status(self.engine.prepare_for_apply(self.id, range, region));
+        self.set_range_cache_status(self.engine.prepare_for_apply(self.id, range.clone(), region));
         self.memory_usage_reach_hard_limit = false;
         self.region_save_point = self.buffer.len();
         self.current_region_evicted = false;
+        if self.range_cache_status != RangeCacheStatus::NotInCache
+            && self.engine.is_write_around(&range)
+        {
+            // The range is configured for the write-around policy: rather than
+            // let an already-cached (or loading) region keep serving
+            // increasingly stale reads until its next eviction pass, evict it
+            // as soon as a write touches it.
+            self.evict_current_region(EvictReason::WriteAround);
+        }
         self.prepare_for_write_duration += time.saturating_elapsed();
     }
 }
@@ -555,7 +584,8 @@ mod tests {
     use crossbeam_skiplist::SkipList;
     use engine_rocks::util::new_engine;
     use engine_traits::{
-        CacheRange, FailedReason, Peekable, RangeCacheEngine, WriteBatch, DATA_CFS,
+        CacheRange, FailedReason, Peekable, RangeCacheEngine, WriteBatch, CF_LOCK, CF_WRITE,
+        DATA_CFS,
     };
     use online_config::{ConfigChange, ConfigManager, ConfigValue};
     use tempfile::Builder;
@@ -605,6 +635,56 @@ mod tests {
         assert_eq!(&b"bbb"[..], val.as_slice());
     }
 
+    #[test]
+    fn test_encryption_covers_write_and_lock_cf() {
+        let tmp = Builder::new()
+            .prefix("test_encryption_covers_write_and_lock_cf")
+            .tempdir()
+            .unwrap();
+        let key_manager =
+            Arc::new(test_util::new_test_key_manager(&tmp, None, None, None).unwrap().unwrap());
+        let mut config = RangeCacheEngineConfig::config_for_test();
+        config.enable_encryption = true;
+        let engine = RangeCacheMemoryEngine::new(
+            RangeCacheEngineContext::new_for_tests(Arc::new(VersionTrack::new(config)))
+                .with_key_manager(key_manager),
+        );
+        let r = new_region(1, b"", b"z");
+        engine.new_region(r.clone());
+        {
+            let mut core = engine.core.write();
+            core.mut_range_manager().set_safe_point(r.id, 10);
+        }
+        let mut wb = RangeCacheWriteBatch::from(&engine);
+        wb.range_cache_status = RangeCacheStatus::Cached;
+        wb.prepare_for_region(&r);
+        wb.put_cf(CF_LOCK, b"aaa", b"lock-value").unwrap();
+        wb.put_cf(CF_WRITE, b"aaa", b"write-value").unwrap();
+        wb.set_sequence_number(1).unwrap();
+        assert_eq!(wb.write().unwrap(), 1);
+
+        let guard = &crossbeam::epoch::pin();
+        let lock_sl = engine.core.read().engine().data[cf_to_id(CF_LOCK)].clone();
+        let lock_val = get_value(&lock_sl, &encode_key(b"aaa", 2, ValueType::Value), guard).unwrap();
+        assert_ne!(lock_val, b"lock-value");
+        let write_sl = engine.core.read().engine().data[cf_to_id(CF_WRITE)].clone();
+        let write_val = get_value(&write_sl, &encode_key(b"aaa", 2, ValueType::Value), guard).unwrap();
+        assert_ne!(write_val, b"write-value");
+
+        // The snapshot read path decrypts transparently regardless of cf.
+        let snapshot = engine
+            .snapshot(r.id, 0, CacheRange::from_region(&r), u64::MAX, 2)
+            .unwrap();
+        assert_eq!(
+            snapshot.get_value_cf(CF_LOCK, b"aaa").unwrap().unwrap(),
+            &b"lock-value"[..]
+        );
+        assert_eq!(
+            snapshot.get_value_cf(CF_WRITE, b"aaa").unwrap().unwrap(),
+            &b"write-value"[..]
+        );
+    }
+
     #[test]
     fn test_savepoints() {
         let engine = RangeCacheMemoryEngine::new(RangeCacheEngineContext::new_for_tests(Arc::new(
@@ -992,4 +1072,32 @@ mod tests {
             .unwrap();
         assert_eq!(snap2.get_value(b"zkk11").unwrap().unwrap(), &val1);
     }
+
+    #[test]
+    fn test_write_around_policy_evicts_on_write() {
+        let engine = RangeCacheMemoryEngine::new(RangeCacheEngineContext::new_for_tests(Arc::new(
+            VersionTrack::new(RangeCacheEngineConfig::config_for_test()),
+        )));
+        let r = new_region(1, b"", b"z");
+        engine.new_region(r.clone());
+        {
+            let mut core = engine.core.write();
+            core.mut_range_manager().set_safe_point(r.id, 10);
+        }
+        // Already cached before the write-around policy is configured for its
+        // range; a subsequent write should evict it rather than write through.
+        engine.record_write_around_range(CacheRange::from_region(&r));
+
+        let mut wb = RangeCacheWriteBatch::from(&engine);
+        wb.prepare_for_region(&r);
+        wb.put(b"zaaa", b"bbb").unwrap();
+        // The put should have been dropped rather than written through.
+        assert_eq!(wb.count(), 0);
+        wb.set_sequence_number(1).unwrap();
+        assert_eq!(wb.write().unwrap(), 1);
+
+        wait_evict_done(&engine);
+        let snap = engine.snapshot(r.id, 0, CacheRange::from_region(&r), u64::MAX, 2);
+        assert_eq!(snap.unwrap_err(), FailedReason::NotCached);
+    }
 }
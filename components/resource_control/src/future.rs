@@ -161,7 +161,7 @@ impl<F: Future> Future for LimitedFuture<F> {
         let start = Instant::now();
         let res = this.f.poll(cx);
         let dur = start.saturating_elapsed();
-        let io_bytes = if let Some(last_io_bytes) = last_io_bytes {
+        let mut io_bytes = if let Some(last_io_bytes) = last_io_bytes {
             match get_thread_io_bytes_stats() {
                 Ok(io_bytes) => io_bytes - last_io_bytes,
                 Err(e) => {
@@ -172,6 +172,12 @@ impl<F: Future> Future for LimitedFuture<F> {
         } else {
             IoBytes::default()
         };
+        // Reads served out of an in-memory cache (e.g. the range cache engine)
+        // never reach the real IO byte counters above, so fold in whatever
+        // was recorded for this thread during the poll. Otherwise a
+        // cache-heavy tenant would look free next to one doing the same
+        // amount of work against disk.
+        io_bytes.read += file_system::take_cache_read_bytes();
         let mut wait_dur = this
             .resource_limiter
             .consume(dur, io_bytes, res.is_pending());
@@ -22,8 +22,9 @@ use engine_rocks::{
     FlowInfo, RocksEngine, RocksStatistics,
 };
 use engine_traits::{
-    data_cf_offset, CachedTablet, CfOptions, CfOptionsExt, FlowControlFactorsExt, KvEngine,
-    RaftEngine, RangeCacheEngine, StatisticsReporter, TabletRegistry, CF_DEFAULT, DATA_CFS,
+    data_cf_offset, CachedTablet, CfOptions, CfOptionsExt, EvictReason, FlowControlFactorsExt,
+    KvEngine, RaftEngine, RangeCacheEngine, StatisticsReporter, TabletRegistry, CF_DEFAULT,
+    DATA_CFS,
 };
 use error_code::ErrorCodeExt;
 use file_system::{get_io_rate_limiter, set_io_rate_limiter, BytesFetcher, File, IoBudgetAdjustor};
@@ -33,8 +34,8 @@ use pd_client::{PdClient, RpcClient};
 use raft_log_engine::RaftLogEngine;
 use raftstore::coprocessor::RegionInfoProvider;
 use range_cache_memory_engine::{
-    flush_range_cache_engine_statistics, RangeCacheEngineContext, RangeCacheMemoryEngine,
-    RangeCacheMemoryEngineStatistics,
+    flush_range_cache_engine_statistics, RangeCacheEngineContext, RangeCacheEngineStatus,
+    RangeCacheMemoryEngine, RangeCacheMemoryEngineStatistics,
 };
 use security::SecurityManager;
 use tikv::{
@@ -702,6 +703,42 @@ pub trait KvEngineBuilder: KvEngine {
         pd_client: Option<Arc<RpcClient>>,
         region_info_provider: Option<Arc<dyn RegionInfoProvider>>,
     ) -> Self;
+
+    // `None` when this engine has no range cache engine to report on, which is
+    // the common case; overridden below for the one `Self` that does.
+    fn range_cache_engine_status(&self) -> Option<RangeCacheEngineStatus> {
+        None
+    }
+
+    // The three methods below back the `/debug/range_cache` debug actions
+    // (evict a region, load a region, trigger a gc pass). Like
+    // `range_cache_engine_status` above, they default to `None` (no range
+    // cache engine to act on) and are overridden below for the one `Self`
+    // that has one.
+    fn range_cache_engine_evict_region(&self, _region: kvproto::metapb::Region) -> Option<()> {
+        None
+    }
+
+    fn range_cache_engine_load_region(
+        &self,
+        _region: kvproto::metapb::Region,
+    ) -> Option<Result<(), String>> {
+        None
+    }
+
+    fn range_cache_engine_trigger_gc(&self, _safe_point: u64) -> Option<Result<(), String>> {
+        None
+    }
+
+    // Backs `/debug/range_cache/region/<id>/hot_keys`. Same `None`-when-absent
+    // convention as the methods above.
+    fn range_cache_engine_hot_keys(
+        &self,
+        _region_id: u64,
+        _top: usize,
+    ) -> Option<Vec<(Vec<u8>, u64)>> {
+        None
+    }
 }
 
 impl KvEngineBuilder for RocksEngine {
@@ -737,6 +774,39 @@ impl KvEngineBuilder for HybridEngine<RocksEngine, RangeCacheMemoryEngine> {
         }
         HybridEngine::new(disk_engine, memory_engine)
     }
+
+    fn range_cache_engine_status(&self) -> Option<RangeCacheEngineStatus> {
+        Some(self.range_cache_engine().status())
+    }
+
+    fn range_cache_engine_evict_region(&self, region: kvproto::metapb::Region) -> Option<()> {
+        self.range_cache_engine()
+            .evict_region(&region, EvictReason::Manual);
+        Some(())
+    }
+
+    fn range_cache_engine_load_region(
+        &self,
+        region: kvproto::metapb::Region,
+    ) -> Option<Result<(), String>> {
+        Some(
+            self.range_cache_engine()
+                .load_region(region)
+                .map_err(|e| format!("{:?}", e)),
+        )
+    }
+
+    fn range_cache_engine_trigger_gc(&self, safe_point: u64) -> Option<Result<(), String>> {
+        Some(self.range_cache_engine().trigger_gc(safe_point))
+    }
+
+    fn range_cache_engine_hot_keys(
+        &self,
+        region_id: u64,
+        top: usize,
+    ) -> Option<Vec<(Vec<u8>, u64)>> {
+        Some(self.range_cache_engine().top_hot_keys(region_id, top))
+    }
 }
 
 pub trait ConfiguredRaftEngine: RaftEngine {
@@ -67,6 +67,7 @@ use raftstore::{
         },
         memory::MEMTRACE_ROOT as MEMTRACE_RAFTSTORE,
         snapshot_backup::PrepareDiskSnapObserver,
+        util::RegionReadProgressRegistry,
         AutoSplitController, CheckLeaderRunner, LocalReader, SnapManager, SnapManagerBuilder,
         SplitCheckRunner, SplitConfigManager, StoreMetaDelegate,
     },
@@ -295,6 +296,17 @@ where
     resolved_ts_scheduler: Option<Scheduler<Task>>,
     grpc_service_mgr: GrpcServiceManager,
     snap_br_rejector: Option<Arc<PrepareDiskSnapObserver>>,
+    // Created up front so it can be handed to the range cache engine (built in
+    // `init_raw_engines`, before `StoreMeta` exists) and later installed into
+    // `StoreMeta` (in `init_engines`) so both sides share the same registry.
+    region_read_progress: RegionReadProgressRegistry,
+    // Created up front for the same reason as `region_read_progress`: the range
+    // cache engine is built in `init_raw_engines`, before the storage GC
+    // worker's own safe point (set up in `init_servers`) exists. Sharing one
+    // `Arc` lets both the raftstore consistency checker and the in-memory
+    // engine's gc see the same safe point the GC worker's compaction filter
+    // uses, instead of each side tracking its own.
+    safe_point: Arc<AtomicU64>,
 }
 
 struct TikvEngines<EK: KvEngine, ER: RaftEngine> {
@@ -505,11 +517,17 @@ where
             resolved_ts_scheduler: None,
             grpc_service_mgr: GrpcServiceManager::new(tx),
             snap_br_rejector: None,
+            region_read_progress: RegionReadProgressRegistry::new(),
+            safe_point: Arc::new(AtomicU64::new(0)),
         }
     }
 
     fn init_engines(&mut self, engines: Engines<EK, ER>) {
-        let store_meta = Arc::new(Mutex::new(StoreMeta::new(PENDING_MSG_CAP)));
+        let mut meta = StoreMeta::new(PENDING_MSG_CAP);
+        // Share the registry the range cache engine was already given in
+        // `init_raw_engines`, instead of the fresh, empty one `StoreMeta::new` makes.
+        meta.region_read_progress = self.region_read_progress.clone();
+        let store_meta = Arc::new(Mutex::new(meta));
         let engine = RaftKv::new(
             ServerRaftStoreRouter::new(
                 self.router.clone(),
@@ -734,7 +752,18 @@ where
 
         // Hybrid engine observer.
         if self.core.config.range_cache_engine.enabled {
-            let observer = HybridEngineObserver::new(Arc::new(engines.engines.kv.clone()));
+            // See the similar store-ident lookup in `init_raw_engines` for why this
+            // is safe to read directly instead of waiting for `bootstrap_store`.
+            let store_id = match engines.engines.raft.get_store_ident() {
+                Ok(Some(ident)) => ident.get_store_id(),
+                Ok(None) => 0,
+                Err(e) => {
+                    warn!("failed to read store ident for hybrid engine observer"; "err" => ?e);
+                    0
+                }
+            };
+            let observer =
+                HybridEngineObserver::new(Arc::new(engines.engines.kv.clone()), store_id);
             observer.register_to(self.coprocessor_host.as_mut().unwrap());
         }
 
@@ -1023,7 +1052,7 @@ where
 
         // `ConsistencyCheckObserver` must be registered before
         // `MultiRaftServer::start`.
-        let safe_point = Arc::new(AtomicU64::new(0));
+        let safe_point = self.safe_point.clone();
         let observer = match self.core.config.coprocessor.consistency_check_method {
             ConsistencyCheckMethod::Mvcc => BoxConsistencyCheckObserver::new(
                 MvccConsistencyCheckObserver::new(safe_point.clone()),
@@ -1529,6 +1558,8 @@ where
         // Create a status server.
         let status_enabled = !self.core.config.server.status_addr.is_empty();
         if status_enabled {
+            let kv_engine = self.engines.as_ref().unwrap().engines.kv.clone();
+            let kv_engine_for_actions = kv_engine.clone();
             let mut status_server = match StatusServer::new(
                 self.core.config.server.status_thread_pool_size,
                 self.cfg_controller.take().unwrap(),
@@ -1537,7 +1568,25 @@ where
                 self.resource_manager.clone(),
                 self.grpc_service_mgr.clone(),
             ) {
-                Ok(status_server) => Box::new(status_server),
+                Ok(status_server) => {
+                    let status_server = status_server.with_range_cache_engine_status(move || {
+                        kv_engine.range_cache_engine_status()
+                    });
+                    let evict_engine = kv_engine_for_actions.clone();
+                    let load_engine = kv_engine_for_actions.clone();
+                    let gc_engine = kv_engine_for_actions.clone();
+                    let hot_keys_engine = kv_engine_for_actions;
+                    let status_server = status_server.with_range_cache_engine_actions(
+                        move |region| evict_engine.range_cache_engine_evict_region(region),
+                        move |region| load_engine.range_cache_engine_load_region(region),
+                        move |safe_point| gc_engine.range_cache_engine_trigger_gc(safe_point),
+                    );
+                    Box::new(status_server.with_range_cache_engine_hot_keys(
+                        move |region_id, top| {
+                            hot_keys_engine.range_cache_engine_hot_keys(region_id, top)
+                        },
+                    ))
+                }
                 Err(e) => {
                     error_unknown!(%e; "failed to start runtime for status service");
                     return;
@@ -1654,8 +1703,30 @@ where
             .expected_region_size
             .get_or_insert(self.core.config.coprocessor.region_split_size());
         let range_cache_engine_config = Arc::new(VersionTrack::new(range_cache_engine_config));
-        let range_cache_engine_context =
-            RangeCacheEngineContext::new(range_cache_engine_config.clone(), self.pd_client.clone());
+        // `raft_engine` is already open above, so if this store has bootstrapped
+        // before (true on every restart except the very first one), its store ID
+        // is already on disk; there's no need to wait for `bootstrap_store` to run
+        // later in `init_servers`. A store that hasn't bootstrapped yet has no
+        // store ID and no regions assigned to it either, so falling back to 0
+        // ("unknown") there is harmless.
+        let store_id = match raft_engine.get_store_ident() {
+            Ok(Some(ident)) => ident.get_store_id(),
+            Ok(None) => 0,
+            Err(e) => {
+                warn!("failed to read store ident for range cache engine"; "err" => ?e);
+                0
+            }
+        };
+        let mut range_cache_engine_context = RangeCacheEngineContext::new(
+            range_cache_engine_config.clone(),
+            self.pd_client.clone(),
+            store_id,
+        )
+        .with_region_read_progress(self.region_read_progress.clone())
+        .with_gc_safe_point(self.safe_point.clone());
+        if let Some(key_manager) = self.core.encryption_key_manager.clone() {
+            range_cache_engine_context = range_cache_engine_context.with_key_manager(key_manager);
+        }
         let range_cache_engine_statistics = range_cache_engine_context.statistics();
         let kv_engine: EK = KvEngineBuilder::build(
             range_cache_engine_context,
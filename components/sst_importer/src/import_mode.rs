@@ -8,7 +8,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use engine_traits::{CfOptions, DbOptions, KvEngine};
+use engine_traits::{CfOptions, DbOptions, KvEngine, RangeCacheEngineExt};
 use futures_util::compat::Future01CompatExt;
 use kvproto::import_sstpb::*;
 use tikv_util::timer::GLOBAL_TIMER_HANDLE;
@@ -38,6 +38,10 @@ impl ImportModeSwitcherInner {
             cf_opts.set_options(db, cf_name, mf)?;
         }
 
+        // Bulk load is done; let the range cache engine resume admitting
+        // newly-hot regions again.
+        db.resume_range_cache_admission();
+
         info!("enter normal mode");
         self.is_import.store(false, Ordering::Release);
         Ok(true)
@@ -59,6 +63,12 @@ impl ImportModeSwitcherInner {
             self.backup_cf_options.push((cf_name.to_owned(), cf_opts));
             import_cf_options.set_options(db, cf_name, mf)?;
         }
+
+        // Data being bulk-loaded is about to be rewritten anyway, so it isn't
+        // worth the range cache engine evicting some other, genuinely hot
+        // region to make room for it.
+        db.pause_range_cache_admission();
+
         info!("enter import mode");
         self.is_import.store(true, Ordering::Release);
         Ok(true)
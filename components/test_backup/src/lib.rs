@@ -16,7 +16,7 @@ use collections::HashMap;
 use engine_rocks::RocksEngine as KTE;
 use engine_traits::{CfName, IterOptions, CF_DEFAULT, CF_WRITE, DATA_KEY_PREFIX_LEN};
 use external_storage::make_local_backend;
-use futures::{channel::mpsc as future_mpsc, executor::block_on};
+use futures::{channel::mpsc as future_mpsc, executor::block_on, StreamExt};
 use grpcio::{ChannelBuilder, Environment};
 use kvproto::{brpb::*, kvrpcpb::*, tikvpb::TikvClient};
 use rand::Rng;
@@ -417,6 +417,31 @@ impl TestSuite {
         (checksum, total_kvs, total_bytes)
     }
 
+    /// Drains `rx` (as returned by [`Self::backup`] or [`Self::backup_raw`])
+    /// and sums up the `(checksum, total_kvs, total_bytes)` carried by each
+    /// produced file, asserting none of the responses report an error.
+    /// Compare the result against [`Self::admin_checksum`] or
+    /// [`Self::raw_kv_checksum`] of the same range to verify a backup
+    /// round-trips the live data faithfully.
+    pub fn must_backup_checksum(
+        &self,
+        rx: future_mpsc::UnboundedReceiver<BackupResponse>,
+    ) -> (u64, u64, u64) {
+        let resps = block_on(rx.collect::<Vec<_>>());
+        let mut checksum = 0;
+        let mut total_kvs = 0;
+        let mut total_bytes = 0;
+        for resp in resps {
+            assert!(!resp.has_error(), "{:?}", resp.get_error());
+            for f in resp.get_files() {
+                checksum ^= f.get_crc64xor();
+                total_kvs += f.get_total_kvs();
+                total_bytes += f.get_total_bytes();
+            }
+        }
+        (checksum, total_kvs, total_bytes)
+    }
+
     pub fn storage_raw_checksum(&self, start: String, end: String) -> (u64, u64, u64) {
         let mut req = RawChecksumRequest::default();
         let mut context = self.context.clone();
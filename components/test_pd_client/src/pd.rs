@@ -1531,6 +1531,18 @@ impl TestPdClient {
         self.cluster.wl().check_merge_target_integrity = false;
     }
 
+    /// Skews the mock PD's TSO clock by `delta_millis`, which may be
+    /// negative. Lets tests exercise safe-point calculation, lease checks,
+    /// and similar TSO-sensitive logic under simulated clock drift between
+    /// PD and the nodes querying it.
+    pub fn skew_tso(&self, delta_millis: i64) {
+        let old = self.tso.load(Ordering::SeqCst);
+        let old_ts: TimeStamp = old.into();
+        let new_physical = (old_ts.physical() as i64 + delta_millis).max(0) as u64;
+        let new_ts = TimeStamp::compose(new_physical, old_ts.logical());
+        self.tso.store(new_ts.into_inner(), Ordering::SeqCst);
+    }
+
     /// The next generated TSO will be `ts + 1`. See `get_tso()` and
     /// `batch_get_tso()`.
     pub fn set_tso(&self, ts: TimeStamp) {
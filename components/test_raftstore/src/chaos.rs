@@ -0,0 +1,69 @@
+// Copyright 2026 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! A tiny DSL for scripting chaos actions against a [`Cluster`] at fixed
+//! offsets from the start of a long-running test, instead of hand-rolling a
+//! sequence of `sleep` calls interleaved with `stop_node`/`add_send_filter`
+//! invocations.
+
+use std::time::Duration;
+
+use crate::{Cluster, KvEngineWithRocks, Simulator};
+
+/// A single scripted action, fired once `at` has elapsed since
+/// [`ChaosSchedule::run`] started.
+struct ChaosStep<EK: KvEngineWithRocks, T: Simulator<EK>> {
+    at: Duration,
+    action: Box<dyn FnOnce(&mut Cluster<EK, T>) + Send>,
+}
+
+/// Builds a list of timed actions and plays them back against a cluster.
+///
+/// ```ignore
+/// ChaosSchedule::new()
+///     .at(Duration::from_secs(5), |c| c.stop_node(2))
+///     .at(Duration::from_secs(10), |c| c.run_node(2).unwrap())
+///     .run(&mut cluster);
+/// ```
+pub struct ChaosSchedule<EK: KvEngineWithRocks, T: Simulator<EK>> {
+    steps: Vec<ChaosStep<EK, T>>,
+}
+
+impl<EK: KvEngineWithRocks, T: Simulator<EK>> Default for ChaosSchedule<EK, T> {
+    fn default() -> Self {
+        ChaosSchedule { steps: Vec::new() }
+    }
+}
+
+impl<EK: KvEngineWithRocks, T: Simulator<EK>> ChaosSchedule<EK, T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedules `action` to run `at` after the schedule starts. Steps may
+    /// be added out of order; `run` sorts them before playback.
+    pub fn at(
+        mut self,
+        at: Duration,
+        action: impl FnOnce(&mut Cluster<EK, T>) + Send + 'static,
+    ) -> Self {
+        self.steps.push(ChaosStep {
+            at,
+            action: Box::new(action),
+        });
+        self
+    }
+
+    /// Plays back every scheduled action against `cluster`, blocking the
+    /// calling thread for the duration of the last step's offset.
+    pub fn run(mut self, cluster: &mut Cluster<EK, T>) {
+        self.steps.sort_by_key(|s| s.at);
+        let mut elapsed = Duration::ZERO;
+        for step in self.steps {
+            if step.at > elapsed {
+                std::thread::sleep(step.at - elapsed);
+                elapsed = step.at;
+            }
+            (step.action)(cluster);
+        }
+    }
+}
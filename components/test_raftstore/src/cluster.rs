@@ -52,12 +52,13 @@ use raftstore::{
     },
     Error, Result,
 };
-use range_cache_memory_engine::RangeCacheMemoryEngine;
+use range_cache_memory_engine::{RangeCacheMemoryEngine, RangeCacheMemoryEngineStatistics};
 use resource_control::ResourceGroupManager;
 use tempfile::TempDir;
 use test_pd_client::TestPdClient;
 use tikv::{config::TikvConfig, server::Result as ServerResult};
 use tikv_util::{
+    config::ReadableSize,
     thread_group::GroupProperties,
     time::{Instant, ThreadReadId},
     worker::LazyWorker,
@@ -183,6 +184,7 @@ pub struct Cluster<EK: KvEngineWithRocks, T: Simulator<EK>> {
     pub engines: HashMap<u64, Engines<EK, RaftTestEngine>>,
     key_managers_map: HashMap<u64, Option<Arc<DataKeyManager>>>,
     pub labels: HashMap<u64, HashMap<String, String>>,
+    snap_io_max_bytes_per_sec: HashMap<u64, u64>,
     group_props: HashMap<u64, GroupProperties>,
     pub sst_workers: Vec<LazyWorker<String>>,
     pub sst_workers_map: HashMap<u64, usize>,
@@ -225,6 +227,7 @@ where
             engines: HashMap::default(),
             key_managers_map: HashMap::default(),
             labels: HashMap::default(),
+            snap_io_max_bytes_per_sec: HashMap::default(),
             group_props: HashMap::default(),
             sim,
             pd_client,
@@ -404,6 +407,9 @@ where
         if let Some(labels) = self.labels.get(&node_id) {
             cfg.server.labels = labels.to_owned();
         }
+        if let Some(bps) = self.snap_io_max_bytes_per_sec.get(&node_id) {
+            cfg.server.snap_io_max_bytes_per_sec = ReadableSize(*bps);
+        }
         let store_meta = match self.store_metas.entry(node_id) {
             MapEntry::Occupied(o) => {
                 let mut meta = o.get().lock().unwrap();
@@ -816,6 +822,35 @@ where
             .insert(key.to_owned(), value.to_owned());
     }
 
+    /// Caps the snapshot send/receive bandwidth of `node_id` to
+    /// `bytes_per_sec`. Must be called before the node is (re)started, as the
+    /// limit is only read when the node's server config is built.
+    pub fn set_snap_io_max_bytes_per_sec(&mut self, node_id: u64, bytes_per_sec: u64) {
+        self.snap_io_max_bytes_per_sec
+            .insert(node_id, bytes_per_sec);
+    }
+
+    /// Blocks until `store_id`'s apply state for `region_id` reaches at least
+    /// `index`, i.e. a snapshot covering that index has been received and
+    /// applied. Makes add-peer-via-snapshot tests deterministic instead of
+    /// relying on sleeps.
+    pub fn wait_snapshot_applied(&self, store_id: u64, region_id: u64, index: u64) {
+        let timer = Instant::now();
+        loop {
+            let applied_index = self.apply_state(region_id, store_id).get_applied_index();
+            if applied_index >= index {
+                return;
+            }
+            if timer.saturating_elapsed() > Duration::from_secs(5) {
+                panic!(
+                    "store {} region {} snapshot not applied to index {} after 5s, applied {}",
+                    store_id, region_id, index, applied_index
+                );
+            }
+            sleep_ms(100);
+        }
+    }
+
     pub fn add_new_engine(&mut self) -> u64 {
         self.create_engine(None);
         self.count += 1;
@@ -1031,6 +1066,7 @@ where
                 )),
                 region_id: 0,
                 epoch_version: 0,
+                force_disk_read: false,
             };
             self.get_cf_with_snap_ctx(CF_DEFAULT, key, true, ctx)
         }
@@ -1048,6 +1084,7 @@ where
                 )),
                 region_id: 0,
                 epoch_version: 0,
+                force_disk_read: false,
             };
             self.get_cf_with_snap_ctx(cf, key, true, ctx)
         }
@@ -1065,6 +1102,7 @@ where
                 )),
                 region_id: 0,
                 epoch_version: 0,
+                force_disk_read: false,
             };
             self.get_cf_with_snap_ctx(CF_DEFAULT, key, true, ctx)
         }
@@ -2171,6 +2209,93 @@ where
         Ok(())
     }
 
+    /// Reads `[start_key, end_key)` from `cf` on every voter replica of
+    /// `region_id` and asserts that all replicas agree, panicking with the
+    /// offending store ids if any pair diverges. Useful for catching apply
+    /// divergences in tests such as remove-and-add-peer.
+    pub fn verify_replica_consistency(
+        &self,
+        region_id: u64,
+        cf: &str,
+        start_key: &[u8],
+        end_key: &[u8],
+    ) {
+        let store_ids = self
+            .voter_store_ids_of_region(region_id)
+            .unwrap_or_else(|| panic!("region {} not found", region_id));
+        assert!(!store_ids.is_empty(), "region {} has no voters", region_id);
+
+        let mut baseline: Option<(u64, Vec<(Vec<u8>, Vec<u8>)>)> = None;
+        for store_id in store_ids {
+            let mut kvs = Vec::new();
+            self.scan(store_id, cf, start_key, end_key, false, |k, v| {
+                kvs.push((k.to_vec(), v.to_vec()));
+                Ok(true)
+            })
+            .unwrap();
+            match &baseline {
+                None => baseline = Some((store_id, kvs)),
+                Some((base_store, base_kvs)) => assert_eq!(
+                    base_kvs, &kvs,
+                    "replica divergence in region {} cf {}: store {} and store {} disagree",
+                    region_id, cf, base_store, store_id
+                ),
+            }
+        }
+    }
+
+    /// Blocks until the leaders of `region_ids` are spread across stores
+    /// such that the busiest store leads at most `max_imbalance` more
+    /// regions than the least busy one, e.g. after issuing several
+    /// `must_transfer_leader` calls meant to balance a cluster.
+    pub fn wait_leader_balanced(&mut self, region_ids: &[u64], max_imbalance: usize) {
+        let timer = Instant::now();
+        loop {
+            let mut leader_count: HashMap<u64, usize> = HashMap::default();
+            for &region_id in region_ids {
+                if let Some(leader) = self.leader_of_region(region_id) {
+                    *leader_count.entry(leader.get_store_id()).or_default() += 1;
+                }
+            }
+            let (min, max) = leader_count
+                .values()
+                .fold((usize::MAX, 0), |(min, max), &c| (min.min(c), max.max(c)));
+            if leader_count.len() >= 2 && max.saturating_sub(min) <= max_imbalance {
+                return;
+            }
+            if timer.saturating_elapsed() > Duration::from_secs(10) {
+                panic!(
+                    "leaders of {:?} not balanced after 10s, counts by store: {:?}",
+                    region_ids, leader_count
+                );
+            }
+            sleep_ms(100);
+        }
+    }
+
+    /// Blocks until `region_id`'s voters are spread across at least
+    /// `min_distinct_stores` stores, i.e. the replicas have been scattered
+    /// away from wherever they started.
+    pub fn wait_replicas_scattered(&self, region_id: u64, min_distinct_stores: usize) {
+        let timer = Instant::now();
+        loop {
+            let distinct = self
+                .voter_store_ids_of_region(region_id)
+                .map(|ids| ids.into_iter().collect::<HashSet<_>>().len())
+                .unwrap_or(0);
+            if distinct >= min_distinct_stores {
+                return;
+            }
+            if timer.saturating_elapsed() > Duration::from_secs(10) {
+                panic!(
+                    "region {} only scattered across {} stores after 10s, wanted {}",
+                    region_id, distinct, min_distinct_stores
+                );
+            }
+            sleep_ms(100);
+        }
+    }
+
     pub fn range_cache_engine_enabled_with_whole_range(&mut self, v: bool) {
         self.range_cache_engine_enabled_with_whole_range = v;
     }
@@ -2248,4 +2373,13 @@ impl<T: Simulator<HybridEngineImpl>> Cluster<HybridEngineImpl, T> {
             .range_cache_engine()
             .clone()
     }
+
+    /// Returns `node_id`'s range cache engine statistics (hit/miss tickers,
+    /// etc), for asserting on cache behavior in tests.
+    pub fn get_range_cache_engine_statistics(
+        &self,
+        node_id: u64,
+    ) -> Arc<RangeCacheMemoryEngineStatistics> {
+        self.get_range_cache_engine(node_id).statistics()
+    }
 }
@@ -8,6 +8,7 @@ extern crate lazy_static;
 #[macro_use]
 extern crate tikv_util;
 
+pub mod chaos;
 mod cluster;
 mod config;
 mod node;
@@ -18,5 +19,6 @@ mod transport_simulate;
 pub mod util;
 
 pub use crate::{
-    cluster::*, config::Config, node::*, router::*, server::*, transport_simulate::*, util::*,
+    chaos::*, cluster::*, config::Config, node::*, router::*, server::*, transport_simulate::*,
+    util::*,
 };
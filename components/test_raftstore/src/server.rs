@@ -236,6 +236,10 @@ impl<EK: KvEngineWithRocks> ServerCluster<EK> {
         self.causal_ts_providers.get(&node_id).cloned()
     }
 
+    pub fn get_importer(&self, node_id: u64) -> Option<Arc<SstImporter<EK>>> {
+        self.importers.get(&node_id).cloned()
+    }
+
     fn init_resource_metering(
         &self,
         cfg: &resource_metering::Config,
@@ -907,6 +911,24 @@ pub fn new_server_cluster_with_hybrid_engine(
     cluster
 }
 
+// Same as `new_server_cluster_with_hybrid_engine`, but lets the caller tweak
+// the range cache engine config (e.g. memory limits, gc interval) before any
+// node is started.
+pub fn new_server_cluster_with_hybrid_engine_with_config(
+    id: u64,
+    count: usize,
+    configure: impl FnOnce(&mut RangeCacheEngineConfig),
+) -> Cluster<HybridEngineImpl, ServerCluster<HybridEngineImpl>> {
+    let pd_client = Arc::new(TestPdClient::new(id, false));
+    let sim = Arc::new(RwLock::new(ServerCluster::new(Arc::clone(&pd_client))));
+    let mut cluster = Cluster::new(id, count, sim, pd_client, ApiVersion::V1);
+    cluster.range_cache_engine_enabled_with_whole_range(true);
+    let mut range_cache_engine_config = RangeCacheEngineConfig::config_for_test();
+    configure(&mut range_cache_engine_config);
+    cluster.cfg.tikv.range_cache_engine = range_cache_engine_config;
+    cluster
+}
+
 pub fn new_server_cluster_with_api_ver(
     id: u64,
     count: usize,
@@ -17,25 +17,29 @@ use encryption_export::{
     data_key_manager_from_config, DataKeyManager, FileConfig, MasterKeyConfig,
 };
 use engine_rocks::{
-    config::BlobRunMode, RocksCompactedEvent, RocksEngine, RocksSnapshot, RocksStatistics,
+    config::BlobRunMode, RocksCompactedEvent, RocksEngine, RocksSnapshot, RocksSstWriterBuilder,
+    RocksStatistics,
 };
 use engine_test::raft::RaftTestEngine;
 use engine_traits::{
-    CfName, CfNamesExt, Engines, Iterable, KvEngine, Peekable, RaftEngineDebug, RaftEngineReadOnly,
-    CF_DEFAULT, CF_RAFT, CF_WRITE,
+    CfName, CfNamesExt, Engines, FlowControlFactorsExt, Iterable, KvEngine, Peekable,
+    RaftEngineDebug, RaftEngineReadOnly, SstWriter, SstWriterBuilder, CF_DEFAULT, CF_RAFT,
+    CF_WRITE,
 };
 use fail::fail_point;
-use file_system::IoRateLimiter;
+use file_system::{calc_crc32_bytes, IoRateLimiter};
 use futures::{executor::block_on, future::BoxFuture, StreamExt};
 use grpcio::{ChannelBuilder, Environment};
 use hybrid_engine::HybridEngine;
 use kvproto::{
+    disk_usage::DiskUsage,
     encryptionpb::EncryptionMethod,
+    import_sstpb::SstMeta,
     kvrpcpb::{PrewriteRequestPessimisticAction::*, *},
-    metapb::{self, RegionEpoch},
+    metapb::{self, Region, RegionEpoch},
     raft_cmdpb::{
         AdminCmdType, AdminRequest, ChangePeerRequest, ChangePeerV2Request, CmdType,
-        RaftCmdRequest, RaftCmdResponse, Request, StatusCmdType, StatusRequest,
+        RaftCmdRequest, RaftCmdResponse, RaftRequestHeader, Request, StatusCmdType, StatusRequest,
     },
     raft_serverpb::{
         PeerState, RaftApplyState, RaftLocalState, RaftTruncatedState, RegionLocalState,
@@ -52,6 +56,7 @@ use raftstore::{
 use rand::{seq::SliceRandom, RngCore};
 use range_cache_memory_engine::{RangeCacheEngineContext, RangeCacheMemoryEngine};
 use server::common::{ConfiguredRaftEngine, KvEngineBuilder};
+use sst_importer::SstImporter;
 use tempfile::TempDir;
 use test_pd_client::TestPdClient;
 use test_util::eventually;
@@ -720,7 +725,9 @@ where
     let disk_engine = factory.create_shared_db(dir.path()).unwrap();
     let config = Arc::new(VersionTrack::new(cfg.tikv.range_cache_engine.clone()));
     let kv_engine: EK = KvEngineBuilder::build(
-        RangeCacheEngineContext::new(config, pd_client),
+        // The store hasn't bootstrapped yet at this point in test cluster setup, so
+        // its store ID isn't known; 0 disables the cache's local-peer role check.
+        RangeCacheEngineContext::new(config, pd_client, 0),
         disk_engine,
         None,
         None,
@@ -1536,6 +1543,119 @@ pub fn check_compacted(
     true
 }
 
+/// One invariant violation observed by [`run_concurrent_raw_workload`]: the
+/// client read back a different value than the one it had just written for
+/// `key`.
+#[derive(Debug)]
+pub struct WorkloadViolation {
+    pub client: usize,
+    pub key: Vec<u8>,
+    pub written: Vec<u8>,
+    pub read: Option<Vec<u8>>,
+}
+
+/// Drives `num_clients` threads, each repeatedly raw-putting then
+/// raw-getting a key of its own (`key_prefix-<client>-<i>`) for `duration`,
+/// asserting read-your-writes on every round. Returns every violation
+/// observed instead of panicking, so callers can decide how to report them.
+pub fn run_concurrent_raw_workload(
+    client: &TikvClient,
+    ctx: Context,
+    num_clients: usize,
+    key_prefix: &[u8],
+    duration: Duration,
+) -> Vec<WorkloadViolation> {
+    let violations = Arc::new(Mutex::new(Vec::new()));
+    let stop_at = Instant::now() + duration;
+    thread::scope(|s| {
+        for client_idx in 0..num_clients {
+            let client = client.clone();
+            let ctx = ctx.clone();
+            let violations = Arc::clone(&violations);
+            s.spawn(move || {
+                let mut i = 0u64;
+                while Instant::now() < stop_at {
+                    let key = [key_prefix, format!("-{}-{}", client_idx, i).as_bytes()].concat();
+                    let value = format!("v{}", i).into_bytes();
+                    must_raw_put(&client, ctx.clone(), key.clone(), value.clone());
+                    let read = must_raw_get(&client, ctx.clone(), key.clone());
+                    if read.as_ref() != Some(&value) {
+                        violations.lock().unwrap().push(WorkloadViolation {
+                            client: client_idx,
+                            key,
+                            written: value,
+                            read,
+                        });
+                    }
+                    i += 1;
+                }
+            });
+        }
+    });
+    Arc::try_unwrap(violations).unwrap().into_inner().unwrap()
+}
+
+/// Configures several fail points together and removes all of them when
+/// dropped, so a test can coordinate a scenario spanning multiple fail
+/// points (e.g. one per store) without hand-rolling cleanup on every exit
+/// path, including panics.
+pub struct FailPointScenario {
+    names: Vec<String>,
+}
+
+impl FailPointScenario {
+    /// Applies `(name, action)` pairs via `fail::cfg`, in order. Panics if
+    /// any `fail::cfg` call fails.
+    pub fn new(fail_points: &[(&str, &str)]) -> FailPointScenario {
+        let mut names = Vec::with_capacity(fail_points.len());
+        for (name, action) in fail_points {
+            fail::cfg(*name, action).unwrap();
+            names.push((*name).to_owned());
+        }
+        FailPointScenario { names }
+    }
+}
+
+impl Drop for FailPointScenario {
+    fn drop(&mut self) {
+        for name in &self.names {
+            fail::remove(name);
+        }
+    }
+}
+
+/// Scrapes the process-wide Prometheus registry (the same source the status
+/// server's `/metrics` endpoint serves) and returns the value of the first
+/// sample whose metric line contains `line_contains`, e.g.
+/// `r#"tikv_raftstore_snapshot_total{type="applying"}"#`.
+pub fn scrape_metric(line_contains: &str) -> Option<f64> {
+    tikv_util::metrics::dump(false).lines().find_map(|line| {
+        if !line.contains(line_contains) || line.starts_with('#') {
+            return None;
+        }
+        line.rsplit(' ').next()?.parse::<f64>().ok()
+    })
+}
+
+/// Waits until [`scrape_metric`] returns a value satisfying `check`, polling
+/// every 100ms for up to 5s. Panics with the last observed value on timeout.
+pub fn wait_for_metric(line_contains: &str, check: impl Fn(Option<f64>) -> bool) {
+    let timer = Instant::now();
+    loop {
+        let value = scrape_metric(line_contains);
+        if check(value) {
+            return;
+        }
+        if timer.saturating_elapsed() > Duration::from_secs(5) {
+            panic!(
+                "metric matching {:?} did not satisfy the predicate after 5s, last value: {:?}",
+                line_contains, value
+            );
+        }
+        sleep_ms(100);
+    }
+}
+
 pub fn must_raw_put(client: &TikvClient, ctx: Context, key: Vec<u8>, value: Vec<u8>) {
     let mut put_req = RawPutRequest::default();
     put_req.set_context(ctx);
@@ -1786,6 +1906,100 @@ pub fn wait_for_synced<EK: KvEngineWithRocks>(
     assert!(snapshot.ext().is_max_ts_synced());
 }
 
+/// Makes store `store_id` report `usage` from `get_disk_status` until
+/// [`clear_store_disk_usage`] is called, via the `disk_{almost,already}_
+/// full_peer_<id>` fail points also used by `tests/failpoints`. Only store
+/// ids 1 through 5 are wired up, matching those fail points.
+pub fn set_store_disk_usage(store_id: u64, usage: DiskUsage) {
+    let name = match usage {
+        DiskUsage::AlmostFull => format!("disk_almost_full_peer_{}", store_id),
+        DiskUsage::AlreadyFull => format!("disk_already_full_peer_{}", store_id),
+        DiskUsage::Normal => return clear_store_disk_usage(store_id),
+    };
+    fail::cfg(name, "return").unwrap();
+}
+
+/// Undoes [`set_store_disk_usage`] for `store_id`.
+pub fn clear_store_disk_usage(store_id: u64) {
+    fail::remove(format!("disk_almost_full_peer_{}", store_id));
+    fail::remove(format!("disk_already_full_peer_{}", store_id));
+}
+
+/// Blocks until `cf`'s memtable has been flushed to L0, i.e. there are no
+/// more immutable memtables left for it. Avoids tests having to sleep and
+/// hope a flush has completed.
+pub fn wait_for_flush(engine: &RocksEngine, cf: CfName) {
+    eventually(Duration::from_millis(20), Duration::from_secs(5), || {
+        engine
+            .get_cf_num_immutable_mem_table(cf)
+            .unwrap()
+            .unwrap_or(0)
+            == 0
+    });
+}
+
+/// Blocks until `cf`'s L0 file count drops to at most `max_l0_files`. Avoids
+/// tests having to sleep and hope a compaction has completed.
+pub fn wait_for_compaction(engine: &RocksEngine, cf: CfName, max_l0_files: u64) {
+    eventually(Duration::from_millis(20), Duration::from_secs(5), || {
+        engine
+            .get_cf_num_files_at_level(cf, 0)
+            .unwrap()
+            .unwrap_or(0)
+            <= max_l0_files
+    });
+}
+
+/// Builds an SST containing `kvs` (already sorted by key) in `cf`, registers
+/// it with `importer`, and ingests it into `region`'s leader via a raft
+/// `IngestSst` command. Consolidates the write-sst/build-meta/ingest dance
+/// that several cache- and import-related tests otherwise hand-roll.
+pub fn must_ingest_sst<EK: KvEngineWithRocks, T: Simulator<EK>>(
+    cluster: &mut Cluster<EK, T>,
+    importer: &SstImporter<EK>,
+    cf: CfName,
+    region: &Region,
+    kvs: &[(Vec<u8>, Vec<u8>)],
+) {
+    let sst_dir = TempDir::new().unwrap();
+    let sst_path = sst_dir.path().join("test.sst");
+    let mut writer = RocksSstWriterBuilder::new()
+        .build(sst_path.to_str().unwrap())
+        .unwrap();
+    for (k, v) in kvs {
+        writer.put(k, v).unwrap();
+    }
+    writer.finish().unwrap();
+
+    let mut sst_meta = SstMeta::default();
+    sst_meta.set_region_id(region.get_id());
+    sst_meta.set_region_epoch(region.get_region_epoch().clone());
+    sst_meta.set_uuid(uuid::Uuid::new_v4().as_bytes().to_vec());
+    sst_meta.set_cf_name(cf.to_owned());
+    let content = std::fs::read(&sst_path).unwrap();
+    sst_meta.set_crc32(calc_crc32_bytes(&content));
+    sst_meta.set_length(content.len() as u64);
+
+    let mut f = importer.create(&sst_meta).unwrap();
+    f.append(&content).unwrap();
+    f.finish().unwrap();
+
+    let mut ingest = Request::default();
+    ingest.set_cmd_type(CmdType::IngestSst);
+    ingest.mut_ingest_sst().set_sst(sst_meta);
+    let mut header = RaftRequestHeader::default();
+    header.set_peer(cluster.leader_of_region(region.get_id()).unwrap());
+    header.set_region_id(region.get_id());
+    header.set_region_epoch(region.get_region_epoch().clone());
+    let mut cmd = RaftCmdRequest::default();
+    cmd.set_header(header);
+    cmd.mut_requests().push(ingest);
+    let resp = cluster
+        .call_command_on_leader(cmd, Duration::from_secs(5))
+        .unwrap();
+    assert!(!resp.get_header().has_error(), "{:?}", resp);
+}
+
 pub fn test_delete_range<EK: KvEngineWithRocks, T: Simulator<EK>>(
     cluster: &mut Cluster<EK, T>,
     cf: CfName,
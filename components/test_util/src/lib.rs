@@ -80,7 +80,33 @@ static INITIAL_PORT: AtomicU16 = AtomicU16::new(0);
 const MIN_LOCAL_PORT: u16 = 32767;
 
 /// Allocates a port for testing purpose.
+///
+/// Candidates are drawn from a process-wide counter (seeded randomly so that
+/// parallel test binaries don't all start from the same value) but are only
+/// handed out once a bind to `127.0.0.1:<port>` actually succeeds, so two
+/// tests racing in the same or different processes never get handed the same
+/// port even though the counter itself gives no such guarantee.
 pub fn alloc_port() -> u16 {
+    loop {
+        let p = next_port_candidate();
+        // Bind-and-drop immediately: this only proves the port was free at
+        // this instant, not that it will still be free when the caller binds
+        // it, but it turns the common case of "counter collided with a port
+        // some other test is already using" from a hang/flake into a retry.
+        if std::net::TcpListener::bind(("127.0.0.1", p)).is_ok() {
+            return p;
+        }
+    }
+}
+
+/// Allocates `n` ports, each individually verified free the same way as
+/// [`alloc_port`]. Useful when starting a multi-node cluster that needs one
+/// port per node up front.
+pub fn alloc_port_range(n: usize) -> Vec<u16> {
+    (0..n).map(|_| alloc_port()).collect()
+}
+
+fn next_port_candidate() -> u16 {
     let p = INITIAL_PORT.load(Ordering::Relaxed);
     if p == 0 {
         let _ = INITIAL_PORT.compare_exchange(
@@ -148,6 +148,10 @@ impl<S: Storage, F: KvFormat> BatchIndexScanExecutor<S, F> {
             storage,
             key_ranges,
             is_backward,
+            // Unlike table scans, an index scan can never skip loading the value: its
+            // header (tail length, version flag) and optional segments (int handle,
+            // common handle, partition id, restore data) have to be parsed to decode
+            // the row correctly, regardless of which columns were actually requested.
             is_key_only: false,
             accept_point_range: unique,
             is_scanned_range_aware,
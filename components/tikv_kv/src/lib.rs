@@ -336,6 +336,9 @@ pub struct SnapContext<'a> {
     pub key_ranges: Vec<KeyRange>,
     // Marks that this snapshot request is allowed in the flashback state.
     pub allowed_in_flashback: bool,
+    // When set, skip the range cache engine for this read and always take the
+    // snapshot from the disk engine, even if the cache could otherwise serve it.
+    pub force_disk_read: bool,
 }
 
 /// Engine defines the common behaviour for a storage engine type.
@@ -14,6 +14,10 @@ use futures::{
     stream::{Stream, StreamExt},
     task::{self, ArcWake, Context, Poll},
 };
+use futures_util::compat::Future01CompatExt;
+use lazy_static::lazy_static;
+use prometheus::*;
+use yatp::task::future::TaskCell;
 
 use crate::{
     callback::must_call,
@@ -219,8 +223,6 @@ pub fn block_on_timeout<F>(fut: F, dur: std::time::Duration) -> Result<F::Output
 where
     F: std::future::Future,
 {
-    use futures_util::compat::Future01CompatExt;
-
     let mut timeout = GLOBAL_TIMER_HANDLE
         .delay(std::time::Instant::now() + dur)
         .compat()
@@ -235,6 +237,44 @@ where
     })
 }
 
+lazy_static! {
+    static ref SPAWN_DEADLINE_EXCEEDED_VEC: IntCounterVec = register_int_counter_vec!(
+        "tikv_spawn_with_deadline_exceeded_total",
+        "Total number of tasks spawned via spawn_with_deadline that were abandoned because they \
+         did not finish before their deadline.",
+        &["name"]
+    )
+    .unwrap();
+}
+
+/// Spawns `fut` on `remote`, racing it against `deadline`. If `fut` hasn't
+/// finished by then, it's dropped (there's no thread to kill, so this only
+/// stops `fut` from being polled further, it does not forcibly interrupt
+/// whatever I/O it was waiting on) and the timeout is reported via
+/// `SPAWN_DEADLINE_EXCEEDED_VEC` and a warning log, rather than letting a
+/// stuck background call (e.g. to PD) wedge the worker indefinitely.
+pub fn spawn_with_deadline(
+    remote: &yatp::Remote<TaskCell>,
+    name: &'static str,
+    fut: impl Future<Output = ()> + Send + 'static,
+    deadline: Duration,
+) {
+    remote.spawn(async move {
+        let timed_out = futures::select! {
+            _ = fut.fuse() => false,
+            _ = GLOBAL_TIMER_HANDLE.delay(std::time::Instant::now() + deadline).compat().fuse() => true,
+        };
+        if timed_out {
+            SPAWN_DEADLINE_EXCEEDED_VEC.with_label_values(&[name]).inc();
+            warn!(
+                "background task exceeded its deadline and was abandoned";
+                "task" => name,
+                "deadline" => ?deadline,
+            );
+        }
+    });
+}
+
 pub struct RescheduleChecker<B> {
     duration: Duration,
     start: Instant,
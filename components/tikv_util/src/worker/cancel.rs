@@ -0,0 +1,91 @@
+// Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! A lightweight, cooperative cancellation handle for scheduled tasks and
+//! futures spawned onto a yatp `Remote`.
+//!
+//! `Scheduler`/`Worker` have no built-in way to pull a task back out of the
+//! queue once it's been submitted, and a future spawned via `Remote::spawn`
+//! runs to completion once started. `CancellationToken` doesn't change
+//! either of those mechanics — cancelling one only flips a flag. Callers are
+//! expected to check `is_cancelled()` themselves at a safe point: a queued
+//! task's `Runnable::run` should check it before doing any work, and a
+//! long-running future should check it between steps (or use
+//! `until_cancelled` to stop being polled once cancelled).
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::Poll,
+};
+
+use futures::future::{self, Either};
+
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken(Arc::new(AtomicBool::new(false)))
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+/// Drives `fut` to completion, but stops polling it and resolves to `None`
+/// as soon as `token` is cancelled. Does not interrupt `fut` mid-poll: it
+/// still needs to check `token` itself (or be composed of futures that do)
+/// to actually stop doing work rather than just being abandoned.
+pub async fn until_cancelled<F: Future>(token: CancellationToken, fut: F) -> Option<F::Output> {
+    let cancelled = future::poll_fn(move |_| {
+        if token.is_cancelled() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    });
+    match future::select(Box::pin(fut), Box::pin(cancelled)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    #[test]
+    fn test_cancellation_token() {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+        let cloned = token.clone();
+        assert!(!cloned.is_cancelled());
+        token.cancel();
+        assert!(cloned.is_cancelled());
+    }
+
+    #[test]
+    fn test_until_cancelled_not_cancelled() {
+        let token = CancellationToken::new();
+        let res = block_on(until_cancelled(token, future::ready(42)));
+        assert_eq!(res, Some(42));
+    }
+
+    #[test]
+    fn test_until_cancelled_already_cancelled() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let res: Option<()> = block_on(until_cancelled(token, future::pending()));
+        assert_eq!(res, None);
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! A shared periodic-scheduling facility, so components stop hand-rolling
+//! their own tick thread with a `crossbeam::channel::tick`/`select!` loop
+//! (see e.g. the TODO on `BgWorkManager::start_tick`: "Instead of spawning a
+//! new thread, we should run this task in a shared background thread").
+//!
+//! `CronScheduler` runs a single background thread that can host any number
+//! of jobs, each firing on a [`Schedule`]. A job is just a closure, so it's
+//! typically a call into an existing `Scheduler::schedule`.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crossbeam::channel::{self, Sender};
+
+use crate::{time::Instant, timer::Timer};
+
+/// How long the cron thread blocks waiting for a doorbell before
+/// re-checking the stop flag and its own next deadline.
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// When a job registered with [`CronScheduler::schedule`] should repeat.
+#[derive(Clone, Copy, Debug)]
+pub enum Schedule {
+    /// Fire every `interval`, phased from when the job is registered. This
+    /// is what a hand-rolled `crossbeam::channel::tick(interval)` gives you.
+    FixedRate(Duration),
+    /// Fire every `interval`, phased so firings land on a wall-clock
+    /// boundary that's a multiple of `interval` since the Unix epoch (e.g.
+    /// `Duration::from_secs(3600)` fires on the hour) the way a crontab
+    /// entry like `0 * * * *` would, rather than drifting based on when the
+    /// job happened to be registered.
+    Aligned(Duration),
+}
+
+impl Schedule {
+    fn repeat_interval(&self) -> Duration {
+        match *self {
+            Schedule::FixedRate(interval) | Schedule::Aligned(interval) => interval,
+        }
+    }
+
+    fn first_delay(&self) -> Duration {
+        match *self {
+            Schedule::FixedRate(interval) => interval,
+            Schedule::Aligned(interval) => {
+                let interval_nanos = interval.as_nanos();
+                if interval_nanos == 0 {
+                    return Duration::ZERO;
+                }
+                let since_epoch = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                let remainder = since_epoch.as_nanos() % interval_nanos;
+                if remainder == 0 {
+                    interval
+                } else {
+                    Duration::from_nanos((interval_nanos - remainder) as u64)
+                }
+            }
+        }
+    }
+}
+
+struct Job {
+    run: Box<dyn FnMut() + Send>,
+    repeat: Duration,
+}
+
+/// A single shared background thread that runs registered jobs on their own
+/// [`Schedule`]s.
+pub struct CronScheduler {
+    timer: Arc<Mutex<Timer<Job>>>,
+    doorbell: Sender<()>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl CronScheduler {
+    pub fn new(name: impl Into<String>) -> Self {
+        let timer = Arc::new(Mutex::new(Timer::new(16)));
+        let (doorbell_tx, doorbell_rx) = channel::unbounded();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let worker_timer = timer.clone();
+        let worker_stop = stop.clone();
+        let handle = thread::Builder::new()
+            .name(name.into())
+            .spawn(move || run(&worker_timer, &doorbell_rx, &worker_stop))
+            .unwrap();
+
+        CronScheduler {
+            timer,
+            doorbell: doorbell_tx,
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Registers `job` to run repeatedly per `schedule`, starting at its
+    /// first occurrence. `job` is typically a closure that calls
+    /// `Scheduler::schedule` (or `schedule_with_backoff`) on an existing
+    /// worker's scheduler; errors scheduling the downstream task are the
+    /// closure's own responsibility to log, the same as in a hand-rolled
+    /// tick loop.
+    pub fn schedule<F: FnMut() + Send + 'static>(&self, schedule: Schedule, job: F) {
+        let first_delay = schedule.first_delay();
+        let repeat = schedule.repeat_interval();
+        self.timer.lock().unwrap().add_task(
+            first_delay,
+            Job {
+                run: Box::new(job),
+                repeat,
+            },
+        );
+        let _ = self.doorbell.send(());
+    }
+
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        let _ = self.doorbell.send(());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for CronScheduler {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn run(timer: &Mutex<Timer<Job>>, doorbell: &channel::Receiver<()>, stop: &AtomicBool) {
+    while !stop.load(Ordering::Acquire) {
+        let wait = {
+            let mut t = timer.lock().unwrap();
+            let now = Instant::now();
+            while let Some(mut job) = t.pop_task_before(now) {
+                (job.run)();
+                t.add_task(job.repeat, job);
+            }
+            t.next_timeout()
+                .map(|at| at.saturating_duration_since(now))
+                .unwrap_or(MAX_POLL_INTERVAL)
+        };
+        let _ = doorbell.recv_timeout(wait.min(MAX_POLL_INTERVAL));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn test_fixed_rate() {
+        let mut cron = CronScheduler::new("test-cron");
+        let (tx, rx) = mpsc::channel();
+        cron.schedule(Schedule::FixedRate(Duration::from_millis(20)), move || {
+            let _ = tx.send(());
+        });
+        rx.recv_timeout(Duration::from_secs(3)).unwrap();
+        rx.recv_timeout(Duration::from_secs(3)).unwrap();
+        cron.stop();
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! A `Worker` variant that deduplicates pending tasks by key.
+//!
+//! Components that only ever want one outstanding task per "thing" (a
+//! region, a CF, ...) tend to reach for an ad hoc guard flag next to the
+//! state they're protecting, e.g. `RegionManager::try_set_regions_in_gc`.
+//! That works, but it conflates "is a task pending" with the actual
+//! scheduling, and every caller has to reinvent the compare-and-swap dance.
+//!
+//! `DedupScheduler` folds that pattern into the scheduling step itself:
+//! submitting a task under a key that already has a pending (not yet
+//! started) task replaces it in place, keeping the original task's position
+//! in the queue. Once a task starts running, its key is free again and the
+//! next `schedule` for that key starts a fresh entry.
+
+use std::{
+    collections::VecDeque,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use collections::HashMap;
+use crossbeam::channel::{self, Sender};
+use prometheus::{IntCounter, IntGauge};
+
+use super::{
+    metrics::{WORKER_HANDLED_TASK_VEC, WORKER_PENDING_TASK_VEC},
+    pool::Runnable,
+};
+
+/// How long the worker thread blocks waiting for a doorbell before
+/// re-checking the stop flag.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+struct Queue<K, T> {
+    // FIFO order of distinct pending keys. A key's position is fixed when it
+    // is first scheduled and doesn't move when a later `schedule` for the
+    // same key replaces its task.
+    order: VecDeque<K>,
+    pending: HashMap<K, T>,
+}
+
+impl<K: Eq + Hash + Clone, T> Queue<K, T> {
+    fn push(&mut self, key: K, task: T) -> Option<T> {
+        let replaced = self.pending.insert(key.clone(), task);
+        if replaced.is_none() {
+            self.order.push_back(key);
+        }
+        replaced
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let key = self.order.pop_front()?;
+        Some(self.pending.remove(&key).unwrap())
+    }
+}
+
+pub struct DedupScheduler<K: Eq + Hash + Clone + Send, T: Send> {
+    queue: Arc<Mutex<Queue<K, T>>>,
+    doorbell: Sender<()>,
+    metrics_pending_task_count: IntGauge,
+}
+
+impl<K: Eq + Hash + Clone + Send, T: Send> DedupScheduler<K, T> {
+    /// Schedules `task` under `key`, returning the task it replaced if one
+    /// was already pending under the same key.
+    ///
+    /// A replaced task is simply dropped: callers whose tasks carry
+    /// resources that must be released on cancellation (e.g. a response
+    /// channel) should fold that into the task's `Drop` impl rather than
+    /// relying on `run` being called.
+    pub fn schedule(&self, key: K, task: T) -> Option<T> {
+        let replaced = {
+            let mut queue = self.queue.lock().unwrap();
+            queue.push(key, task)
+        };
+        if replaced.is_none() {
+            self.metrics_pending_task_count.inc();
+        }
+        let _ = self.doorbell.send(());
+        replaced
+    }
+}
+
+impl<K: Eq + Hash + Clone + Send, T: Send> Clone for DedupScheduler<K, T> {
+    fn clone(&self) -> Self {
+        DedupScheduler {
+            queue: self.queue.clone(),
+            doorbell: self.doorbell.clone(),
+            metrics_pending_task_count: self.metrics_pending_task_count.clone(),
+        }
+    }
+}
+
+/// A worker running on its own thread that drains a `DedupScheduler`'s queue
+/// in FIFO-by-first-schedule order.
+pub struct DedupWorker {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl DedupWorker {
+    pub fn start<K, R>(
+        name: impl Into<String>,
+        mut runner: R,
+    ) -> (DedupWorker, DedupScheduler<K, R::Task>)
+    where
+        K: Eq + Hash + Clone + Send + 'static,
+        R: Runnable + 'static,
+    {
+        let name = name.into();
+        let queue = Arc::new(Mutex::new(Queue {
+            order: VecDeque::new(),
+            pending: HashMap::default(),
+        }));
+        let (doorbell_tx, doorbell_rx) = channel::unbounded();
+        let metrics_pending_task_count = WORKER_PENDING_TASK_VEC.with_label_values(&[&name]);
+        let metrics_handled_task_count = WORKER_HANDLED_TASK_VEC.with_label_values(&[&name]);
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let worker_stop = stop.clone();
+        let worker_queue = queue.clone();
+        let pending = metrics_pending_task_count.clone();
+        let handle = thread::Builder::new()
+            .name(name)
+            .spawn(move || {
+                run(
+                    &mut runner,
+                    &worker_queue,
+                    &doorbell_rx,
+                    &worker_stop,
+                    &pending,
+                    &metrics_handled_task_count,
+                );
+                runner.shutdown();
+            })
+            .unwrap();
+
+        (
+            DedupWorker {
+                stop,
+                handle: Some(handle),
+            },
+            DedupScheduler {
+                queue,
+                doorbell: doorbell_tx,
+                metrics_pending_task_count: pending,
+            },
+        )
+    }
+
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for DedupWorker {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn run<K: Eq + Hash, R: Runnable>(
+    runner: &mut R,
+    queue: &Mutex<Queue<K, R::Task>>,
+    doorbell: &channel::Receiver<()>,
+    stop: &AtomicBool,
+    metrics_pending_task_count: &IntGauge,
+    metrics_handled_task_count: &IntCounter,
+) {
+    while !stop.load(Ordering::Acquire) {
+        let task = queue.lock().unwrap().pop();
+        let Some(task) = task else {
+            let _ = doorbell.recv_timeout(POLL_INTERVAL);
+            continue;
+        };
+        runner.run(task);
+        metrics_pending_task_count.dec();
+        metrics_handled_task_count.inc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    struct EchoRunner {
+        ch: mpsc::Sender<&'static str>,
+    }
+
+    impl Runnable for EchoRunner {
+        type Task = &'static str;
+
+        fn run(&mut self, task: &'static str) {
+            self.ch.send(task).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_dedup_replaces_pending_task() {
+        let (tx, rx) = mpsc::channel();
+        let (mut worker, scheduler) = DedupWorker::start("test-dedup", EchoRunner { ch: tx });
+
+        // Block the worker thread on the first task so the next two
+        // schedules under the same key are guaranteed to still be pending.
+        scheduler.schedule(1, "first");
+        assert_eq!(rx.recv_timeout(Duration::from_secs(3)).unwrap(), "first");
+
+        let replaced = scheduler.schedule(2, "stale");
+        assert!(replaced.is_none());
+        let replaced = scheduler.schedule(2, "fresh");
+        assert_eq!(replaced, Some("stale"));
+
+        assert_eq!(rx.recv_timeout(Duration::from_secs(3)).unwrap(), "fresh");
+
+        worker.stop();
+    }
+}
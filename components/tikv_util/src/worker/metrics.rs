@@ -16,4 +16,39 @@ lazy_static! {
         &["name"]
     )
     .unwrap();
+    pub static ref WORKER_TASK_IN_FLIGHT_VEC: IntGaugeVec = register_int_gauge_vec!(
+        "tikv_worker_task_in_flight",
+        "Current number of queued or running tasks, by worker and task name.",
+        &["name", "task"]
+    )
+    .unwrap();
+    pub static ref WORKER_TASK_WAIT_DURATION_VEC: HistogramVec = register_histogram_vec!(
+        "tikv_worker_task_wait_duration_seconds",
+        "Bucketed histogram of the time a task spends queued before it starts running, by \
+         worker and task name.",
+        &["name", "task"],
+        exponential_buckets(0.0001, 2.0, 20).unwrap()
+    )
+    .unwrap();
+    pub static ref WORKER_TASK_EXEC_DURATION_VEC: HistogramVec = register_histogram_vec!(
+        "tikv_worker_task_exec_duration_seconds",
+        "Bucketed histogram of the time spent running a task, by worker and task name.",
+        &["name", "task"],
+        exponential_buckets(0.0001, 2.0, 20).unwrap()
+    )
+    .unwrap();
+    pub static ref WORKER_TASK_PANIC_VEC: IntCounterVec = register_int_counter_vec!(
+        "tikv_worker_task_panic_total",
+        "Total number of tasks whose `Runnable::run` panicked, by worker and task name.",
+        &["name", "task"]
+    )
+    .unwrap();
+    pub static ref WORKER_UNHEALTHY_VEC: IntGaugeVec = register_int_gauge_vec!(
+        "tikv_worker_unhealthy",
+        "Set to 1 once a worker's runnable has panicked on enough consecutive tasks to hit its \
+         RestartPolicy::max_consecutive_panics, reset to 0 on the next task that runs \
+         successfully, by worker name.",
+        &["name"]
+    )
+    .unwrap();
 }
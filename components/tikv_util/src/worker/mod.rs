@@ -11,14 +11,22 @@
 //!
 //! Briefly speaking, this is a mpsc (multiple-producer-single-consumer) model.
 
+mod cancel;
+mod cron;
+mod dedup;
 mod future;
 mod metrics;
 mod pool;
+mod priority;
 
+pub use cancel::{until_cancelled, CancellationToken};
+pub use cron::{CronScheduler, Schedule};
+pub use dedup::{DedupScheduler, DedupWorker};
 pub use pool::{
-    dummy_scheduler, Builder, LazyWorker, ReceiverWrapper, Runnable, RunnableWithTimer,
-    ScheduleError, Scheduler, Worker,
+    dummy_scheduler, BackoffConfig, Builder, LazyWorker, ReceiverWrapper, RestartPolicy, Runnable,
+    RunnableWithTimer, ScheduleError, Scheduler, Worker,
 };
+pub use priority::{PriorityScheduler, PriorityWorker, TaskPriority};
 
 pub use self::future::{
     dummy_scheduler as dummy_future_scheduler, Runnable as FutureRunnable,
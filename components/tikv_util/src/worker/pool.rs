@@ -2,9 +2,11 @@
 
 // #[PerformanceCriticalPath]
 use std::{
+    any::Any,
     error::Error,
     fmt::{self, Debug, Display, Formatter},
     future::Future,
+    panic::{self, AssertUnwindSafe},
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
@@ -61,6 +63,50 @@ impl<T> Debug for ScheduleError<T> {
     }
 }
 
+/// Tuning knobs for how a `Worker` reacts to a `Runnable::run` panicking.
+///
+/// This is opt-in via `Builder::restart_policy`: a `Builder` defaults to
+/// catching nothing, so a panic unwinds the worker thread exactly as it
+/// would anywhere else in TiKV (see `panic_hook`, which documents that in
+/// production any panic is meant to be fatal). Only a worker that explicitly
+/// requests a `RestartPolicy` gets its tasks run under `catch_unwind`, so one
+/// bad task can't silently kill it and stop it from processing anything
+/// after. These knobs then control how a *run* of consecutive panics is
+/// handled: each one is followed by a backoff sleep before the next task is
+/// picked up, and once `max_consecutive_panics` happen in a row without a
+/// successful task in between, the worker is reported unhealthy via
+/// `WORKER_UNHEALTHY_VEC` (and keeps running — there's no separate
+/// "runnable" to restart, so this is a health signal for operators rather
+/// than a recovery action).
+#[derive(Clone, Copy, Debug)]
+pub struct RestartPolicy {
+    pub max_consecutive_panics: u32,
+    pub backoff: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        RestartPolicy {
+            max_consecutive_panics: 5,
+            backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+/// How often `Scheduler::stop_with_deadline` re-checks whether the queue has
+/// drained.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.as_str()
+    } else {
+        "unknown panic payload"
+    }
+}
+
 pub trait Runnable: Send {
     type Task: Display + Send + 'static;
 
@@ -88,10 +134,26 @@ impl<R: Runnable + 'static> Drop for RunnableWrapper<R> {
 }
 
 enum Msg<T: Display + Send> {
-    Task(T),
+    Task(T, Instant),
     Timeout,
 }
 
+/// Derives a bounded-cardinality metric label from a task's `Display` output.
+///
+/// Task `Display` impls in this codebase conventionally lead with the task's
+/// variant/struct name before any field values (most go through
+/// `Formatter::debug_struct`/`debug_tuple`), so cutting the string at the
+/// first `(`, `{`, or whitespace recovers just that name. The length cap is a
+/// safety net for `Display` impls that don't follow the convention, so a
+/// single stray task type can't blow up label cardinality.
+fn task_metric_tag<T: Display>(task: &T) -> String {
+    let rendered = task.to_string();
+    let end = rendered
+        .find(|c: char| c == '(' || c == '{' || c.is_whitespace())
+        .unwrap_or(rendered.len());
+    rendered[..end].chars().take(64).collect()
+}
+
 // A wrapper of Runnable that implements RunnableWithTimer with no timeout.
 struct NoTimeoutRunnableWrapper<T: Runnable>(T);
 
@@ -121,6 +183,7 @@ pub struct Scheduler<T: Display + Send> {
     sender: UnboundedSender<Msg<T>>,
     pending_capacity: usize,
     metrics_pending_task_count: IntGauge,
+    name: Arc<str>,
 }
 
 impl<T: Display + Send> Scheduler<T> {
@@ -129,12 +192,14 @@ impl<T: Display + Send> Scheduler<T> {
         counter: Arc<AtomicUsize>,
         pending_capacity: usize,
         metrics_pending_task_count: IntGauge,
+        name: Arc<str>,
     ) -> Scheduler<T> {
         Scheduler {
             counter,
             sender,
             pending_capacity,
             metrics_pending_task_count,
+            name,
         }
     }
 
@@ -157,10 +222,17 @@ impl<T: Display + Send> Scheduler<T> {
     pub fn schedule_force(&self, task: T) -> Result<(), ScheduleError<T>> {
         self.counter.fetch_add(1, Ordering::SeqCst);
         self.metrics_pending_task_count.inc();
-        if let Err(e) = self.sender.unbounded_send(Msg::Task(task)) {
-            if let Msg::Task(t) = e.into_inner() {
+        let task_tag = task_metric_tag(&task);
+        WORKER_TASK_IN_FLIGHT_VEC
+            .with_label_values(&[&self.name, &task_tag])
+            .inc();
+        if let Err(e) = self.sender.unbounded_send(Msg::Task(task, Instant::now())) {
+            if let Msg::Task(t, _) = e.into_inner() {
                 self.counter.fetch_sub(1, Ordering::SeqCst);
                 self.metrics_pending_task_count.dec();
+                WORKER_TASK_IN_FLIGHT_VEC
+                    .with_label_values(&[&self.name, &task_tag])
+                    .dec();
                 return Err(ScheduleError::Stopped(t));
             }
         }
@@ -176,9 +248,77 @@ impl<T: Display + Send> Scheduler<T> {
         self.sender.close_channel();
     }
 
+    /// Stops accepting new tasks, then blocks the calling thread for up to
+    /// `deadline` waiting for already-queued and in-flight tasks to finish
+    /// running, instead of abandoning them the way a bare `Worker::stop`
+    /// does. Returns whether the queue fully drained within the deadline.
+    ///
+    /// This only closes the channel; the caller is still responsible for
+    /// stopping the underlying `Worker` (or dropping it) afterwards to
+    /// actually tear down its thread pool.
+    pub fn stop_with_deadline(&self, deadline: Duration) -> bool {
+        self.stop();
+        let start = Instant::now();
+        while self.pending_tasks() > 0 {
+            if start.elapsed() >= deadline {
+                return false;
+            }
+            std::thread::sleep(DRAIN_POLL_INTERVAL);
+        }
+        true
+    }
+
     pub fn pending_tasks(&self) -> usize {
         self.counter.load(Ordering::Acquire)
     }
+
+    /// Schedules a task, retrying with exponential backoff while the queue is
+    /// full instead of either bypassing `pending_capacity` like
+    /// `schedule_force` does, or giving up on the very first `Full`.
+    ///
+    /// Blocks the calling thread between retries, so this is meant for
+    /// background-thread callers that would rather wait a bit for room to
+    /// free up than either drop the task or risk unbounded queue growth.
+    /// Returns `Err(ScheduleError::Full(task))` if the queue is still full
+    /// after `backoff.max_retries` attempts, or `Err(ScheduleError::Stopped)`
+    /// immediately once the worker is gone.
+    pub fn schedule_with_backoff(
+        &self,
+        mut task: T,
+        backoff: BackoffConfig,
+    ) -> Result<(), ScheduleError<T>> {
+        let mut wait = backoff.initial_backoff;
+        for _ in 0..backoff.max_retries {
+            match self.schedule(task) {
+                Ok(()) => return Ok(()),
+                Err(ScheduleError::Full(t)) => {
+                    task = t;
+                    std::thread::sleep(wait);
+                    wait = std::cmp::min(wait * 2, backoff.max_backoff);
+                }
+                Err(e @ ScheduleError::Stopped(_)) => return Err(e),
+            }
+        }
+        self.schedule(task)
+    }
+}
+
+/// Tuning knobs for `Scheduler::schedule_with_backoff`.
+#[derive(Clone, Copy, Debug)]
+pub struct BackoffConfig {
+    pub max_retries: usize,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        BackoffConfig {
+            max_retries: 20,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(500),
+        }
+    }
 }
 
 impl<T: Display + Send> Clone for Scheduler<T> {
@@ -188,6 +328,7 @@ impl<T: Display + Send> Clone for Scheduler<T> {
             sender: self.sender.clone(),
             pending_capacity: self.pending_capacity,
             metrics_pending_task_count: self.metrics_pending_task_count.clone(),
+            name: self.name.clone(),
         }
     }
 }
@@ -223,6 +364,8 @@ impl<T: Display + Send + 'static> LazyWorker<T> {
                 receiver,
                 self.metrics_pending_task_count.clone(),
                 self.metrics_handled_task_count.clone(),
+                self.scheduler.name.clone(),
+                self.worker.restart_policy,
             );
             return true;
         }
@@ -242,6 +385,15 @@ impl<T: Display + Send + 'static> LazyWorker<T> {
         self.worker.stop()
     }
 
+    /// Like `stop_worker`, but waits up to `deadline` for queued and
+    /// in-flight tasks to finish before forcibly stopping the underlying
+    /// `Worker`. Returns whether everything drained within the deadline.
+    pub fn stop_worker_with_deadline(self, deadline: Duration) -> bool {
+        let drained = self.scheduler.stop_with_deadline(deadline);
+        self.worker.stop();
+        drained
+    }
+
     pub fn remote(&self) -> Remote<yatp::task::future::TaskCell> {
         self.worker.remote()
     }
@@ -263,7 +415,7 @@ impl<T: Display + Send> ReceiverWrapper<T> {
     pub fn recv(&mut self) -> Option<T> {
         let msg = block_on(self.inner.next());
         match msg {
-            Some(Msg::Task(t)) => Some(t),
+            Some(Msg::Task(t, _)) => Some(t),
             _ => None,
         }
     }
@@ -274,7 +426,7 @@ impl<T: Display + Send> ReceiverWrapper<T> {
     ) -> Result<Option<T>, std::sync::mpsc::RecvTimeoutError> {
         let msg = block_on_timeout(self.inner.next(), timeout)
             .map_err(|_| std::sync::mpsc::RecvTimeoutError::Timeout)?;
-        if let Some(Msg::Task(t)) = msg {
+        if let Some(Msg::Task(t, _)) = msg {
             return Ok(Some(t));
         }
         Ok(None)
@@ -292,6 +444,7 @@ pub fn dummy_scheduler<T: Display + Send>() -> (Scheduler<T>, ReceiverWrapper<T>
             Arc::new(AtomicUsize::new(0)),
             1000,
             WORKER_PENDING_TASK_VEC.with_label_values(&["dummy"]),
+            Arc::from("dummy"),
         ),
         ReceiverWrapper { inner: rx },
     )
@@ -302,6 +455,7 @@ pub struct Builder<S: Into<String>> {
     name: S,
     thread_count: usize,
     pending_capacity: usize,
+    restart_policy: Option<RestartPolicy>,
 }
 
 impl<S: Into<String>> Builder<S> {
@@ -310,6 +464,7 @@ impl<S: Into<String>> Builder<S> {
             name,
             thread_count: 1,
             pending_capacity: usize::MAX,
+            restart_policy: None,
         }
     }
 
@@ -326,6 +481,17 @@ impl<S: Into<String>> Builder<S> {
         self
     }
 
+    /// Opts this worker into catching a panicking task under `catch_unwind`
+    /// and applying `restart_policy` to it, instead of the default of letting
+    /// the panic unwind the worker thread like anywhere else in TiKV. Only
+    /// request this for a worker that's specifically designed to tolerate a
+    /// task panicking without the rest of the process going down with it.
+    #[must_use]
+    pub fn restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = Some(restart_policy);
+        self
+    }
+
     pub fn create(self) -> Worker {
         let pool = YatpPoolBuilder::new(DefaultTicker::default())
             .name_prefix(self.name)
@@ -337,6 +503,7 @@ impl<S: Into<String>> Builder<S> {
             counter: Arc::new(AtomicUsize::new(0)),
             pending_capacity: self.pending_capacity,
             thread_count: self.thread_count,
+            restart_policy: self.restart_policy,
         }
     }
 }
@@ -349,6 +516,7 @@ pub struct Worker {
     counter: Arc<AtomicUsize>,
     stop: Arc<AtomicBool>,
     thread_count: usize,
+    restart_policy: Option<RestartPolicy>,
 }
 
 impl Worker {
@@ -372,6 +540,7 @@ impl Worker {
     ) -> Scheduler<R::Task> {
         let (tx, rx) = unbounded();
         let name = name.into();
+        let name: Arc<str> = Arc::from(name);
         let metrics_pending_task_count = WORKER_PENDING_TASK_VEC.with_label_values(&[&name]);
         let metrics_handled_task_count = WORKER_HANDLED_TASK_VEC.with_label_values(&[&name]);
         self.start_with_timer_impl(
@@ -380,12 +549,15 @@ impl Worker {
             rx,
             metrics_pending_task_count.clone(),
             metrics_handled_task_count,
+            name.clone(),
+            self.restart_policy,
         );
         Scheduler::new(
             tx,
             self.counter.clone(),
             self.pending_capacity,
             metrics_pending_task_count,
+            name,
         )
     }
 
@@ -455,6 +627,7 @@ impl Worker {
     ) -> LazyWorker<T> {
         let (tx, rx) = unbounded();
         let name = name.into();
+        let name: Arc<str> = Arc::from(name);
         let metrics_pending_task_count = WORKER_PENDING_TASK_VEC.with_label_values(&[&name]);
         let metrics_handled_task_count = WORKER_HANDLED_TASK_VEC.with_label_values(&[&name]);
         LazyWorker {
@@ -465,6 +638,7 @@ impl Worker {
                 self.counter.clone(),
                 self.pending_capacity,
                 metrics_pending_task_count.clone(),
+                name,
             ),
             metrics_pending_task_count,
             metrics_handled_task_count,
@@ -502,6 +676,8 @@ impl Worker {
         mut receiver: UnboundedReceiver<Msg<R::Task>>,
         metrics_pending_task_count: IntGauge,
         metrics_handled_task_count: IntCounter,
+        name: Arc<str>,
+        restart_policy: Option<RestartPolicy>,
     ) where
         R: RunnableWithTimer + 'static,
     {
@@ -511,13 +687,76 @@ impl Worker {
         Self::delay_notify(tx.clone(), timeout);
         let _ = self.pool.spawn(async move {
             let mut handle = RunnableWrapper { inner: runner };
+            let unhealthy = WORKER_UNHEALTHY_VEC.with_label_values(&[&name]);
+            let mut consecutive_panics = 0u32;
             while let Some(msg) = receiver.next().await {
                 match msg {
-                    Msg::Task(task) => {
-                        handle.inner.run(task);
+                    Msg::Task(task, enqueued_at) => {
+                        let task_tag = task_metric_tag(&task);
+                        WORKER_TASK_WAIT_DURATION_VEC
+                            .with_label_values(&[&name, &task_tag])
+                            .observe(enqueued_at.elapsed().as_secs_f64());
+                        let start = Instant::now();
+                        // No `restart_policy` means this worker didn't opt in to catching a
+                        // panicking task, so let it unwind the worker thread like anywhere
+                        // else in TiKV instead of silently swallowing it.
+                        let Some(restart_policy) = restart_policy else {
+                            handle.inner.run(task);
+                            WORKER_TASK_EXEC_DURATION_VEC
+                                .with_label_values(&[&name, &task_tag])
+                                .observe(start.elapsed().as_secs_f64());
+                            WORKER_TASK_IN_FLIGHT_VEC
+                                .with_label_values(&[&name, &task_tag])
+                                .dec();
+                            counter.fetch_sub(1, Ordering::SeqCst);
+                            metrics_pending_task_count.dec();
+                            metrics_handled_task_count.inc();
+                            continue;
+                        };
+                        let result =
+                            panic::catch_unwind(AssertUnwindSafe(|| handle.inner.run(task)));
+                        WORKER_TASK_EXEC_DURATION_VEC
+                            .with_label_values(&[&name, &task_tag])
+                            .observe(start.elapsed().as_secs_f64());
+                        WORKER_TASK_IN_FLIGHT_VEC
+                            .with_label_values(&[&name, &task_tag])
+                            .dec();
                         counter.fetch_sub(1, Ordering::SeqCst);
                         metrics_pending_task_count.dec();
                         metrics_handled_task_count.inc();
+                        match result {
+                            Ok(()) => {
+                                consecutive_panics = 0;
+                                unhealthy.set(0);
+                            }
+                            Err(panic) => {
+                                WORKER_TASK_PANIC_VEC
+                                    .with_label_values(&[&name, &task_tag])
+                                    .inc();
+                                consecutive_panics += 1;
+                                error!(
+                                    "worker task panicked";
+                                    "worker" => &*name,
+                                    "task" => %task_tag,
+                                    "consecutive_panics" => consecutive_panics,
+                                    "panic" => panic_message(&*panic),
+                                );
+                                if consecutive_panics >= restart_policy.max_consecutive_panics {
+                                    unhealthy.set(1);
+                                    error!(
+                                        "worker marked unhealthy after repeated panics";
+                                        "worker" => &*name,
+                                        "consecutive_panics" => consecutive_panics,
+                                    );
+                                } else {
+                                    let backoff = restart_policy.backoff * consecutive_panics;
+                                    let _ = GLOBAL_TIMER_HANDLE
+                                        .delay(std::time::Instant::now() + backoff)
+                                        .compat()
+                                        .await;
+                                }
+                            }
+                        }
                     }
                     Msg::Timeout => {
                         handle.inner.on_timeout();
@@ -609,4 +848,41 @@ mod tests {
         // Handled task must be 3.
         assert_eq!(3, worker.metrics_handled_task_count.get());
     }
+
+    struct PanicOnOddRunner {
+        handled: Arc<AtomicU64>,
+    }
+
+    impl Runnable for PanicOnOddRunner {
+        type Task = u64;
+
+        fn run(&mut self, task: u64) {
+            if task % 2 == 1 {
+                panic!("odd task");
+            }
+            self.handled.fetch_add(1, atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_worker_with_restart_policy_survives_panicking_task() {
+        let worker = Builder::new("test_worker_with_restart_policy")
+            .restart_policy(RestartPolicy {
+                max_consecutive_panics: 5,
+                backoff: Duration::from_millis(1),
+            })
+            .create();
+        let handled = Arc::new(AtomicU64::new(0));
+        let scheduler = worker.start(
+            "test_worker_with_restart_policy",
+            PanicOnOddRunner {
+                handled: handled.clone(),
+            },
+        );
+        scheduler.schedule(1).unwrap();
+        scheduler.schedule(2).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(1, handled.load(atomic::Ordering::SeqCst));
+        worker.stop();
+    }
 }
@@ -0,0 +1,187 @@
+// Copyright 2024 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! A `Worker`/`Scheduler` variant that lets callers mark individual tasks as
+//! `Urgent`, so they are served ahead of ordinary `Normal` work, while still
+//! guaranteeing `Normal` tasks make progress under a steady stream of urgent
+//! work.
+//!
+//! This runs on a single dedicated thread rather than the shared yatp pool
+//! used by `Worker`, since the in-memory engine's eviction-vs-region-load
+//! use case needs exactly one extra lane of urgency, not a general-purpose
+//! thread pool.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crossbeam::channel::{self, Sender};
+use prometheus::{IntCounter, IntGauge};
+
+use super::{
+    metrics::{WORKER_HANDLED_TASK_VEC, WORKER_PENDING_TASK_VEC},
+    pool::Runnable,
+};
+
+/// Priority a task is submitted with. `Urgent` tasks are served ahead of
+/// `Normal` ones, subject to the starvation protection documented on
+/// `PriorityWorker::start`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TaskPriority {
+    Urgent,
+    Normal,
+}
+
+/// After this many `Urgent` tasks have been served back-to-back, the worker
+/// serves one pending `Normal` task first (if any), even if more urgent work
+/// is waiting, so a steady stream of urgent tasks can't starve bulk work
+/// indefinitely.
+const MAX_CONSECUTIVE_URGENT: u32 = 16;
+
+/// How long the worker thread blocks waiting for a task before re-checking
+/// the stop flag.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+pub struct PriorityScheduler<T: Send> {
+    urgent_tx: Sender<T>,
+    normal_tx: Sender<T>,
+    metrics_pending_task_count: IntGauge,
+}
+
+impl<T: Send> PriorityScheduler<T> {
+    pub fn schedule(&self, task: T, priority: TaskPriority) -> Result<(), T> {
+        self.metrics_pending_task_count.inc();
+        let res = match priority {
+            TaskPriority::Urgent => self.urgent_tx.send(task),
+            TaskPriority::Normal => self.normal_tx.send(task),
+        };
+        res.map_err(|e| {
+            self.metrics_pending_task_count.dec();
+            e.into_inner()
+        })
+    }
+
+    pub fn schedule_urgent(&self, task: T) -> Result<(), T> {
+        self.schedule(task, TaskPriority::Urgent)
+    }
+}
+
+impl<T: Send> Clone for PriorityScheduler<T> {
+    fn clone(&self) -> Self {
+        PriorityScheduler {
+            urgent_tx: self.urgent_tx.clone(),
+            normal_tx: self.normal_tx.clone(),
+            metrics_pending_task_count: self.metrics_pending_task_count.clone(),
+        }
+    }
+}
+
+/// A worker running on its own thread that drains a `PriorityScheduler`'s
+/// urgent lane ahead of its normal lane.
+pub struct PriorityWorker {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl PriorityWorker {
+    pub fn start<R: Runnable + 'static>(
+        name: impl Into<String>,
+        mut runner: R,
+    ) -> (PriorityWorker, PriorityScheduler<R::Task>) {
+        let name = name.into();
+        let (urgent_tx, urgent_rx) = channel::unbounded();
+        let (normal_tx, normal_rx) = channel::unbounded();
+        let metrics_pending_task_count = WORKER_PENDING_TASK_VEC.with_label_values(&[&name]);
+        let metrics_handled_task_count = WORKER_HANDLED_TASK_VEC.with_label_values(&[&name]);
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let worker_stop = stop.clone();
+        let pending = metrics_pending_task_count.clone();
+        let handle = thread::Builder::new()
+            .name(name)
+            .spawn(move || {
+                run(
+                    &mut runner,
+                    &urgent_rx,
+                    &normal_rx,
+                    &worker_stop,
+                    &pending,
+                    &metrics_handled_task_count,
+                );
+                runner.shutdown();
+            })
+            .unwrap();
+
+        (
+            PriorityWorker {
+                stop,
+                handle: Some(handle),
+            },
+            PriorityScheduler {
+                urgent_tx,
+                normal_tx,
+                metrics_pending_task_count: pending,
+            },
+        )
+    }
+
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for PriorityWorker {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn run<R: Runnable>(
+    runner: &mut R,
+    urgent_rx: &channel::Receiver<R::Task>,
+    normal_rx: &channel::Receiver<R::Task>,
+    stop: &AtomicBool,
+    metrics_pending_task_count: &IntGauge,
+    metrics_handled_task_count: &IntCounter,
+) {
+    let mut consecutive_urgent = 0u32;
+    while !stop.load(Ordering::Acquire) {
+        let task = if consecutive_urgent >= MAX_CONSECUTIVE_URGENT {
+            normal_rx.try_recv().ok().map(|t| (t, false))
+        } else {
+            None
+        };
+        let task = task.or_else(|| urgent_rx.try_recv().ok().map(|t| (t, true)));
+        let task = task.or_else(|| normal_rx.try_recv().ok().map(|t| (t, false)));
+
+        let task = match task {
+            Some(t) => Some(t),
+            None => {
+                let mut select = channel::Select::new();
+                let urgent_idx = select.recv(urgent_rx);
+                let normal_idx = select.recv(normal_rx);
+                match select.select_timeout(POLL_INTERVAL) {
+                    Ok(op) if op.index() == urgent_idx => op.recv(urgent_rx).ok().map(|t| (t, true)),
+                    Ok(op) if op.index() == normal_idx => op.recv(normal_rx).ok().map(|t| (t, false)),
+                    _ => None,
+                }
+            }
+        };
+
+        let Some((task, urgent)) = task else {
+            continue;
+        };
+        consecutive_urgent = if urgent { consecutive_urgent + 1 } else { 0 };
+
+        runner.run(task);
+        metrics_pending_task_count.dec();
+        metrics_handled_task_count.inc();
+    }
+}
@@ -250,6 +250,13 @@ impl TitanCfConfig {
         if self.sample_ratio.is_some() {
             warn!("sample-ratio is deprecated. Ignoring the value.");
         }
+        if !self.shared_blob_cache && self.blob_cache_size.0 == 0 {
+            warn!(
+                "shared-blob-cache is disabled but blob-cache-size is 0; blob values will \
+                 not be cached and every blob read will hit the blob file on disk. Set \
+                 blob-cache-size to a nonzero value or leave shared-blob-cache enabled."
+            );
+        }
         Ok(())
     }
 }
@@ -1295,7 +1302,6 @@ pub struct DbConfig {
     pub rate_limiter_auto_tuned: bool,
     pub bytes_per_sync: ReadableSize,
     pub wal_bytes_per_sync: ReadableSize,
-    #[online_config(skip)]
     pub max_sub_compactions: u32,
     pub writable_file_max_buffer_size: ReadableSize,
     #[online_config(skip)]
@@ -2853,6 +2859,12 @@ pub struct BackupConfig {
     // Do not expose this config to user.
     // It used to debug s3 503 error.
     pub s3_multi_part_size: ReadableSize,
+    // Let a backup scan take its snapshot through the range cache engine instead of
+    // always going straight to the disk engine. Only helps when the range cache
+    // engine itself is enabled; it's still the range cache engine's snapshot
+    // machinery that decides, per region, whether the cache actually covers the
+    // backup ts, falling back to disk otherwise.
+    pub enable_range_cache_engine: bool,
     #[online_config(submodule)]
     pub hadoop: HadoopConfig,
 }
@@ -2902,6 +2914,7 @@ impl Default for BackupConfig {
             io_thread_size: 2,
             // 5MB is the minimum part size that S3 allowed.
             s3_multi_part_size: ReadableSize::mb(5),
+            enable_range_cache_engine: false,
             hadoop: Default::default(),
         }
     }
@@ -3962,6 +3975,28 @@ impl TikvConfig {
         self.causal_ts.validate()?;
         self.range_cache_engine.validate()?;
 
+        // The in-memory engine's hard limit is a separate budget on top of
+        // `memory_usage_limit` (its memory isn't backed by page cache the way
+        // block cache is), so make sure the two together still fit in what the
+        // system actually has, rather than only catching the resulting OOM
+        // once the engine starts loading regions hours later.
+        if self.range_cache_engine.enabled {
+            let ime_hard_limit =
+                ReadableSize(self.range_cache_engine.hard_limit_threshold() as u64);
+            let total = SysQuota::memory_limit_in_bytes();
+            if self.memory_usage_limit.unwrap().0 + ime_hard_limit.0 > total {
+                return Err(format!(
+                    "The sum of `memory-usage-limit` and \
+                    `range-cache-engine.hard-limit-threshold` is greater than system memory \
+                    capacity: {} + {} > {}",
+                    self.memory_usage_limit.unwrap(),
+                    ime_hard_limit,
+                    total,
+                )
+                .into());
+            }
+        }
+
         // Validate feature TTL with Titan configuration.
         if matches!(self.rocksdb.titan.enabled, Some(true)) && self.storage.enable_ttl {
             return Err("Titan is unavailable for feature TTL".to_string().into());
@@ -6615,6 +6650,17 @@ mod tests {
         cfg.validate().unwrap();
         assert_eq!(cfg.memory_usage_limit.unwrap(), ReadableSize(system));
 
+        // Test range_cache_engine.hard_limit_threshold is validated against
+        // system memory capacity on top of memory_usage_limit.
+        let mut cfg = TikvConfig::default();
+        cfg.range_cache_engine = RangeCacheEngineConfig::config_for_test();
+        let system = SysQuota::memory_limit_in_bytes();
+        cfg.memory_usage_limit = Some(ReadableSize(system / 2));
+        cfg.range_cache_engine.hard_limit_threshold = Some(ReadableSize(system));
+        cfg.validate().unwrap_err();
+        cfg.range_cache_engine.hard_limit_threshold = Some(ReadableSize(system / 4));
+        cfg.validate().unwrap();
+
         // Test raftstore.enable-partitioned-raft-kv-compatible-learner.
         let mut cfg = TikvConfig::default();
         cfg.raft_store.enable_v2_compatible_learner = true;
@@ -309,6 +309,7 @@ pub fn tls_collect_read_flow(
             end,
             &statistics.write.flow_stats,
             &statistics.data.flow_stats,
+            (statistics.write.total_op_count() + statistics.data.total_op_count()) as u64,
         );
     });
 }
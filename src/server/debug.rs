@@ -14,12 +14,14 @@ use collections::HashSet;
 use engine_rocks::{
     raw::{CompactOptions, DBBottommostLevelCompaction},
     util::get_cf_handle,
-    RocksEngine, RocksEngineIterator, RocksMvccProperties, RocksStatistics, RocksWriteBatchVec,
+    RocksEngine, RocksEngineIterator, RocksMvccProperties, RocksStatistics, RocksSstWriterBuilder,
+    RocksWriteBatchVec,
 };
 use engine_traits::{
-    Engines, Error as EngineTraitError, IterOptions, Iterable, Iterator as EngineIterator, MiscExt,
-    Mutable, MvccProperties, Peekable, RaftEngine, RaftLogBatch, Range, RangePropertiesExt,
-    SyncMutable, WriteBatch, WriteBatchExt, WriteOptions, CF_DEFAULT, CF_LOCK, CF_RAFT, CF_WRITE,
+    Engines, Error as EngineTraitError, ImportExt, IterOptions, Iterable,
+    Iterator as EngineIterator, MiscExt, Mutable, MvccProperties, Peekable, RaftEngine,
+    RaftLogBatch, Range, RangePropertiesExt, SstWriter, SstWriterBuilder, SyncMutable, WriteBatch,
+    WriteBatchExt, WriteOptions, CF_DEFAULT, CF_LOCK, CF_RAFT, CF_WRITE,
 };
 use futures::future::Future;
 use kvproto::{
@@ -31,7 +33,9 @@ use kvproto::{
 use protobuf::Message;
 use raft::{self, eraftpb::Entry, RawNode};
 use raftstore::{
-    coprocessor::get_region_approximate_middle,
+    coprocessor::{
+        get_approximate_split_keys, get_region_approximate_middle, get_region_approximate_size,
+    },
     store::{write_initial_apply_state, write_initial_raft_state, write_peer_state, PeerStorage},
 };
 use thiserror::Error;
@@ -149,6 +153,31 @@ pub trait Debugger {
 
     fn region_size<T: AsRef<str>>(&self, region_id: u64, cfs: Vec<T>) -> Result<Vec<(T, usize)>>;
 
+    /// Compute a crc32 checksum over a region's visible default/lock/write
+    /// cf contents, so the same region on different replicas can be compared
+    /// cheaply without transferring the data itself.
+    fn region_checksum(&self, region_id: u64) -> Result<u32>;
+
+    /// Propose split keys for a region so that each resulting piece is
+    /// approximately `target_size` bytes, based on table properties rather
+    /// than a full scan.
+    fn get_region_approximate_split_keys(
+        &self,
+        region_id: u64,
+        target_size: u64,
+    ) -> Result<Vec<Vec<u8>>>;
+
+    /// Dump a region's visible contents in the given cf to a local SST file,
+    /// so it can be copied elsewhere and loaded back with `load_region_sst`.
+    /// This is a point-in-time scan, not a consistent multi-cf snapshot.
+    fn dump_region_sst(&self, region_id: u64, cf: &str, path: &str) -> Result<()>;
+
+    /// Ingest a previously dumped SST file directly into the given cf,
+    /// bypassing the raft layer. Intended for restoring a region snapshot
+    /// produced by `dump_region_sst` into a node's data directory while it
+    /// is offline.
+    fn load_region_sst(&self, region_id: u64, cf: &str, path: &str) -> Result<()>;
+
     /// Scan MVCC Infos for given range `[start, end)`.
     fn scan_mvcc(
         &self,
@@ -168,6 +197,9 @@ pub trait Debugger {
         bottommost: BottommostLevelCompaction,
     ) -> Result<()>;
 
+    /// Flush the cf in the db, forcing memtable data out to SST files.
+    fn flush(&self, db: DbType, cf: &str, wait: bool) -> Result<()>;
+
     /// Get all regions holding region meta data from raft CF in KV storage.
     fn get_all_regions_in_store(&self) -> Result<Vec<u64>>;
 
@@ -181,6 +213,24 @@ pub trait Debugger {
 
     fn get_region_properties(&self, region_id: u64) -> Result<Vec<(String, String)>>;
 
+    /// Count how many write-cf versions in a region would already be
+    /// obsolete at `safe_point` -- i.e. superseded by a newer, already
+    /// committed write at or before `safe_point` -- versus how many
+    /// versions exist in total. Returns `(total_versions,
+    /// reclaimable_versions)`.
+    ///
+    /// This only reports what GC could reclaim; it doesn't perform GC and
+    /// it has no effect on the GC safe point, which is a single,
+    /// cluster-wide value owned by PD, not something set per region. The
+    /// count is an approximation of what `storage::txn::actions::gc::gc`
+    /// would actually delete: it looks only at how many write-cf records
+    /// exist per key, not at locks, rollbacks, or short values.
+    fn get_region_mvcc_reclaimable_versions(
+        &self,
+        region_id: u64,
+        safe_point: u64,
+    ) -> Result<(u64, u64)>;
+
     fn reset_to_version(&self, version: u64);
 
     fn key_range_flashback_to_version(
@@ -882,6 +932,96 @@ where
         }
     }
 
+    fn region_checksum(&self, region_id: u64) -> Result<u32> {
+        let region_state_key = keys::region_state_key(region_id);
+        let region_state = box_try!(
+            self.engines
+                .kv
+                .get_msg_cf::<RegionLocalState>(CF_RAFT, &region_state_key)
+        )
+        .ok_or_else(|| Error::NotFound(format!("none region {:?}", region_id)))?;
+        let region = region_state.get_region();
+        let start_key = &keys::data_key(region.get_start_key());
+        let end_key = &keys::data_end_key(region.get_end_key());
+        let mut digest = crc32fast::Hasher::new();
+        for cf in &[CF_DEFAULT, CF_LOCK, CF_WRITE] {
+            box_try!(self.engines.kv.scan(cf, start_key, end_key, false, |k, v| {
+                digest.update(k);
+                digest.update(v);
+                Ok(true)
+            }));
+        }
+        Ok(digest.finalize())
+    }
+
+    fn get_region_approximate_split_keys(
+        &self,
+        region_id: u64,
+        target_size: u64,
+    ) -> Result<Vec<Vec<u8>>> {
+        let region_state_key = keys::region_state_key(region_id);
+        let region_state = box_try!(
+            self.engines
+                .kv
+                .get_msg_cf::<RegionLocalState>(CF_RAFT, &region_state_key)
+        )
+        .ok_or_else(|| Error::NotFound(format!("none region {:?}", region_id)))?;
+        let region = region_state.get_region();
+        let region_size = box_try!(get_region_approximate_size(&self.engines.kv, region, 0));
+        if region_size <= target_size {
+            return Ok(vec![]);
+        }
+        let split_key_count = region_size / target_size;
+        Ok(box_try!(get_approximate_split_keys(
+            &self.engines.kv,
+            region,
+            split_key_count
+        )))
+    }
+
+    fn dump_region_sst(&self, region_id: u64, cf: &str, path: &str) -> Result<()> {
+        validate_db_and_cf(DbType::Kv, cf)?;
+        let region_state_key = keys::region_state_key(region_id);
+        let region_state = box_try!(
+            self.engines
+                .kv
+                .get_msg_cf::<RegionLocalState>(CF_RAFT, &region_state_key)
+        )
+        .ok_or_else(|| Error::NotFound(format!("none region {:?}", region_id)))?;
+        let region = region_state.get_region();
+        let start_key = keys::data_key(region.get_start_key());
+        let end_key = keys::data_end_key(region.get_end_key());
+        let mut writer = box_try!(
+            RocksSstWriterBuilder::new()
+                .set_db(&self.engines.kv)
+                .set_cf(cf)
+                .build(path)
+        );
+        box_try!(
+            self.engines
+                .kv
+                .scan(cf, &start_key, &end_key, false, |k, v| {
+                    writer.put(k, v)?;
+                    Ok(true)
+                })
+        );
+        box_try!(writer.finish());
+        Ok(())
+    }
+
+    fn load_region_sst(&self, region_id: u64, cf: &str, path: &str) -> Result<()> {
+        validate_db_and_cf(DbType::Kv, cf)?;
+        let region_state_key = keys::region_state_key(region_id);
+        box_try!(
+            self.engines
+                .kv
+                .get_msg_cf::<RegionLocalState>(CF_RAFT, &region_state_key)
+        )
+        .ok_or_else(|| Error::NotFound(format!("none region {:?}", region_id)))?;
+        box_try!(self.engines.kv.ingest_external_file_cf(cf, &[path]));
+        Ok(())
+    }
+
     fn scan_mvcc(
         &self,
         start: &[u8],
@@ -929,6 +1069,15 @@ where
         Ok(())
     }
 
+    fn flush(&self, db: DbType, cf: &str, wait: bool) -> Result<()> {
+        validate_db_and_cf(db, cf)?;
+        let db = self.get_db_from_type(db)?;
+        info!("Debugger starts manual flush"; "db" => ?db, "cf" => cf);
+        box_try!(db.flush_cfs(&[cf], wait));
+        info!("Debugger finishes manual flush"; "db" => ?db, "cf" => cf);
+        Ok(())
+    }
+
     fn get_all_regions_in_store(&self) -> Result<Vec<u64>> {
         let db = &self.engines.kv;
         let cf = CF_RAFT;
@@ -1012,6 +1161,58 @@ where
         Ok(res)
     }
 
+    fn get_region_mvcc_reclaimable_versions(
+        &self,
+        region_id: u64,
+        safe_point: u64,
+    ) -> Result<(u64, u64)> {
+        let region_state_key = keys::region_state_key(region_id);
+        let region_state = box_try!(
+            self.engines
+                .kv
+                .get_msg_cf::<RegionLocalState>(CF_RAFT, &region_state_key)
+        )
+        .ok_or_else(|| Error::NotFound(format!("none region {:?}", region_id)))?;
+        let region = region_state.get_region();
+        let start_key = keys::data_key(region.get_start_key());
+        let end_key = keys::data_end_key(region.get_end_key());
+
+        let safe_point = TimeStamp::from(safe_point);
+        let mut total_versions = 0u64;
+        let mut reclaimable_versions = 0u64;
+        let mut cur_user_key: Option<Vec<u8>> = None;
+        let mut kept_visible_version = false;
+        box_try!(self.engines.kv.scan(
+            CF_WRITE,
+            &start_key,
+            &end_key,
+            false,
+            |k, _| {
+                total_versions += 1;
+                let origin_key = keys::origin_key(k);
+                let user_key = box_try!(Key::truncate_ts_for(origin_key)).to_vec();
+                if cur_user_key.as_deref() != Some(user_key.as_slice()) {
+                    cur_user_key = Some(user_key);
+                    kept_visible_version = false;
+                }
+                // Within a single user key, write-cf records are ordered by
+                // decreasing commit ts, so the first one we see at or before
+                // `safe_point` is the version still visible at `safe_point`;
+                // any further ones are reclaimable.
+                let commit_ts = box_try!(Key::decode_ts_from(origin_key));
+                if commit_ts <= safe_point {
+                    if kept_visible_version {
+                        reclaimable_versions += 1;
+                    } else {
+                        kept_visible_version = true;
+                    }
+                }
+                Ok(true)
+            }
+        ));
+        Ok((total_versions, reclaimable_versions))
+    }
+
     fn reset_to_version(&self, version: u64) {
         self.reset_to_version_manager.start(version.into());
     }
@@ -3,11 +3,12 @@
 use std::{sync::Arc, thread::JoinHandle};
 
 use engine_rocks::{
-    raw::CompactOptions, util::get_cf_handle, RocksEngine, RocksEngineIterator, RocksStatistics,
+    raw::CompactOptions, util::get_cf_handle, RocksEngine, RocksEngineIterator,
+    RocksSstWriterBuilder, RocksStatistics,
 };
 use engine_traits::{
-    CachedTablet, Iterable, MiscExt, Peekable, RaftEngine, RaftLogBatch, TabletContext,
-    TabletRegistry, CF_DEFAULT, CF_LOCK, CF_RAFT, CF_WRITE,
+    CachedTablet, ImportExt, Iterable, MiscExt, Peekable, RaftEngine, RaftLogBatch, SstWriter,
+    SstWriterBuilder, TabletContext, TabletRegistry, CF_DEFAULT, CF_LOCK, CF_RAFT, CF_WRITE,
 };
 use keys::{data_key, enc_end_key, enc_start_key, DATA_MAX_KEY, DATA_PREFIX_KEY};
 use kvproto::{
@@ -19,7 +20,9 @@ use kvproto::{
 use nom::AsBytes;
 use raft::{prelude::Entry, RawNode};
 use raftstore::{
-    coprocessor::{get_region_approximate_middle, get_region_approximate_size},
+    coprocessor::{
+        get_approximate_split_keys, get_region_approximate_middle, get_region_approximate_size,
+    },
     store::util::check_key_in_region,
 };
 use raftstore_v2::Storage;
@@ -27,6 +30,7 @@ use slog::o;
 use tikv_util::{
     config::ReadableSize, store::find_peer, sys::thread::StdThreadBuildWrapper, worker::Worker,
 };
+use txn_types::{Key, TimeStamp};
 
 use super::debug::{recover_mvcc_for_range, BottommostLevelCompaction, Debugger, RegionInfo};
 use crate::{
@@ -715,6 +719,112 @@ impl<ER: RaftEngine> Debugger for DebuggerImplV2<ER> {
         }
     }
 
+    fn region_checksum(&self, region_id: u64) -> Result<u32> {
+        let region_state = match self.raft_engine.get_region_state(region_id, u64::MAX) {
+            Ok(Some(region_state)) => region_state,
+            Ok(None) => return Err(Error::NotFound(format!("none region {:?}", region_id))),
+            Err(e) => return Err(box_err!(e)),
+        };
+        let region = region_state.get_region().clone();
+        let start_key = &keys::data_key(region.get_start_key());
+        let end_key = &keys::data_end_key(region.get_end_key());
+        let mut tablet_cache = get_tablet_cache(&self.tablet_reg, region.id, Some(region_state))?;
+        let Some(tablet) = tablet_cache.latest() else {
+            return Err(Error::NotFound(format!(
+                "tablet not found, region_id={:?}",
+                region_id
+            )));
+        };
+        let mut digest = crc32fast::Hasher::new();
+        for cf in &[CF_DEFAULT, CF_LOCK, CF_WRITE] {
+            box_try!(tablet.scan(cf, start_key, end_key, false, |k, v| {
+                digest.update(k);
+                digest.update(v);
+                Ok(true)
+            }));
+        }
+        Ok(digest.finalize())
+    }
+
+    fn get_region_approximate_split_keys(
+        &self,
+        region_id: u64,
+        target_size: u64,
+    ) -> Result<Vec<Vec<u8>>> {
+        let region_state = match self.raft_engine.get_region_state(region_id, u64::MAX) {
+            Ok(Some(region_state)) => region_state,
+            Ok(None) => return Err(Error::NotFound(format!("none region {:?}", region_id))),
+            Err(e) => return Err(box_err!(e)),
+        };
+        let region = region_state.get_region().clone();
+        let mut tablet_cache = get_tablet_cache(&self.tablet_reg, region.id, Some(region_state))?;
+        let Some(tablet) = tablet_cache.latest() else {
+            return Err(Error::NotFound(format!(
+                "tablet not found, region_id={:?}",
+                region_id
+            )));
+        };
+        let region_size = box_try!(get_region_approximate_size(tablet, &region, 0));
+        if region_size <= target_size {
+            return Ok(vec![]);
+        }
+        let split_key_count = region_size / target_size;
+        Ok(box_try!(get_approximate_split_keys(
+            tablet,
+            &region,
+            split_key_count
+        )))
+    }
+
+    fn dump_region_sst(&self, region_id: u64, cf: &str, path: &str) -> Result<()> {
+        validate_db_and_cf(DbType::Kv, cf)?;
+        let region_state = match self.raft_engine.get_region_state(region_id, u64::MAX) {
+            Ok(Some(region_state)) => region_state,
+            Ok(None) => return Err(Error::NotFound(format!("none region {:?}", region_id))),
+            Err(e) => return Err(box_err!(e)),
+        };
+        let region = region_state.get_region().clone();
+        let start_key = keys::data_key(region.get_start_key());
+        let end_key = keys::data_end_key(region.get_end_key());
+        let mut tablet_cache = get_tablet_cache(&self.tablet_reg, region.id, Some(region_state))?;
+        let Some(tablet) = tablet_cache.latest() else {
+            return Err(Error::NotFound(format!(
+                "tablet not found, region_id={:?}",
+                region_id
+            )));
+        };
+        let mut writer = box_try!(
+            RocksSstWriterBuilder::new()
+                .set_db(tablet)
+                .set_cf(cf)
+                .build(path)
+        );
+        box_try!(tablet.scan(cf, &start_key, &end_key, false, |k, v| {
+            writer.put(k, v)?;
+            Ok(true)
+        }));
+        box_try!(writer.finish());
+        Ok(())
+    }
+
+    fn load_region_sst(&self, region_id: u64, cf: &str, path: &str) -> Result<()> {
+        validate_db_and_cf(DbType::Kv, cf)?;
+        let region_state = match self.raft_engine.get_region_state(region_id, u64::MAX) {
+            Ok(Some(region_state)) => region_state,
+            Ok(None) => return Err(Error::NotFound(format!("none region {:?}", region_id))),
+            Err(e) => return Err(box_err!(e)),
+        };
+        let mut tablet_cache = get_tablet_cache(&self.tablet_reg, region_id, Some(region_state))?;
+        let Some(tablet) = tablet_cache.latest() else {
+            return Err(Error::NotFound(format!(
+                "tablet not found, region_id={:?}",
+                region_id
+            )));
+        };
+        box_try!(tablet.ingest_external_file_cf(cf, &[path]));
+        Ok(())
+    }
+
     fn scan_mvcc(
         &self,
         start: &[u8],
@@ -783,6 +893,24 @@ impl<ER: RaftEngine> Debugger for DebuggerImplV2<ER> {
         Ok(())
     }
 
+    fn flush(&self, db: DbType, cf: &str, wait: bool) -> Result<()> {
+        validate_db_and_cf(db, cf)?;
+        if db == DbType::Raft {
+            return Err(box_err!("Get raft db is not allowed"));
+        }
+        let flushes = find_region_states_by_key_range(&self.raft_engine, &[], &[]);
+        for (region_id, _, _, region_state) in flushes {
+            let mut tablet_cache =
+                get_tablet_cache(&self.tablet_reg, region_id, Some(region_state))?;
+            let tablet = tablet_cache.latest().unwrap();
+            info!("Debugger starts manual flush"; "tablet" => ?tablet, "cf" => cf);
+            box_try!(tablet.flush_cfs(&[cf], wait));
+            info!("Debugger finishes manual flush"; "tablet" => ?tablet, "cf" => cf);
+        }
+
+        Ok(())
+    }
+
     fn get_all_regions_in_store(&self) -> Result<Vec<u64>> {
         let mut region_ids = vec![];
         let raft_engine = &self.raft_engine;
@@ -896,6 +1024,66 @@ impl<ER: RaftEngine> Debugger for DebuggerImplV2<ER> {
         Ok(res)
     }
 
+    fn get_region_mvcc_reclaimable_versions(
+        &self,
+        region_id: u64,
+        safe_point: u64,
+    ) -> Result<(u64, u64)> {
+        let region_state = match self.raft_engine.get_region_state(region_id, u64::MAX) {
+            Ok(Some(region_state)) => region_state,
+            Ok(None) => return Err(Error::NotFound(format!("none region {:?}", region_id))),
+            Err(e) => return Err(Error::EngineTrait(e)),
+        };
+
+        let state = region_state.get_state();
+        if state == PeerState::Tombstone {
+            return Err(Error::NotFound(format!(
+                "region {:?} is tombstone",
+                region_id
+            )));
+        }
+        let region = region_state.get_region().clone();
+        let start_key = keys::enc_start_key(&region);
+        let end_key = keys::enc_end_key(&region);
+
+        let mut tablet_cache = get_tablet_cache(&self.tablet_reg, region.id, Some(region_state))?;
+        let Some(tablet) = tablet_cache.latest() else {
+            return Err(Error::NotFound(format!(
+                "tablet not found, region_id={:?}, peer_state={:?}",
+                region_id, state
+            )));
+        };
+
+        let safe_point = TimeStamp::from(safe_point);
+        let mut total_versions = 0u64;
+        let mut reclaimable_versions = 0u64;
+        let mut cur_user_key: Option<Vec<u8>> = None;
+        let mut kept_visible_version = false;
+        box_try!(tablet.scan(CF_WRITE, &start_key, &end_key, false, |k, _| {
+            total_versions += 1;
+            let origin_key = keys::origin_key(k);
+            let user_key = box_try!(Key::truncate_ts_for(origin_key)).to_vec();
+            if cur_user_key.as_deref() != Some(user_key.as_slice()) {
+                cur_user_key = Some(user_key);
+                kept_visible_version = false;
+            }
+            // Within a single user key, write-cf records are ordered by
+            // decreasing commit ts, so the first one we see at or before
+            // `safe_point` is the version still visible at `safe_point`;
+            // any further ones are reclaimable.
+            let commit_ts = box_try!(Key::decode_ts_from(origin_key));
+            if commit_ts <= safe_point {
+                if kept_visible_version {
+                    reclaimable_versions += 1;
+                } else {
+                    kept_visible_version = true;
+                }
+            }
+            Ok(true)
+        }));
+        Ok((total_versions, reclaimable_versions))
+    }
+
     fn reset_to_version(&self, _version: u64) {
         unimplemented!()
     }
@@ -740,6 +740,11 @@ impl<E: Engine> GcRunnerCore<E> {
 
         // We are in single-rocksdb version if we can get a local_storage, otherwise, we
         // are in multi-rocksdb version.
+        //
+        // When the range cache engine is enabled, `local_storage` is the hybrid engine, so
+        // each `delete_ranges_cf` call below already evicts the overlapping cached regions
+        // (see `HybridEngine`'s `MiscExt` impl) before the disk-side delete runs, keeping the
+        // cache from serving keys this range is about to drop.
         if let Some(local_storage) = self.engine.kv_engine() {
             // Convert keys to RocksDB layer form
             // TODO: Logic coupled with raftstore's implementation. Maybe better design is
@@ -889,6 +894,9 @@ impl<E: Engine> GcRunnerCore<E> {
         let ctx = init_snap_ctx(store_id, region);
         let snap_ctx = SnapContext {
             pb_ctx: &ctx,
+            // GC needs to see versions below the safe point, which the range cache
+            // engine does not retain, so always read from disk here.
+            force_disk_read: true,
             ..Default::default()
         };
 
@@ -654,6 +654,7 @@ where
                 epoch_version: 0,
                 read_ts: ts.into_inner(),
                 range: None,
+                force_disk_read: ctx.force_disk_read,
             })
         } else {
             None
@@ -715,6 +715,19 @@ where
             self.handle_response(ctx, sink, f, "debug_get_region_read_progress");
         }
     }
+
+    // TODO: a cache-control RPC surface (load range, evict range, list cached
+    // regions with state) belongs here, delegating to
+    // `RangeCacheMemoryEngine::{load_region, evict_region, region_cache_stats}`
+    // (see `range_cache_memory_engine::engine`) the same way the methods
+    // above delegate to `Debugger`/`StoreRegionMeta` -- the engine-side
+    // capability already exists, so this would just be new handler methods
+    // here. What's missing is the RPC surface itself: every method on this
+    // trait corresponds to a `Debug` service RPC defined in the vendored
+    // kvproto's debugpb.proto, and it has no `LoadRange`/`EvictRange`/
+    // `ListCachedRegions`-shaped request/response messages or RPC to
+    // implement yet. That has to be added to kvproto first (out of this
+    // repo's tree) before a handler can be written here.
 }
 
 mod region_size_response {
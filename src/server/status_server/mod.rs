@@ -34,7 +34,7 @@ use hyper::{
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
 };
-use kvproto::resource_manager::ResourceGroup;
+use kvproto::{metapb, resource_manager::ResourceGroup};
 use metrics::STATUS_REQUEST_DURATION;
 use online_config::OnlineConfig;
 use openssl::{
@@ -44,6 +44,7 @@ use openssl::{
 use pin_project::pin_project;
 use profile::*;
 use prometheus::TEXT_FORMAT;
+use range_cache_memory_engine::RangeCacheEngineStatus;
 use regex::Regex;
 use resource_control::ResourceGroupManager;
 use security::{self, SecurityConfig};
@@ -85,6 +86,38 @@ struct LogLevelRequest {
     pub log_level: LogLevel,
 }
 
+// Response body for `/debug/mvcc_gc_report`. `disk_filtered_versions` is a
+// store-wide total, since the compaction filter runs over SST files rather
+// than per region; `range_cache` (when the range cache engine is enabled)
+// additionally breaks its own count down per region in `regions`.
+#[derive(Serialize)]
+struct MvccGcReport {
+    disk_filtered_versions: i64,
+    range_cache: Option<RangeCacheEngineStatus>,
+}
+
+// One entry of the `/debug/range_cache/region/<id>/hot_keys` response. `key`
+// is hex-escaped the same way other debug endpoints render raw keys, since
+// it may not be valid UTF-8.
+#[derive(Serialize)]
+struct HotKeyReport {
+    key: String,
+    approx_read_count: u64,
+}
+
+// Debug-only mutations for the range cache engine, set via
+// `with_range_cache_engine_actions` and backing the `/debug/range_cache`
+// POST actions. Bundled into one struct, rather than three separate
+// `StatusServer` fields, since `KvEngineBuilder` always supplies all three
+// together.
+#[derive(Clone)]
+struct RangeCacheDebugActions {
+    evict_region: Arc<dyn Fn(metapb::Region) -> Option<()> + Send + Sync>,
+    load_region:
+        Arc<dyn Fn(metapb::Region) -> Option<std::result::Result<(), String>> + Send + Sync>,
+    trigger_gc: Arc<dyn Fn(u64) -> Option<std::result::Result<(), String>> + Send + Sync>,
+}
+
 pub struct StatusServer<R> {
     thread_pool: Runtime,
     tx: Sender<()>,
@@ -95,6 +128,20 @@ pub struct StatusServer<R> {
     security_config: Arc<SecurityConfig>,
     resource_manager: Option<Arc<ResourceGroupManager>>,
     grpc_service_mgr: GrpcServiceManager,
+    // Set via `with_range_cache_engine_status`. The closure itself returns
+    // `None` when the range cache engine isn't enabled; this field is only
+    // `None` when the caller never set it at all (e.g. in tests). Either way
+    // the `/debug/range_cache` handler reports the engine as not enabled.
+    range_cache_engine_status_fn:
+        Option<Arc<dyn Fn() -> Option<RangeCacheEngineStatus> + Send + Sync>>,
+    // Set via `with_range_cache_engine_actions`.
+    range_cache_debug_actions: Option<RangeCacheDebugActions>,
+    // Set via `with_range_cache_engine_hot_keys`, backing the
+    // `/debug/range_cache/region/<id>/hot_keys` GET endpoint. `None` when the
+    // range cache engine isn't enabled, the same convention as
+    // `range_cache_engine_status_fn`.
+    range_cache_hot_keys_fn:
+        Option<Arc<dyn Fn(u64, usize) -> Option<Vec<(Vec<u8>, u64)>> + Send + Sync>>,
 }
 
 impl<R> StatusServer<R>
@@ -130,9 +177,61 @@ where
             security_config,
             resource_manager,
             grpc_service_mgr,
+            range_cache_engine_status_fn: None,
+            range_cache_debug_actions: None,
+            range_cache_hot_keys_fn: None,
         })
     }
 
+    /// Lets `/debug/range_cache` report live range cache engine state via
+    /// `status_fn`. Takes a closure rather than the engine itself so this
+    /// module doesn't need to know the concrete engine type, which varies
+    /// with whether the range cache engine is compiled in and enabled.
+    pub fn with_range_cache_engine_status<F>(mut self, status_fn: F) -> Self
+    where
+        F: Fn() -> Option<RangeCacheEngineStatus> + Send + Sync + 'static,
+    {
+        self.range_cache_engine_status_fn = Some(Arc::new(status_fn));
+        self
+    }
+
+    /// Lets the `/debug/range_cache` POST actions evict a region, load a
+    /// region, or trigger a gc pass against the live range cache engine.
+    /// Like `with_range_cache_engine_status`, closures are used instead of
+    /// the engine itself so this module doesn't need to know the concrete
+    /// engine type.
+    pub fn with_range_cache_engine_actions<E, L, G>(
+        mut self,
+        evict_region: E,
+        load_region: L,
+        trigger_gc: G,
+    ) -> Self
+    where
+        E: Fn(metapb::Region) -> Option<()> + Send + Sync + 'static,
+        L: Fn(metapb::Region) -> Option<std::result::Result<(), String>> + Send + Sync + 'static,
+        G: Fn(u64) -> Option<std::result::Result<(), String>> + Send + Sync + 'static,
+    {
+        self.range_cache_debug_actions = Some(RangeCacheDebugActions {
+            evict_region: Arc::new(evict_region),
+            load_region: Arc::new(load_region),
+            trigger_gc: Arc::new(trigger_gc),
+        });
+        self
+    }
+
+    /// Lets `/debug/range_cache/region/<id>/hot_keys` report the region's
+    /// approximate hottest keys via `hot_keys_fn`. Like
+    /// `with_range_cache_engine_status`, a closure is used instead of the
+    /// engine itself so this module doesn't need to know the concrete engine
+    /// type.
+    pub fn with_range_cache_engine_hot_keys<F>(mut self, hot_keys_fn: F) -> Self
+    where
+        F: Fn(u64, usize) -> Option<Vec<(Vec<u8>, u64)>> + Send + Sync + 'static,
+    {
+        self.range_cache_hot_keys_fn = Some(Arc::new(hot_keys_fn));
+        self
+    }
+
     fn dump_heap_prof_to_resp(req: Request<Body>) -> hyper::Result<Response<Body>> {
         let query = req.uri().query().unwrap_or("");
         let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
@@ -613,6 +712,9 @@ where
         let router = self.router.clone();
         let resource_manager = self.resource_manager.clone();
         let grpc_service_mgr = self.grpc_service_mgr.clone();
+        let range_cache_engine_status_fn = self.range_cache_engine_status_fn.clone();
+        let range_cache_debug_actions = self.range_cache_debug_actions.clone();
+        let range_cache_hot_keys_fn = self.range_cache_hot_keys_fn.clone();
         // Start to serve.
         let server = builder.serve(make_service_fn(move |conn: &C| {
             let x509 = conn.get_x509();
@@ -621,6 +723,9 @@ where
             let router = router.clone();
             let resource_manager = resource_manager.clone();
             let grpc_service_mgr = grpc_service_mgr.clone();
+            let range_cache_engine_status_fn = range_cache_engine_status_fn.clone();
+            let range_cache_debug_actions = range_cache_debug_actions.clone();
+            let range_cache_hot_keys_fn = range_cache_hot_keys_fn.clone();
             async move {
                 // Create a status service.
                 Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
@@ -630,6 +735,9 @@ where
                     let router = router.clone();
                     let resource_manager = resource_manager.clone();
                     let grpc_service_mgr = grpc_service_mgr.clone();
+                    let range_cache_engine_status_fn = range_cache_engine_status_fn.clone();
+                    let range_cache_debug_actions = range_cache_debug_actions.clone();
+                    let range_cache_hot_keys_fn = range_cache_hot_keys_fn.clone();
                     async move {
                         let path = req.uri().path().to_owned();
                         let method = req.method().to_owned();
@@ -734,6 +842,52 @@ where
                                 Self::handle_resume_grpc(grpc_service_mgr)
                             }
                             (Method::GET, "/async_tasks") => Self::dump_async_trace(),
+                            (Method::GET, "/debug/range_cache") => {
+                                Self::handle_get_range_cache_status(&range_cache_engine_status_fn)
+                            }
+                            (Method::GET, "/debug/mvcc_gc_report") => {
+                                Self::handle_get_mvcc_gc_report(&range_cache_engine_status_fn)
+                            }
+                            (Method::POST, path)
+                                if path.starts_with("/debug/range_cache/region/")
+                                    && path.ends_with("/evict") =>
+                            {
+                                Self::handle_evict_range_cache_region(
+                                    req,
+                                    router,
+                                    &range_cache_debug_actions,
+                                    &x509,
+                                )
+                                .await
+                            }
+                            (Method::POST, path)
+                                if path.starts_with("/debug/range_cache/region/")
+                                    && path.ends_with("/load") =>
+                            {
+                                Self::handle_load_range_cache_region(
+                                    req,
+                                    router,
+                                    &range_cache_debug_actions,
+                                    &x509,
+                                )
+                                .await
+                            }
+                            (Method::POST, "/debug/range_cache/gc") => {
+                                Self::handle_trigger_range_cache_gc(
+                                    &req,
+                                    &range_cache_debug_actions,
+                                    &x509,
+                                )
+                            }
+                            (Method::GET, path)
+                                if path.starts_with("/debug/range_cache/region/")
+                                    && path.ends_with("/hot_keys") =>
+                            {
+                                Self::handle_get_range_cache_hot_keys(
+                                    &req,
+                                    &range_cache_hot_keys_fn,
+                                )
+                            }
                             _ => {
                                 is_unknown_path = true;
                                 Ok(make_response(StatusCode::NOT_FOUND, "path not found"))
@@ -816,6 +970,351 @@ where
             )),
         }
     }
+
+    // Disk GC (the compaction filter) and the range cache engine's own GC each
+    // report filtered MVCC versions through their own metrics, with different
+    // label schemes and no shared place an operator can compare them. This
+    // combines both into one report so GC health across both engines is
+    // assessable without cross-referencing two dashboards.
+    fn handle_get_mvcc_gc_report(
+        range_cache_engine_status_fn: &Option<
+            Arc<dyn Fn() -> Option<RangeCacheEngineStatus> + Send + Sync>,
+        >,
+    ) -> hyper::Result<Response<Body>> {
+        use crate::server::gc_worker::{
+            compaction_filter::GC_COMPACTION_FILTERED, STAT_RAW_KEYMODE, STAT_TXN_KEYMODE,
+        };
+
+        let disk_filtered_versions = GC_COMPACTION_FILTERED
+            .with_label_values(&[STAT_TXN_KEYMODE])
+            .get()
+            + GC_COMPACTION_FILTERED.with_label_values(&[STAT_RAW_KEYMODE]).get();
+        let report = MvccGcReport {
+            disk_filtered_versions,
+            range_cache: range_cache_engine_status_fn.as_ref().and_then(|f| f()),
+        };
+        let body = match serde_json::to_vec(&report) {
+            Ok(body) => body,
+            Err(err) => {
+                return Ok(make_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("fails to json: {}", err),
+                ));
+            }
+        };
+        match Response::builder()
+            .header("content-type", "application/json")
+            .body(hyper::Body::from(body))
+        {
+            Ok(resp) => Ok(resp),
+            Err(err) => Ok(make_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("fails to build response: {}", err),
+            )),
+        }
+    }
+
+    fn handle_get_range_cache_status(
+        range_cache_engine_status_fn: &Option<
+            Arc<dyn Fn() -> Option<RangeCacheEngineStatus> + Send + Sync>,
+        >,
+    ) -> hyper::Result<Response<Body>> {
+        let status = range_cache_engine_status_fn.as_ref().and_then(|f| f());
+        let Some(status) = status else {
+            return Ok(make_response(
+                StatusCode::NOT_FOUND,
+                "range cache engine is not enabled",
+            ));
+        };
+        let body = match serde_json::to_vec(&status) {
+            Ok(body) => body,
+            Err(err) => {
+                return Ok(make_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("fails to json: {}", err),
+                ));
+            }
+        };
+        match Response::builder()
+            .header("content-type", "application/json")
+            .body(hyper::Body::from(body))
+        {
+            Ok(resp) => Ok(resp),
+            Err(err) => Ok(make_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("fails to build response: {}", err),
+            )),
+        }
+    }
+
+    // Backs `GET /debug/range_cache/region/<id>/hot_keys?top=<n>`: the
+    // approximate top-K most frequently read keys for a cached region, see
+    // `range_cache_memory_engine::RangeCacheMemoryEngine::top_hot_keys`.
+    // Read-only, so unlike the evict/load/gc actions this needs no audit log
+    // or `resolve_region` round trip through the router.
+    fn handle_get_range_cache_hot_keys(
+        req: &Request<Body>,
+        hot_keys_fn: &Option<Arc<dyn Fn(u64, usize) -> Option<Vec<(Vec<u8>, u64)>> + Send + Sync>>,
+    ) -> hyper::Result<Response<Body>> {
+        let Some(hot_keys_fn) = hot_keys_fn else {
+            return Ok(make_response(
+                StatusCode::NOT_FOUND,
+                "range cache engine is not enabled",
+            ));
+        };
+        let Some(region_id) = Self::parse_range_cache_region_id(req.uri().path()) else {
+            return Ok(make_response(
+                StatusCode::BAD_REQUEST,
+                format!("invalid path {}", req.uri().path()),
+            ));
+        };
+        let query = req.uri().query().unwrap_or("");
+        let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+        let top: usize = match query_pairs.get("top").map(|v| v.parse()) {
+            Some(Ok(top)) => top,
+            Some(Err(err)) => {
+                return Ok(make_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("invalid top: {}", err),
+                ));
+            }
+            None => 10,
+        };
+        let Some(hot_keys) = hot_keys_fn(region_id, top) else {
+            return Ok(make_response(
+                StatusCode::NOT_FOUND,
+                "range cache engine is not enabled",
+            ));
+        };
+        let hot_keys: Vec<_> = hot_keys
+            .into_iter()
+            .map(|(key, count)| HotKeyReport {
+                key: log_wrappers::Value::key(&key).to_string(),
+                approx_read_count: count,
+            })
+            .collect();
+        let body = match serde_json::to_vec(&hot_keys) {
+            Ok(body) => body,
+            Err(err) => {
+                return Ok(make_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("fails to json: {}", err),
+                ));
+            }
+        };
+        match Response::builder()
+            .header("content-type", "application/json")
+            .body(hyper::Body::from(body))
+        {
+            Ok(resp) => Ok(resp),
+            Err(err) => Ok(make_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("fails to build response: {}", err),
+            )),
+        }
+    }
+
+    // Builds the `metapb::Region` that `evict_region`/`load_region` need (id,
+    // key range, epoch, peers) out of the debug-only `RegionMeta` this store's
+    // router already exposes via `query_region` for the `/region` endpoint,
+    // rather than requiring the caller to supply that information itself.
+    async fn resolve_region(
+        router: R,
+        region_id: u64,
+    ) -> std::result::Result<metapb::Region, Response<Body>> {
+        let meta = match router.query_region(region_id).await {
+            Ok(meta) => meta,
+            Err(tikv_kv::Error(box tikv_kv::ErrorInner::Request(header)))
+                if header.has_region_not_found() =>
+            {
+                return Err(make_response(
+                    StatusCode::NOT_FOUND,
+                    format!("region({}) not found", region_id),
+                ));
+            }
+            Err(err) => {
+                return Err(make_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("query failed: {}", err),
+                ));
+            }
+        };
+        let state = &meta.region_state;
+        let mut region = metapb::Region::default();
+        region.set_id(state.id);
+        region.set_start_key(state.start_key.clone());
+        region.set_end_key(state.end_key.clone());
+        let mut epoch = metapb::RegionEpoch::default();
+        epoch.set_conf_ver(state.epoch.conf_ver);
+        epoch.set_version(state.epoch.version);
+        region.set_region_epoch(epoch);
+        region.set_peers(state.peers.iter().map(|p| (*p).into()).collect());
+        Ok(region)
+    }
+
+    fn parse_range_cache_region_id(path: &str) -> Option<u64> {
+        lazy_static! {
+            static ref REGION: Regex =
+                Regex::new(r"/debug/range_cache/region/(?P<id>\d+)/").unwrap();
+        }
+        REGION.captures(path)?["id"].parse().ok()
+    }
+
+    // Identifies the caller of a manual range cache action for the audit log
+    // below. Falls back to "unknown" rather than failing the request: most
+    // deployments don't set `cert-allowed-cn`, so there's often no peer
+    // certificate to read a name from even though the action itself is still
+    // worth auditing.
+    fn audit_actor(cert: &Option<X509>) -> String {
+        cert.as_ref()
+            .and_then(|x509| {
+                x509.subject_name()
+                    .entries_by_nid(openssl::nid::Nid::COMMONNAME)
+                    .next()
+            })
+            .and_then(|name| {
+                std::str::from_utf8(name.data().as_slice())
+                    .ok()
+                    .map(str::to_owned)
+            })
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
+    async fn handle_evict_range_cache_region(
+        req: Request<Body>,
+        router: R,
+        actions: &Option<RangeCacheDebugActions>,
+        cert: &Option<X509>,
+    ) -> hyper::Result<Response<Body>> {
+        let Some(actions) = actions else {
+            return Ok(make_response(
+                StatusCode::NOT_FOUND,
+                "range cache engine is not enabled",
+            ));
+        };
+        let Some(region_id) = Self::parse_range_cache_region_id(req.uri().path()) else {
+            return Ok(make_response(
+                StatusCode::BAD_REQUEST,
+                format!("invalid path {}", req.uri().path()),
+            ));
+        };
+        let region = match Self::resolve_region(router, region_id).await {
+            Ok(region) => region,
+            Err(resp) => return Ok(resp),
+        };
+        let actor = Self::audit_actor(cert);
+        let outcome = (actions.evict_region)(region);
+        info!(
+            "range cache audit: evict region";
+            "actor" => %actor,
+            "region_id" => region_id,
+            "outcome" => if outcome.is_some() { "success" } else { "not_enabled" },
+        );
+        match outcome {
+            Some(()) => Ok(make_response(StatusCode::OK, "Successfully evict region")),
+            None => Ok(make_response(
+                StatusCode::NOT_FOUND,
+                "range cache engine is not enabled",
+            )),
+        }
+    }
+
+    async fn handle_load_range_cache_region(
+        req: Request<Body>,
+        router: R,
+        actions: &Option<RangeCacheDebugActions>,
+        cert: &Option<X509>,
+    ) -> hyper::Result<Response<Body>> {
+        let Some(actions) = actions else {
+            return Ok(make_response(
+                StatusCode::NOT_FOUND,
+                "range cache engine is not enabled",
+            ));
+        };
+        let Some(region_id) = Self::parse_range_cache_region_id(req.uri().path()) else {
+            return Ok(make_response(
+                StatusCode::BAD_REQUEST,
+                format!("invalid path {}", req.uri().path()),
+            ));
+        };
+        let region = match Self::resolve_region(router, region_id).await {
+            Ok(region) => region,
+            Err(resp) => return Ok(resp),
+        };
+        let actor = Self::audit_actor(cert);
+        let outcome = (actions.load_region)(region);
+        info!(
+            "range cache audit: load region";
+            "actor" => %actor,
+            "region_id" => region_id,
+            "outcome" => match &outcome {
+                Some(Ok(())) => "success".to_string(),
+                Some(Err(err)) => format!("error: {}", err),
+                None => "not_enabled".to_string(),
+            },
+        );
+        match outcome {
+            Some(Ok(())) => Ok(make_response(StatusCode::OK, "Successfully load region")),
+            Some(Err(err)) => Ok(make_response(
+                StatusCode::BAD_REQUEST,
+                format!("load failed: {}", err),
+            )),
+            None => Ok(make_response(
+                StatusCode::NOT_FOUND,
+                "range cache engine is not enabled",
+            )),
+        }
+    }
+
+    fn handle_trigger_range_cache_gc(
+        req: &Request<Body>,
+        actions: &Option<RangeCacheDebugActions>,
+        cert: &Option<X509>,
+    ) -> hyper::Result<Response<Body>> {
+        let Some(actions) = actions else {
+            return Ok(make_response(
+                StatusCode::NOT_FOUND,
+                "range cache engine is not enabled",
+            ));
+        };
+        let query = req.uri().query().unwrap_or("");
+        let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+        let safe_point: u64 = match query_pairs.get("safe_point").map(|v| v.parse()) {
+            Some(Ok(safe_point)) => safe_point,
+            Some(Err(err)) => {
+                return Ok(make_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("invalid safe_point: {}", err),
+                ));
+            }
+            None => {
+                return Ok(make_response(StatusCode::BAD_REQUEST, "missing safe_point"));
+            }
+        };
+        let actor = Self::audit_actor(cert);
+        let outcome = (actions.trigger_gc)(safe_point);
+        info!(
+            "range cache audit: trigger gc";
+            "actor" => %actor,
+            "safe_point" => safe_point,
+            "outcome" => match &outcome {
+                Some(Ok(())) => "success".to_string(),
+                Some(Err(err)) => format!("error: {}", err),
+                None => "not_enabled".to_string(),
+            },
+        );
+        match outcome {
+            Some(Ok(())) => Ok(make_response(StatusCode::OK, "Successfully trigger gc")),
+            Some(Err(err)) => Ok(make_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("trigger gc failed: {}", err),
+            )),
+            None => Ok(make_response(
+                StatusCode::NOT_FOUND,
+                "range cache engine is not enabled",
+            )),
+        }
+    }
 }
 
 #[derive(Serialize)]
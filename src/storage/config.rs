@@ -105,6 +105,21 @@ pub struct Config {
     #[online_config(skip)]
     pub txn_status_cache_capacity: usize,
     pub memory_quota: ReadableSize,
+    // Fraction of user `get`s, independent of the range cache engine's own consistency
+    // checking, to additionally read through the non-cached (disk) path and compare against
+    // the result actually returned, so a divergence between the two is caught even when
+    // nothing else would have noticed. `0.0` (the default) disables this entirely, since the
+    // extra read doubles the cost of a sampled request. Read once at startup, not hot-reloadable.
+    #[online_config(skip)]
+    pub shadow_read_sample_ratio: f64,
+    // Number of consecutive shadow-read mismatches (see `shadow_read_sample_ratio`)
+    // for the same store before the range cache engine fences itself off: evicts
+    // every cached region and pauses admission until an operator intervenes. `0`
+    // (the default) disables fencing from shadow reads entirely, since a single
+    // mismatch is more likely to be a transient race (e.g. a write landing between
+    // the primary and shadow snapshot) than real corruption.
+    #[online_config(skip)]
+    pub shadow_read_corruption_fence_threshold: u64,
     #[online_config(submodule)]
     pub flow_control: FlowControlConfig,
     #[online_config(submodule)]
@@ -140,6 +155,8 @@ impl Default for Config {
             io_rate_limit: IoRateLimitConfig::default(),
             background_error_recovery_window: ReadableDuration::hours(1),
             memory_quota: DEFAULT_TXN_MEMORY_QUOTA_CAPACITY,
+            shadow_read_sample_ratio: 0.0,
+            shadow_read_corruption_fence_threshold: 0,
         }
     }
 }
@@ -209,6 +226,9 @@ impl Config {
                 ).into()
             );
         }
+        if !(0.0..=1.0).contains(&self.shadow_read_sample_ratio) {
+            return Err("storage.shadow-read-sample-ratio must be in [0, 1]".into());
+        }
         self.io_rate_limit.validate()?;
         if self.memory_quota < self.scheduler_pending_write_threshold {
             warn!(
@@ -254,13 +274,9 @@ impl Config {
 #[serde(rename_all = "kebab-case")]
 pub struct FlowControlConfig {
     pub enable: bool,
-    #[online_config(skip)]
     pub soft_pending_compaction_bytes_limit: ReadableSize,
-    #[online_config(skip)]
     pub hard_pending_compaction_bytes_limit: ReadableSize,
-    #[online_config(skip)]
     pub memtables_threshold: u64,
-    #[online_config(skip)]
     pub l0_files_threshold: u64,
 }
 
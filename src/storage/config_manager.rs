@@ -78,6 +78,26 @@ impl<EK: Engine, K: ConfigurableDb, L: LockManager> ConfigManager
                 }
                 self.flow_controller.enable(enable);
             }
+            let soft_limit = flow_control
+                .remove("soft_pending_compaction_bytes_limit")
+                .map(|v| ReadableSize::from(v).0);
+            let hard_limit = flow_control
+                .remove("hard_pending_compaction_bytes_limit")
+                .map(|v| ReadableSize::from(v).0);
+            let memtables_threshold = flow_control.remove("memtables_threshold").map(u64::from);
+            let l0_files_threshold = flow_control.remove("l0_files_threshold").map(u64::from);
+            if soft_limit.is_some()
+                || hard_limit.is_some()
+                || memtables_threshold.is_some()
+                || l0_files_threshold.is_some()
+            {
+                self.flow_controller.set_pending_compaction_limit(
+                    soft_limit,
+                    hard_limit,
+                    memtables_threshold,
+                    l0_files_threshold,
+                );
+            }
         } else if let Some(v) = change.get("scheduler_worker_pool_size") {
             let pool_size: usize = v.into();
             self.scheduler.scale_pool_size(pool_size);
@@ -84,6 +84,7 @@ pub fn tls_collect_read_flow(
             end,
             &statistics.write.flow_stats,
             &statistics.data.flow_stats,
+            (statistics.write.total_op_count() + statistics.data.total_op_count()) as u64,
         );
     });
 }
@@ -486,6 +487,12 @@ lazy_static! {
         "The CF being throttled.",
         &["cf"]
     ).unwrap();
+    pub static ref SCHED_THROTTLE_STALL_DURATION_GAUGE: IntGauge = register_int_gauge!(
+        "tikv_scheduler_throttle_stall_duration_seconds",
+        "How long, in seconds, the flow controller's speed limiter has been actively \
+         throttling writes, or 0 if it isn't currently throttling."
+    )
+    .unwrap();
     pub static ref SCHED_PENDING_COMPACTION_BYTES_GAUGE: IntGaugeVec = register_int_gauge_vec!(
         "tikv_scheduler_pending_compaction_bytes",
         "The number of pending compaction bytes.",
@@ -608,6 +615,16 @@ lazy_static! {
     pub static ref IN_MEMORY_PESSIMISTIC_LOCKING_COUNTER_STATIC: InMemoryPessimisticLockingCounter =
         auto_flush_from!(IN_MEMORY_PESSIMISTIC_LOCKING_COUNTER, InMemoryPessimisticLockingCounter);
 
+    // Driven by `Config::shadow_read_sample_ratio`: for a sampled fraction of `get`s, the
+    // result actually returned is compared against a second read of the same key taken
+    // through the non-cached (disk) path.
+    pub static ref STORAGE_SHADOW_READ_COUNTER_VEC: IntCounterVec = register_int_counter_vec!(
+        "tikv_storage_shadow_read_total",
+        "Total number of sampled shadow reads, by comparison result",
+        &["result"]
+    )
+    .unwrap();
+
     pub static ref LOCK_WAIT_QUEUE_ENTRIES_GAUGE_VEC: LockWaitQueueEntriesGauge = register_static_int_gauge_vec!(
         LockWaitQueueEntriesGauge,
         "tikv_lock_wait_queue_entries_gauge_vec",
@@ -77,7 +77,8 @@ use causal_ts::{CausalTsProvider, CausalTsProviderImpl};
 use collections::HashMap;
 use concurrency_manager::{ConcurrencyManager, KeyHandleGuard};
 use engine_traits::{
-    raw_ttl::ttl_to_expire_ts, CfName, CF_DEFAULT, CF_LOCK, CF_WRITE, DATA_CFS, DATA_CFS_LEN,
+    raw_ttl::ttl_to_expire_ts, CfName, RangeCacheEngineExt, CF_DEFAULT, CF_LOCK, CF_WRITE,
+    DATA_CFS, DATA_CFS_LEN,
 };
 use futures::{future::Either, prelude::*};
 use kvproto::{
@@ -213,6 +214,17 @@ pub struct Storage<E: Engine, L: LockManager, F: KvFormat> {
     quota_limiter: Arc<QuotaLimiter>,
     resource_manager: Option<Arc<ResourceGroupManager>>,
 
+    // Fraction of `get`s to additionally read through the disk-only path and compare
+    // against the result actually returned. See `Config::shadow_read_sample_ratio`.
+    shadow_read_sample_ratio: f64,
+
+    // See `Config::shadow_read_corruption_fence_threshold`.
+    shadow_read_corruption_fence_threshold: u64,
+    // Consecutive shadow-read mismatches observed so far; reset to 0 on a match and
+    // on every fence. Shared across `Storage` clones since shadow reads for the
+    // same store can run on any of them.
+    shadow_read_consecutive_mismatches: Arc<AtomicU64>,
+
     _phantom: PhantomData<F>,
 }
 
@@ -237,6 +249,9 @@ impl<E: Engine, L: LockManager, F: KvFormat> Clone for Storage<E, L, F> {
             resource_tag_factory: self.resource_tag_factory.clone(),
             quota_limiter: self.quota_limiter.clone(),
             resource_manager: self.resource_manager.clone(),
+            shadow_read_sample_ratio: self.shadow_read_sample_ratio,
+            shadow_read_corruption_fence_threshold: self.shadow_read_corruption_fence_threshold,
+            shadow_read_consecutive_mismatches: self.shadow_read_consecutive_mismatches.clone(),
             _phantom: PhantomData,
         }
     }
@@ -309,6 +324,9 @@ impl<E: Engine, L: LockManager, F: KvFormat> Storage<E, L, F> {
             resource_tag_factory,
             quota_limiter,
             resource_manager,
+            shadow_read_sample_ratio: config.shadow_read_sample_ratio,
+            shadow_read_corruption_fence_threshold: config.shadow_read_corruption_fence_threshold,
+            shadow_read_consecutive_mismatches: Arc::new(AtomicU64::new(0)),
             _phantom: PhantomData,
         })
     }
@@ -603,7 +621,7 @@ impl<E: Engine, L: LockManager, F: KvFormat> Storage<E, L, F> {
         let stage_begin_ts = Instant::now();
         let deadline = Self::get_deadline(&ctx);
         const CMD: CommandKind = CommandKind::get;
-        let priority = ctx.get_priority();
+        let priority = self.fast_lane_priority(ctx.get_priority(), key.as_encoded());
         let metadata = TaskMetadata::from_ctx(ctx.get_resource_control_context());
         let resource_limiter = self.resource_manager.as_ref().and_then(|r| {
             r.get_resource_limiter(
@@ -623,6 +641,9 @@ impl<E: Engine, L: LockManager, F: KvFormat> Storage<E, L, F> {
 
         let quota_limiter = self.quota_limiter.clone();
         let mut sample = quota_limiter.new_sample(true);
+        let shadow_read_sample_ratio = self.shadow_read_sample_ratio;
+        let shadow_read_corruption_fence_threshold = self.shadow_read_corruption_fence_threshold;
+        let shadow_read_consecutive_mismatches = self.shadow_read_consecutive_mismatches.clone();
         with_tls_tracker(|tracker| {
             tracker.metrics.grpc_process_nanos =
                 stage_begin_ts.saturating_elapsed().as_nanos() as u64;
@@ -656,6 +677,10 @@ impl<E: Engine, L: LockManager, F: KvFormat> Storage<E, L, F> {
                 // `TsSet::vec` is more efficient here.
                 let bypass_locks = TsSet::vec_from_u64s(ctx.take_resolved_locks());
                 let access_locks = TsSet::vec_from_u64s(ctx.take_committed_locks());
+                // Cloned before `bypass_locks`/`access_locks` are moved into the real read
+                // below, for the (rare) sampled shadow read further down.
+                let shadow_read_sample = (thread_rng().gen::<f64>() < shadow_read_sample_ratio)
+                    .then(|| (bypass_locks.clone(), access_locks.clone()));
 
                 let snap_ctx = prepare_snap_ctx(
                     &ctx,
@@ -702,6 +727,95 @@ impl<E: Engine, L: LockManager, F: KvFormat> Storage<E, L, F> {
                         &statistics,
                         buckets.as_ref(),
                     );
+
+                    // For a sampled fraction of reads, independently re-read the same key
+                    // through the non-cached (disk) path and compare against what was just
+                    // returned, so a divergence between the range cache engine and the disk
+                    // engine is caught even when nothing else would have noticed. See
+                    // `Config::shadow_read_sample_ratio`.
+                    if let Some((shadow_bypass_locks, shadow_access_locks)) = shadow_read_sample {
+                        let shadow_snap_ctx = SnapContext {
+                            pb_ctx: &ctx,
+                            start_ts: Some(start_ts),
+                            force_disk_read: true,
+                            ..Default::default()
+                        };
+                        match Self::with_tls_engine(|engine| {
+                            Self::snapshot(engine, shadow_snap_ctx)
+                        })
+                        .await
+                        {
+                            Ok(shadow_snapshot) => {
+                                let mut shadow_statistics = Statistics::default();
+                                let shadow_store = SnapshotStore::new(
+                                    shadow_snapshot,
+                                    start_ts,
+                                    ctx.get_isolation_level(),
+                                    !ctx.get_not_fill_cache(),
+                                    shadow_bypass_locks,
+                                    shadow_access_locks,
+                                    false,
+                                );
+                                let shadow_result = shadow_store
+                                    .get(&key, &mut shadow_statistics)
+                                    .map_err(Error::from);
+                                match (&result, &shadow_result) {
+                                    (Ok(value), Ok(shadow_value)) if value == shadow_value => {
+                                        STORAGE_SHADOW_READ_COUNTER_VEC
+                                            .with_label_values(&["match"])
+                                            .inc();
+                                        shadow_read_consecutive_mismatches
+                                            .store(0, Ordering::Relaxed);
+                                    }
+                                    (Ok(_), Ok(_)) => {
+                                        STORAGE_SHADOW_READ_COUNTER_VEC
+                                            .with_label_values(&["mismatch"])
+                                            .inc();
+                                        error!(
+                                            "shadow read diverged from primary read";
+                                            "region_id" => ctx.get_region_id(),
+                                            "key" => %key,
+                                            "start_ts" => start_ts,
+                                        );
+                                        // `0` disables fencing from shadow reads entirely (see
+                                        // `Config::shadow_read_corruption_fence_threshold`).
+                                        if shadow_read_corruption_fence_threshold > 0 {
+                                            let mismatches = shadow_read_consecutive_mismatches
+                                                .fetch_add(1, Ordering::Relaxed)
+                                                + 1;
+                                            if mismatches >= shadow_read_corruption_fence_threshold {
+                                                shadow_read_consecutive_mismatches
+                                                    .store(0, Ordering::Relaxed);
+                                                if let Some(kv_engine) =
+                                                    Self::with_tls_engine(|engine| engine.kv_engine())
+                                                {
+                                                    kv_engine.fence_range_cache_for_corruption(
+                                                        "shadow read mismatches exceeded configured threshold",
+                                                    );
+                                                }
+                                            }
+                                        }
+                                    }
+                                    _ => {
+                                        // Either read failed outright (e.g. region split or
+                                        // epoch mismatch on the force-disk-read path); treat
+                                        // this as inconclusive rather than a genuine
+                                        // divergence, since it may be transient.
+                                        STORAGE_SHADOW_READ_COUNTER_VEC
+                                            .with_label_values(&["error"])
+                                            .inc();
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                STORAGE_SHADOW_READ_COUNTER_VEC
+                                    .with_label_values(&["error"])
+                                    .inc();
+                                warn!("failed to take shadow read snapshot"; "err" => ?e);
+                            }
+                        }
+                    }
+
                     let now = Instant::now();
                     SCHED_PROCESSING_READ_HISTOGRAM_STATIC
                         .get(CMD)
@@ -1185,7 +1299,10 @@ impl<E: Engine, L: LockManager, F: KvFormat> Storage<E, L, F> {
         let stage_begin_ts = Instant::now();
         let deadline = Self::get_deadline(&ctx);
         const CMD: CommandKind = CommandKind::batch_get;
-        let priority = ctx.get_priority();
+        let priority = self.fast_lane_priority_for_keys(
+            ctx.get_priority(),
+            keys.iter().map(|k| k.as_encoded().as_slice()),
+        );
         let metadata = TaskMetadata::from_ctx(ctx.get_resource_control_context());
         let resource_limiter = self.resource_manager.as_ref().and_then(|r| {
             r.get_resource_limiter(
@@ -1828,6 +1945,42 @@ impl<E: Engine, L: LockManager, F: KvFormat> Storage<E, L, F> {
         Deadline::from_now(execution_duration_limit)
     }
 
+    // When the range cache engine already has a cached region covering `key`,
+    // a read of it is almost certain to be served from memory in
+    // microseconds, so it's worth scheduling onto the unified read pool's
+    // dedicated top level the same way a `CommandPri::High` request is,
+    // regardless of the request's own priority. Without this, a cache-served
+    // read can sit in the same queue behind disk-bound reads of the same
+    // priority, eroding the tail latency the cache exists to improve.
+    fn fast_lane_priority(&self, priority: CommandPri, key: &[u8]) -> CommandPri {
+        self.fast_lane_priority_for_keys(priority, std::iter::once(key))
+    }
+
+    // Same as `fast_lane_priority`, but for a batch read: only fast-lanes the
+    // batch if every key in it is covered by a cached region, since a batch
+    // that still has to fall through to disk for even one key gets none of
+    // the latency win the fast lane is for.
+    fn fast_lane_priority_for_keys<'k>(
+        &self,
+        priority: CommandPri,
+        mut keys: impl Iterator<Item = &'k [u8]>,
+    ) -> CommandPri {
+        if priority == CommandPri::High {
+            return priority;
+        }
+        let Some(kv_engine) = self.engine.kv_engine() else {
+            return priority;
+        };
+        if !kv_engine.range_cache_engine_enabled() {
+            return priority;
+        }
+        if keys.all(|key| kv_engine.get_region_for_key(key).is_some()) {
+            CommandPri::High
+        } else {
+            priority
+        }
+    }
+
     /// Delete all keys in the range [`start_key`, `end_key`).
     ///
     /// All keys in the range will be deleted permanently regardless of their
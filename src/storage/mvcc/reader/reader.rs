@@ -240,6 +240,12 @@ impl<S: EngineSnapshot> MvccReader<S> {
             self.create_lock_cursor_if_not_exist()?;
         }
 
+        // When the snapshot is a `HybridEngineSnapshot` backed by a cached region, this
+        // already reads the lock CF out of the range cache engine instead of RocksDB: the
+        // lock CF is one of the cached data CFs, and `Snapshot`/`Peekable` route to the
+        // cache transparently. The cache only drops lock tombstones once they're older
+        // than every live RocksDB snapshot's sequence number (see `CleanLockTombstone`),
+        // so a lock visible to this snapshot is never pruned out from under it.
         let res = if let Some(ref mut cursor) = self.lock_cursor {
             match cursor.get(key, &mut self.statistics.lock)? {
                 Some(v) => Some(Lock::parse(v)?),
@@ -6,12 +6,40 @@ use std::time::Duration;
 
 pub use singleton_flow_controller::EngineFlowController;
 pub use tablet_flow_controller::TabletFlowController;
+use tikv_util::time::Instant;
 
 pub enum FlowController {
     Singleton(EngineFlowController),
     Tablet(TabletFlowController),
 }
 
+/// A snapshot of whether a region's writes are currently being slowed down by
+/// the flow controller's speed limiter, which CF is driving it, and for how
+/// long it's been in that state. Used by tests and by tikv-ctl to observe
+/// backpressure directly instead of inferring it from write latency.
+#[derive(Clone, Debug, Default)]
+pub struct FlowControlStallInfo {
+    pub throttled: bool,
+    pub trigger_cf: Option<String>,
+    since: Option<Instant>,
+}
+
+impl FlowControlStallInfo {
+    fn new(trigger_cf: Option<String>, since: Option<Instant>) -> Self {
+        Self {
+            throttled: since.is_some(),
+            trigger_cf,
+            since,
+        }
+    }
+
+    /// How long the region has been throttled so far, or zero if it isn't
+    /// currently throttled.
+    pub fn stall_duration(&self) -> Duration {
+        self.since.map_or(Duration::ZERO, |t| t.saturating_elapsed())
+    }
+}
+
 macro_rules! flow_controller_fn {
     ($fn_name:ident, $region_id:ident, $type:ident) => {
         pub fn $fn_name(&self, $region_id: u64) -> $type {
@@ -41,6 +69,7 @@ impl FlowController {
     #[cfg(test)]
     flow_controller_fn!(total_bytes_consumed, region_id, usize);
     flow_controller_fn!(is_unlimited, region_id, bool);
+    flow_controller_fn!(stall_info, region_id, FlowControlStallInfo);
 
     pub fn unconsume(&self, region_id: u64, bytes: usize) {
         match self {
@@ -62,6 +91,29 @@ impl FlowController {
         }
     }
 
+    pub fn set_pending_compaction_limit(
+        &self,
+        soft_pending_compaction_bytes_limit: Option<u64>,
+        hard_pending_compaction_bytes_limit: Option<u64>,
+        memtables_threshold: Option<u64>,
+        l0_files_threshold: Option<u64>,
+    ) {
+        match self {
+            FlowController::Singleton(ref controller) => controller.set_pending_compaction_limit(
+                soft_pending_compaction_bytes_limit,
+                hard_pending_compaction_bytes_limit,
+                memtables_threshold,
+                l0_files_threshold,
+            ),
+            FlowController::Tablet(ref controller) => controller.set_pending_compaction_limit(
+                soft_pending_compaction_bytes_limit,
+                hard_pending_compaction_bytes_limit,
+                memtables_threshold,
+                l0_files_threshold,
+            ),
+        }
+    }
+
     #[cfg(test)]
     pub fn set_speed_limit(&self, region_id: u64, speed_limit: f64) {
         match self {
@@ -8,7 +8,7 @@ use std::{
     sync::{
         atomic::{AtomicBool, AtomicU32, Ordering},
         mpsc::{self, Receiver, RecvTimeoutError, SyncSender},
-        Arc,
+        Arc, Mutex,
     },
     thread::{Builder, JoinHandle},
     time::Duration,
@@ -26,6 +26,7 @@ use tikv_util::{
     time::{Instant, Limiter},
 };
 
+use super::FlowControlStallInfo;
 use crate::storage::{config::FlowControlConfig, metrics::*};
 
 pub(super) const TICK_DURATION: Duration = Duration::from_millis(1000);
@@ -75,6 +76,7 @@ pub struct EngineFlowController {
     discard_ratio: Arc<AtomicU32>,
     limiter: Arc<Limiter>,
     enabled: Arc<AtomicBool>,
+    stall_info: Arc<Mutex<FlowControlStallInfo>>,
     tx: Option<SyncSender<Msg>>,
     handle: Option<std::thread::JoinHandle<()>>,
 }
@@ -83,6 +85,12 @@ pub(super) enum Msg {
     Close,
     Enable,
     Disable,
+    SetPendingCompactionLimit {
+        soft_pending_compaction_bytes_limit: Option<u64>,
+        hard_pending_compaction_bytes_limit: Option<u64>,
+        memtables_threshold: Option<u64>,
+        l0_files_threshold: Option<u64>,
+    },
 }
 
 impl Drop for EngineFlowController {
@@ -110,6 +118,7 @@ impl EngineFlowController {
             discard_ratio: Arc::new(AtomicU32::new(0)),
             limiter: Arc::new(Limiter::new(f64::INFINITY)),
             enabled: Arc::new(AtomicBool::new(false)),
+            stall_info: Arc::new(Mutex::new(FlowControlStallInfo::default())),
             tx: None,
             handle: None,
         }
@@ -127,6 +136,7 @@ impl EngineFlowController {
         );
         let discard_ratio = Arc::new(AtomicU32::new(0));
         let checker = FlowChecker::new(config, engine, discard_ratio.clone(), limiter.clone());
+        let stall_info = checker.stall_info_handle();
         let (tx, rx) = mpsc::sync_channel(5);
 
         tx.send(if config.enable {
@@ -140,6 +150,7 @@ impl EngineFlowController {
             discard_ratio,
             limiter,
             enabled: Arc::new(AtomicBool::new(config.enable)),
+            stall_info,
             tx: Some(tx),
             handle: Some(checker.start(rx, flow_info_receiver)),
         }
@@ -186,6 +197,24 @@ impl EngineFlowController {
         self.enabled.load(Ordering::Relaxed)
     }
 
+    pub fn set_pending_compaction_limit(
+        &self,
+        soft_pending_compaction_bytes_limit: Option<u64>,
+        hard_pending_compaction_bytes_limit: Option<u64>,
+        memtables_threshold: Option<u64>,
+        l0_files_threshold: Option<u64>,
+    ) {
+        if let Some(tx) = &self.tx {
+            tx.send(Msg::SetPendingCompactionLimit {
+                soft_pending_compaction_bytes_limit,
+                hard_pending_compaction_bytes_limit,
+                memtables_threshold,
+                l0_files_threshold,
+            })
+            .unwrap();
+        }
+    }
+
     #[cfg(test)]
     pub fn set_speed_limit(&self, _region_id: u64, speed_limit: f64) {
         self.limiter.set_speed_limit(speed_limit);
@@ -194,6 +223,10 @@ impl EngineFlowController {
     pub fn is_unlimited(&self, _region_id: u64) -> bool {
         self.limiter.speed_limit() == f64::INFINITY
     }
+
+    pub fn stall_info(&self, _region_id: u64) -> FlowControlStallInfo {
+        self.stall_info.lock().unwrap().clone()
+    }
 }
 
 const SMOOTHER_STALE_RECORD_THRESHOLD: u64 = 300; // 5min
@@ -500,6 +533,12 @@ pub(super) struct FlowChecker<E: FlowControlFactorStore + Send + 'static> {
     // decided based on the statistics of the throttle CF. If the multiple CFs
     // exceed the threshold, choose the larger one.
     throttle_cf: Option<String>,
+    // When throttle_cf became Some, for reporting how long a region has been
+    // throttled through stall_info().
+    throttle_start: Option<Instant>,
+    // Shared snapshot of throttle_cf/throttle_start, readable from outside the
+    // checker thread via EngineFlowController::stall_info().
+    stall_info: Arc<Mutex<FlowControlStallInfo>>,
     // Discard ratio is decided by pending compaction bytes, it's the ratio to
     // drop write requests(return ServerIsBusy to TiDB) randomly.
     discard_ratio: Arc<AtomicU32>,
@@ -554,6 +593,8 @@ impl<E: FlowControlFactorStore + Send + 'static> FlowChecker<E> {
             write_flow_recorder: Smoother::default(),
             cf_checkers,
             throttle_cf: None,
+            throttle_start: None,
+            stall_info: Arc::new(Mutex::new(FlowControlStallInfo::default())),
             last_record_time: Instant::now_coarse(),
             last_speed: 0.0,
             wait_for_destroy_range_finish: false,
@@ -561,6 +602,34 @@ impl<E: FlowControlFactorStore + Send + 'static> FlowChecker<E> {
         }
     }
 
+    // Shared handle other controllers can clone out right after construction to
+    // observe this checker's throttle state without reaching into the checker
+    // thread.
+    pub(super) fn stall_info_handle(&self) -> Arc<Mutex<FlowControlStallInfo>> {
+        self.stall_info.clone()
+    }
+
+    pub(super) fn set_pending_compaction_limit(
+        &mut self,
+        soft_pending_compaction_bytes_limit: Option<u64>,
+        hard_pending_compaction_bytes_limit: Option<u64>,
+        memtables_threshold: Option<u64>,
+        l0_files_threshold: Option<u64>,
+    ) {
+        if let Some(v) = soft_pending_compaction_bytes_limit {
+            self.soft_pending_compaction_bytes_limit = v;
+        }
+        if let Some(v) = hard_pending_compaction_bytes_limit {
+            self.hard_pending_compaction_bytes_limit = v;
+        }
+        if let Some(v) = memtables_threshold {
+            self.memtables_threshold = v;
+        }
+        if let Some(v) = l0_files_threshold {
+            self.l0_files_threshold = v;
+        }
+    }
+
     pub fn on_flow_info_msg(
         &mut self,
         enabled: bool,
@@ -660,6 +729,19 @@ impl<E: FlowControlFactorStore + Send + 'static> FlowChecker<E> {
                         Ok(Msg::Enable) => {
                             enabled = true;
                         }
+                        Ok(Msg::SetPendingCompactionLimit {
+                            soft_pending_compaction_bytes_limit,
+                            hard_pending_compaction_bytes_limit,
+                            memtables_threshold,
+                            l0_files_threshold,
+                        }) => {
+                            checker.set_pending_compaction_limit(
+                                soft_pending_compaction_bytes_limit,
+                                hard_pending_compaction_bytes_limit,
+                                memtables_threshold,
+                                l0_files_threshold,
+                            );
+                        }
                         Err(_) => {}
                     }
 
@@ -697,9 +779,14 @@ impl<E: FlowControlFactorStore + Send + 'static> FlowChecker<E> {
         self.limiter.set_speed_limit(f64::INFINITY);
         SCHED_DISCARD_RATIO_GAUGE.set(0);
         self.discard_ratio.store(0, Ordering::Relaxed);
+        SCHED_THROTTLE_STALL_DURATION_GAUGE.set(0);
     }
 
     pub fn update_statistics(&mut self) -> (f64, HashMap<&str, i64>) {
+        SCHED_THROTTLE_STALL_DURATION_GAUGE.set(
+            self.throttle_start
+                .map_or(0, |t| t.saturating_elapsed().as_secs() as i64),
+        );
         let mut cf_throttle_flags = HashMap::default();
         if let Some(throttle_cf) = self.throttle_cf.as_ref() {
             cf_throttle_flags.insert(throttle_cf.as_str(), 1);
@@ -1032,6 +1119,13 @@ impl<E: FlowControlFactorStore + Send + 'static> FlowChecker<E> {
             self.throttle_cf = None;
             throttle = f64::INFINITY;
         }
+        if throttle == f64::INFINITY {
+            self.throttle_start = None;
+        } else if self.throttle_start.is_none() {
+            self.throttle_start = Some(Instant::now_coarse());
+        }
+        *self.stall_info.lock().unwrap() =
+            FlowControlStallInfo::new(self.throttle_cf.clone(), self.throttle_start);
         SCHED_THROTTLE_FLOW_GAUGE.set(if throttle == f64::INFINITY {
             0
         } else {
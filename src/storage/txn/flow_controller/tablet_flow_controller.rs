@@ -5,7 +5,7 @@ use std::{
     sync::{
         atomic::{AtomicBool, AtomicU32, Ordering},
         mpsc::{self, Receiver, RecvTimeoutError, SyncSender},
-        Arc, RwLock,
+        Arc, Mutex, RwLock,
     },
     thread::{Builder, JoinHandle},
     time::Duration,
@@ -15,10 +15,13 @@ use collections::{HashMap, HashMapEntry};
 use engine_rocks::FlowInfo;
 use engine_traits::{CfNamesExt, FlowControlFactorsExt, TabletRegistry, DATA_CFS};
 use rand::Rng;
-use tikv_util::{sys::thread::StdThreadBuildWrapper, time::Limiter};
+use tikv_util::{config::ReadableSize, sys::thread::StdThreadBuildWrapper, time::Limiter};
 
-use super::singleton_flow_controller::{
-    FlowChecker, FlowControlFactorStore, Msg, RATIO_SCALE_FACTOR, TICK_DURATION,
+use super::{
+    singleton_flow_controller::{
+        FlowChecker, FlowControlFactorStore, Msg, RATIO_SCALE_FACTOR, TICK_DURATION,
+    },
+    FlowControlStallInfo,
 };
 use crate::storage::{config::FlowControlConfig, metrics::*};
 
@@ -60,11 +63,13 @@ impl<EK: CfNamesExt + FlowControlFactorsExt + Clone> FlowControlFactorStore
 }
 
 type Limiters = Arc<RwLock<HashMap<u64, (Arc<Limiter>, Arc<AtomicU32>)>>>;
+type StallInfos = Arc<RwLock<HashMap<u64, Arc<Mutex<FlowControlStallInfo>>>>>;
 pub struct TabletFlowController {
     enabled: Arc<AtomicBool>,
     tx: Option<SyncSender<Msg>>,
     handle: Option<std::thread::JoinHandle<()>>,
     limiters: Limiters,
+    stall_infos: StallInfos,
     global_discard_ratio: Arc<AtomicU32>,
 }
 
@@ -101,17 +106,20 @@ impl TabletFlowController {
         .unwrap();
         let flow_checkers = Arc::new(RwLock::new(HashMap::default()));
         let limiters: Limiters = Arc::new(RwLock::new(HashMap::default()));
+        let stall_infos: StallInfos = Arc::new(RwLock::new(HashMap::default()));
         let global_discard_ratio = Arc::new(AtomicU32::new(0));
         Self {
             enabled: Arc::new(AtomicBool::new(config.enable)),
             tx: Some(tx),
             limiters: limiters.clone(),
+            stall_infos: stall_infos.clone(),
             handle: Some(FlowInfoDispatcher::start(
                 rx,
                 flow_info_receiver,
                 registry,
                 flow_checkers,
                 limiters,
+                stall_infos,
                 config.clone(),
                 global_discard_ratio.clone(),
             )),
@@ -134,6 +142,7 @@ impl FlowInfoDispatcher {
         registry: TabletRegistry<E>,
         flow_checkers: Arc<RwLock<HashMap<u64, FlowChecker<TabletFlowFactorStore<E>>>>>,
         limiters: Limiters,
+        stall_infos: StallInfos,
         config: FlowControlConfig,
         global_discard_ratio: Arc<AtomicU32>,
     ) -> JoinHandle<()> {
@@ -143,6 +152,7 @@ impl FlowInfoDispatcher {
                 let mut deadline = std::time::Instant::now();
                 let mut enabled = config.enable;
                 let engine = TabletFlowFactorStore::new(registry.clone());
+                let mut config = config;
                 let mut pending_compaction_checker = CompactionPendingBytesChecker::new(
                     config.clone(),
                     global_discard_ratio,
@@ -161,6 +171,40 @@ impl FlowInfoDispatcher {
                         Ok(Msg::Enable) => {
                             enabled = true;
                         }
+                        Ok(Msg::SetPendingCompactionLimit {
+                            soft_pending_compaction_bytes_limit,
+                            hard_pending_compaction_bytes_limit,
+                            memtables_threshold,
+                            l0_files_threshold,
+                        }) => {
+                            if let Some(v) = soft_pending_compaction_bytes_limit {
+                                config.soft_pending_compaction_bytes_limit = ReadableSize(v);
+                            }
+                            if let Some(v) = hard_pending_compaction_bytes_limit {
+                                config.hard_pending_compaction_bytes_limit = ReadableSize(v);
+                            }
+                            if let Some(v) = memtables_threshold {
+                                config.memtables_threshold = v;
+                            }
+                            if let Some(v) = l0_files_threshold {
+                                config.l0_files_threshold = v;
+                            }
+                            pending_compaction_checker.set_pending_compaction_limit(
+                                soft_pending_compaction_bytes_limit,
+                                hard_pending_compaction_bytes_limit,
+                                memtables_threshold,
+                                l0_files_threshold,
+                            );
+                            let mut checkers = flow_checkers.as_ref().write().unwrap();
+                            for checker in (*checkers).values_mut() {
+                                checker.set_pending_compaction_limit(
+                                    soft_pending_compaction_bytes_limit,
+                                    hard_pending_compaction_bytes_limit,
+                                    memtables_threshold,
+                                    l0_files_threshold,
+                                );
+                            }
+                        }
                         Err(_) => {}
                     }
 
@@ -223,13 +267,19 @@ impl FlowInfoDispatcher {
                                         "region_id" => region_id,
                                         "current_count" => current_count,
                                     );
-                                    e.insert(FlowChecker::new_with_region_id(
+                                    let checker = e.insert(FlowChecker::new_with_region_id(
                                         region_id,
                                         &config,
                                         engine,
                                         limiter.1.clone(),
                                         limiter.0.clone(),
-                                    ))
+                                    ));
+                                    stall_infos
+                                        .as_ref()
+                                        .write()
+                                        .unwrap()
+                                        .insert(region_id, checker.stall_info_handle());
+                                    checker
                                 }
                             };
                         }
@@ -250,6 +300,7 @@ impl FlowInfoDispatcher {
                             }
                             if remove_limiter {
                                 limiters.as_ref().write().unwrap().remove(&region_id);
+                                stall_infos.as_ref().write().unwrap().remove(&region_id);
                                 pending_compaction_checker.on_region_destroy(&region_id);
                                 info!(
                                     "remove FlowChecker";
@@ -355,6 +406,24 @@ impl TabletFlowController {
         self.enabled.load(Ordering::Relaxed)
     }
 
+    pub fn set_pending_compaction_limit(
+        &self,
+        soft_pending_compaction_bytes_limit: Option<u64>,
+        hard_pending_compaction_bytes_limit: Option<u64>,
+        memtables_threshold: Option<u64>,
+        l0_files_threshold: Option<u64>,
+    ) {
+        if let Some(tx) = &self.tx {
+            tx.send(Msg::SetPendingCompactionLimit {
+                soft_pending_compaction_bytes_limit,
+                hard_pending_compaction_bytes_limit,
+                memtables_threshold,
+                l0_files_threshold,
+            })
+            .unwrap();
+        }
+    }
+
     #[cfg(test)]
     pub fn set_speed_limit(&self, region_id: u64, speed_limit: f64) {
         let limiters = self.limiters.as_ref().read().unwrap();
@@ -370,6 +439,14 @@ impl TabletFlowController {
         }
         true
     }
+
+    pub fn stall_info(&self, region_id: u64) -> FlowControlStallInfo {
+        let stall_infos = self.stall_infos.as_ref().read().unwrap();
+        if let Some(stall_info) = stall_infos.get(&region_id) {
+            return stall_info.lock().unwrap().clone();
+        }
+        FlowControlStallInfo::default()
+    }
 }
 
 struct CompactionPendingBytesChecker<E: FlowControlFactorStore + Send + 'static> {
@@ -438,6 +515,21 @@ impl<E: FlowControlFactorStore + Send + 'static> CompactionPendingBytesChecker<E
         self.checker
             .on_pending_compaction_bytes_change_cf(self.total_pending_compaction_bytes(&cf), cf);
     }
+
+    fn set_pending_compaction_limit(
+        &mut self,
+        soft_pending_compaction_bytes_limit: Option<u64>,
+        hard_pending_compaction_bytes_limit: Option<u64>,
+        memtables_threshold: Option<u64>,
+        l0_files_threshold: Option<u64>,
+    ) {
+        self.checker.set_pending_compaction_limit(
+            soft_pending_compaction_bytes_limit,
+            hard_pending_compaction_bytes_limit,
+            memtables_threshold,
+            l0_files_threshold,
+        );
+    }
 }
 
 #[cfg(test)]
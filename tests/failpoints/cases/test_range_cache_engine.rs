@@ -47,6 +47,7 @@ fn test_basic_put_get() {
         epoch_version: 0,
         read_ts: 1001,
         range: None,
+        force_disk_read: false,
     };
     let (tx, rx) = sync_channel(1);
     fail::cfg_callback("on_range_cache_get_value", move || {
@@ -135,6 +136,7 @@ fn test_load() {
         epoch_version: 0,
         read_ts: 20,
         range: None,
+        force_disk_read: false,
     };
 
     for i in 0..30 {
@@ -230,6 +232,7 @@ fn test_load_with_split() {
         epoch_version: 0,
         read_ts: 20,
         range: None,
+        force_disk_read: false,
     };
 
     for i in 0..30 {
@@ -331,6 +334,7 @@ fn test_load_with_split2() {
         epoch_version: 0,
         read_ts: 20,
         range: None,
+        force_disk_read: false,
     };
 
     let _ = cluster
@@ -430,6 +434,7 @@ fn test_load_with_eviction() {
         epoch_version: 0,
         read_ts: u64::MAX,
         range: None,
+        force_disk_read: false,
     };
     let val = cluster
         .get_cf_with_snap_ctx(CF_DEFAULT, b"k01", false, snap_ctx.clone())